@@ -4,9 +4,10 @@ use mimalloc::MiMalloc;
 static GLOBAL: MiMalloc = MiMalloc;
 extern crate karamellib;
 use clap::{Arg, App};
+use std::fs;
 
 
-use karamellib::{constants::{KARAMEL_CONTACT_EMAIL, KARAMEL_HELP_ABOUT, KARAMEL_TITLE, KARAMEL_VERSION}, vm::executer::{ExecutionParameters, ExecutionSource}};
+use karamellib::{constants::{KARAMEL_CONTACT_EMAIL, KARAMEL_HELP_ABOUT, KARAMEL_TITLE, KARAMEL_VERSION}, vm::executer::{ExecutionParameters, ExecutionSource, compile_to_bytecode}, compiler::bytecode::is_bytecode};
 
 fn main() {
     let matches = App::new(KARAMEL_TITLE)
@@ -19,15 +20,40 @@ fn main() {
                                .value_name("FILE")
                                .help("Çalıştırılacak karamel dosyası")
                                .takes_value(true))
+                          .arg(Arg::with_name("compile")
+                               .short("c")
+                               .long("derle")
+                               .value_name("OUTPUT")
+                               .help("Dosyayı çalıştırmadan derlenmiş kod olarak kaydet")
+                               .requires("file")
+                               .takes_value(true))
+                          .arg(Arg::with_name("strict")
+                               .short("k")
+                               .long("kati")
+                               .help("Uyumsuz türler ile yapılan işlemlerde boş yerine hata üret"))
                           .get_matches();
 
+    if let (Some(file), Some(output)) = (matches.value_of("file"), matches.value_of("compile")) {
+        match compile_to_bytecode(ExecutionSource::File(file.to_string())).map(|bytecode| fs::write(output, bytecode)) {
+            Ok(Ok(_)) => println!("Success"),
+            Ok(Err(error)) => println!("Fail: {}", error),
+            Err(error) => println!("Fail: {}", error)
+        };
+        return;
+    }
+
     let parameters = match matches.value_of("file") {
         Some(file) => ExecutionParameters {
-            source: ExecutionSource::File(file.to_string()),
+            /* Compiled files are loaded without parsing */
+            source: match fs::read(file) {
+                Ok(data) if is_bytecode(&data) => ExecutionSource::Bytecode(data),
+                _ => ExecutionSource::File(file.to_string())
+            },
             return_opcode: true,
             return_output: true,
-            dump_opcode: false,
-            dump_memory: false
+            optimize: true,
+            strict_types: matches.is_present("strict"),
+            ..ExecutionParameters::default()
         },
         None => ExecutionParameters {
             source: ExecutionSource::Code(r#"
@@ -40,8 +66,8 @@ döngü i = 0, i < 10, i++:
 "#.to_string()),
             return_opcode: true,
             return_output: true,
-            dump_opcode: false,
-            dump_memory: false
+            optimize: true,
+            ..ExecutionParameters::default()
         }
     };
 
@@ -33,6 +33,11 @@ pub struct BasicInnerClass {
             None => None
         }
     }
+
+
+    fn set_element(&self, _: Option<VmObject>, _: Rc<String>, _: VmObject) -> bool {
+        false
+    }
     
     fn property_count(&self) -> usize {
         self.config.properties.len()
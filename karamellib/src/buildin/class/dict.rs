@@ -1,5 +1,4 @@
 use std::rc::Rc;
-use std::cell::RefCell;
 
 use crate::{buildin::{Class, ClassConfig, ClassProperty}, compiler::{GetType, function::{FunctionParameter, IndexerGetCall, IndexerSetCall, NativeCall, NativeCallResult, FunctionFlag}}};
 use crate::compiler::value::EMPTY_OBJECT;
@@ -76,6 +75,11 @@ impl DictClass {
             }
         }
     }
+
+
+    fn set_element(&self, source: Option<VmObject>, field: Rc<String>, data: VmObject) -> bool {
+        self.base.set_element(source, field, data)
+    }
     
     fn property_count(&self) -> usize {
         self.base.property_count()
@@ -0,0 +1,136 @@
+use crate::compiler::KaramelPrimative;
+use crate::{
+    buildin::{Class, ClassProperty},
+    compiler::function::{IndexerGetCall, IndexerSetCall, NativeCall, FunctionFlag},
+    types::VmObject,
+};
+
+use crate::buildin::ClassConfig;
+use crate::compiler::GetType;
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/* Object that created from user defined class. Fields are stored at instance level, methods are coming from class */
+pub struct ClassInstance {
+    class: Rc<dyn Class>,
+    fields: RefCell<HashMap<String, VmObject>>
+}
+
+impl ClassInstance {
+    pub fn new(class: Rc<dyn Class>, fields: HashMap<String, VmObject>) -> Self {
+        ClassInstance {
+            class,
+            fields: RefCell::new(fields)
+        }
+    }
+
+    pub fn get_class(&self) -> Rc<dyn Class> {
+        self.class.clone()
+    }
+}
+
+impl Class for ClassInstance {
+    fn set_class_config(&mut self, _: ClassConfig) {}
+
+    fn get_class_name(&self) -> String {
+        self.class.get_class_name()
+    }
+
+    fn has_element(&self, source: Option<VmObject>, field: Rc<String>) -> bool {
+        self.fields.borrow().contains_key(&*field) || self.class.has_element(source, field)
+    }
+
+    fn properties(&self) -> std::collections::hash_map::Iter<'_, String, ClassProperty> {
+        self.class.properties()
+    }
+
+    fn get_element(&self, source: Option<VmObject>, field: Rc<String>) -> Option<ClassProperty> {
+        match self.fields.borrow().get(&*field) {
+            Some(data) => Some(ClassProperty::Field(data.deref())),
+            None => self.class.get_element(source, field)
+        }
+    }
+
+    fn set_element(&self, _: Option<VmObject>, field: Rc<String>, data: VmObject) -> bool {
+        self.fields.borrow_mut().insert(field.to_string(), data);
+        true
+    }
+
+    fn property_count(&self) -> usize {
+        self.fields.borrow().len() + self.class.property_count()
+    }
+
+    /* Methods belong to class, instance could not have own method */
+    fn add_method(&mut self, _: &str, _: NativeCall, _: FunctionFlag) {}
+
+    fn add_property(&mut self, name: &str, property: Rc<KaramelPrimative>) {
+        self.fields.borrow_mut().insert(name.to_string(), VmObject::convert(property));
+    }
+
+    fn set_getter(&mut self, _: IndexerGetCall) {}
+
+    fn get_getter(&self) -> Option<IndexerGetCall> {
+        self.class.get_getter()
+    }
+
+    fn set_setter(&mut self, _: IndexerSetCall) {}
+
+    fn get_setter(&self) -> Option<IndexerSetCall> {
+        self.class.get_setter()
+    }
+
+    fn references(&self) -> Vec<VmObject> {
+        self.fields.borrow().values().copied().collect()
+    }
+}
+
+impl GetType for ClassInstance {
+    fn get_type(&self) -> String {
+        self.class.get_class_name()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use crate::buildin::{Class, ClassProperty};
+    use crate::buildin::class::baseclass::BasicInnerClass;
+    use crate::buildin::class::instance::ClassInstance;
+    use crate::compiler::GetType;
+    use crate::compiler::KaramelPrimative;
+    use crate::types::VmObject;
+
+    #[test]
+    fn test_class_instance_1() {
+        let mut class = BasicInnerClass::default();
+        class.set_name("nokta");
+
+        let instance = ClassInstance::new(Rc::new(class), HashMap::new());
+        assert_eq!(instance.get_type(), "nokta".to_string());
+        assert_eq!(instance.get_class_name(), "nokta".to_string());
+        assert_eq!(instance.property_count(), 0);
+    }
+
+    #[test]
+    fn test_class_instance_2() {
+        let mut class = BasicInnerClass::default();
+        class.set_name("nokta");
+        class.add_property("sabit", Rc::new(KaramelPrimative::Number(1.0)));
+
+        let mut fields = HashMap::new();
+        fields.insert("x".to_string(), VmObject::from(10.0));
+
+        let instance = ClassInstance::new(Rc::new(class), fields);
+        assert!(instance.has_element(None, Rc::new("x".to_string())));
+        assert!(instance.has_element(None, Rc::new("sabit".to_string())));
+        assert!(!instance.has_element(None, Rc::new("y".to_string())));
+
+        assert!(instance.set_element(None, Rc::new("y".to_string()), VmObject::from(20.0)));
+        match instance.get_element(None, Rc::new("y".to_string())) {
+            Some(ClassProperty::Field(field)) => assert_eq!(*field, KaramelPrimative::Number(20.0)),
+            _ => assert!(false)
+        };
+        assert_eq!(instance.property_count(), 3);
+    }
+}
@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::rc::Rc;
 
 use crate::{buildin::Class, compiler::function::{FunctionParameter, NativeCallResult}};
@@ -7,7 +8,7 @@ use crate::compiler::value::KaramelPrimative;
 use crate::error::KaramelErrorType;
 use crate::types::VmObject;
 use crate::{n_parameter_expected, expected_parameter_type, arc_bool, arc_empty};
-use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+use crate::buildin::class::{PRIMATIVE_CLASS_NAMES, normalize_index};
 
 pub fn get_primative_class() -> Rc<dyn Class> {
     let mut opcode = BasicInnerClass::default();
@@ -22,6 +23,10 @@ pub fn get_primative_class() -> Rc<dyn Class> {
     opcode.add_class_method("arayaekle", insert);
     opcode.add_class_method("pop", pop);
     opcode.add_class_method("sil", remove);
+    opcode.add_class_method("haritala", map);
+    opcode.add_class_method("filtrele", filter);
+    opcode.add_class_method("sırala", sort);
+    opcode.add_class_method("sirala", sort);
     opcode.set_getter(getter);
     opcode.set_setter(setter);
 
@@ -79,40 +84,25 @@ fn set(parameter: FunctionParameter) -> NativeCallResult {
 }
 
 fn getter(source: VmObject, index: f64) -> NativeCallResult {
-    let index = match index >= 0.0 {
-        true => index as usize,
-        false =>  return Ok(EMPTY_OBJECT)
-    };
-
     if let KaramelPrimative::List(list) = &*source.deref() {
-
-        let is_in_size = index <= list.borrow().len();
-        return match is_in_size {
-            true => match list.borrow().get(index) {
-                Some(item) => Ok(*item),
-                _ => Ok(EMPTY_OBJECT)
-            },
-            false => Ok(arc_empty!())
+        let list = list.borrow();
+        return match normalize_index(index, list.len()) {
+            Some(index) => Ok(list[index]),
+            None => Ok(arc_empty!())
         };
     }
     Ok(EMPTY_OBJECT)
 }
 
 fn setter(source: VmObject, index: f64, item: VmObject) -> NativeCallResult {
-    let index = match index >= 0.0 {
-        true => index as usize,
-        false =>  return Ok(EMPTY_OBJECT)
-    };
-
     if let KaramelPrimative::List(list) = &*source.deref() {
-
-        let is_in_size = index <= list.borrow().len();
-        return match is_in_size {
-            true => {
+        let length = list.borrow().len();
+        return match normalize_index(index, length) {
+            Some(index) => {
                 list.borrow_mut()[index] = item; 
                 Ok(arc_bool!(true))
             },
-            false => Ok(arc_bool!(false))
+            None => Ok(arc_bool!(false))
         };
     }
     Ok(EMPTY_OBJECT)
@@ -209,12 +199,113 @@ fn pop(parameter: FunctionParameter) -> NativeCallResult {
     Ok(EMPTY_OBJECT)
 }
 
+/* Source is not available if the method is called without a list */
+fn get_source(parameter: &FunctionParameter, name: &str) -> Result<VmObject, KaramelErrorType> {
+    parameter.source().ok_or_else(|| KaramelErrorType::MethodSourceNotFound(name.to_string()))
+}
+
+/* Items are copied before the calls, called function could change the list. Copied items are not reachable from the
+list anymore if it changes, so they are kept alive until the function returns. */
+fn map(parameter: FunctionParameter) -> NativeCallResult {
+    let source = get_source(&parameter, "haritala")?;
+    if let KaramelPrimative::List(list) = &*source.deref() {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("haritala".to_string(), 1, parameter.length());
+        }
+
+        let function = *parameter.iter().next().unwrap();
+        let items = list.borrow().clone();
+        parameter.keep_alive(&items);
+        let mut result = Vec::with_capacity(items.len());
+        for item in items {
+            result.push(parameter.call(function, &[item])?);
+        }
+        return Ok(VmObject::from(result));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+fn filter(parameter: FunctionParameter) -> NativeCallResult {
+    let source = get_source(&parameter, "filtrele")?;
+    if let KaramelPrimative::List(list) = &*source.deref() {
+        if parameter.length() != 1 {
+            return n_parameter_expected!("filtrele".to_string(), 1, parameter.length());
+        }
+
+        let function = *parameter.iter().next().unwrap();
+        let items = list.borrow().clone();
+        parameter.keep_alive(&items);
+        let mut result = Vec::new();
+        for item in items {
+            if parameter.call(function, &[item])?.deref().is_true() {
+                result.push(item);
+            }
+        }
+        return Ok(VmObject::from(result));
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+/* Comparison function returns negative number if the first item comes first. Without a function, numbers and texts are sorted. */
+fn sort(parameter: FunctionParameter) -> NativeCallResult {
+    let source = get_source(&parameter, "sırala")?;
+    if let KaramelPrimative::List(list) = &*source.deref() {
+        let function = match parameter.length() {
+            0 => None,
+            1 => Some(*parameter.iter().next().unwrap()),
+            _ => return n_parameter_expected!("sırala".to_string(), 1, parameter.length())
+        };
+
+        let items = list.borrow().clone();
+        parameter.keep_alive(&items);
+        let sorted = merge_sort(items, &mut |left, right| match function {
+            Some(function) => match parameter.call(function, &[left, right])?.as_number() {
+                Some(number) => Ok(number.partial_cmp(&0.0).unwrap_or(Ordering::Equal)),
+                None => expected_parameter_type!("sırala".to_string(), "Sayı".to_string())
+            },
+            None => match (&*left.deref(), &*right.deref()) {
+                (KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => Ok(left.partial_cmp(right).unwrap_or(Ordering::Equal)),
+                (KaramelPrimative::Text(left), KaramelPrimative::Text(right)) => Ok(left.cmp(right)),
+                _ => expected_parameter_type!("sırala".to_string(), "Karşılaştırma fonksiyonu".to_string())
+            }
+        })?;
+
+        *list.borrow_mut() = sorted;
+        return Ok(source);
+    }
+    Ok(EMPTY_OBJECT)
+}
+
+/* Stable sort that stops at the first comparison error */
+fn merge_sort(mut items: Vec<VmObject>, compare: &mut dyn FnMut(VmObject, VmObject) -> Result<Ordering, KaramelErrorType>) -> Result<Vec<VmObject>, KaramelErrorType> {
+    if items.len() < 2 {
+        return Ok(items);
+    }
+
+    let right = merge_sort(items.split_off(items.len() / 2), compare)?;
+    let left = merge_sort(items, compare)?;
+
+    let mut result = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(left_item), Some(right_item)) = (left.peek(), right.peek()) {
+        match compare(*left_item, *right_item)? {
+            Ordering::Greater => result.push(right.next().unwrap()),
+            _ => result.push(left.next().unwrap())
+        };
+    }
+
+    result.extend(left);
+    result.extend(right);
+    Ok(result)
+}
 
 #[cfg(test)]
 mod tests {
     use std::rc::Rc;
     use crate::compiler::value::KaramelPrimative;
     use super::*;
+    use crate::vm::io::BufferedIo;
 
     use crate::nativecall_test_with_params;
     use crate::nativecall_test;
@@ -238,12 +329,11 @@ mod tests {
     fn test_add_3 () {
         use std::cell::RefCell;
         let stack: Vec<VmObject> = [arc_text!("merhaba")].to_vec();
-        let stdout = Some(RefCell::new(String::new()));
-        let stderr = Some(RefCell::new(String::new()));
+        let io = BufferedIo::new();
         let list = KaramelPrimative::List(RefCell::new(Vec::new()));
         let obj = VmObject::native_convert(list);
         
-        let parameter = FunctionParameter::new(&stack, Some(obj), stack.len() as usize, stack.len() as u8, &stdout, &stderr);
+        let parameter = FunctionParameter::new(&stack, Some(obj), stack.len() as usize, stack.len() as u8, &io);
         let result = add(parameter);
         assert!(result.is_ok());
 
@@ -253,15 +343,34 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_map_without_context () {
+        use std::cell::RefCell;
+        let stack: Vec<VmObject> = [arc_number!(1)].to_vec();
+        let io = BufferedIo::new();
+        let obj = VmObject::native_convert(KaramelPrimative::List(RefCell::new([arc_number!(1)].to_vec())));
+
+        let parameter = FunctionParameter::new(&stack, Some(obj), stack.len() as usize, stack.len() as u8, &io);
+        assert_eq!(map(parameter), Err(KaramelErrorType::FunctionCallNotAvailable));
+    }
+
+    #[test]
+    fn test_map_without_source () {
+        let stack: Vec<VmObject> = [arc_number!(1)].to_vec();
+        let io = BufferedIo::new();
+
+        let parameter = FunctionParameter::new(&stack, None, stack.len() as usize, stack.len() as u8, &io);
+        assert_eq!(map(parameter), Err(KaramelErrorType::MethodSourceNotFound("haritala".to_string())));
+    }
+
     #[test]
     fn test_insert_1 () {
         use std::cell::RefCell;
-        let stdout = Some(RefCell::new(String::new()));
-        let stderr = Some(RefCell::new(String::new()));
+        let io = BufferedIo::new();
         let list = Rc::new(KaramelPrimative::List(RefCell::new(Vec::new())));
         let obj = VmObject::native_convert_by_ref(list.clone());
         
-        let result = add(FunctionParameter::new(&[arc_text!("dünya")].to_vec(), Some(obj), 1 as usize, 1 as u8, &stdout, &stderr));
+        let result = add(FunctionParameter::new(&[arc_text!("dünya")].to_vec(), Some(obj), 1 as usize, 1 as u8, &io));
         assert!(result.is_ok());
 
         match &*list {
@@ -269,7 +378,7 @@ mod tests {
             _ => assert_eq!(true, false)
         };
 
-        let result = insert(FunctionParameter::new(&[arc_number!(0), arc_text!("merhaba")].to_vec(), Some(obj), 2 as usize, 2 as u8, &stdout, &stderr));
+        let result = insert(FunctionParameter::new(&[arc_number!(0), arc_text!("merhaba")].to_vec(), Some(obj), 2 as usize, 2 as u8, &io));
         assert!(result.is_ok());
 
         match &*list {
@@ -286,16 +395,15 @@ mod tests {
     fn test_clear_1 () {
         use std::cell::RefCell;
         let stack: Vec<VmObject> = Vec::new();
-        let stdout = Some(RefCell::new(String::new()));
-        let stderr = Some(RefCell::new(String::new()));
+        let io = BufferedIo::new();
         let list = Rc::new(KaramelPrimative::List(RefCell::new([arc_bool!(true), arc_empty!(), arc_number!(1)].to_vec())));
         let obj = VmObject::native_convert_by_ref(list.clone());
         
-        let result = add(FunctionParameter::new(&[arc_text!("dünya")].to_vec(), Some(obj), 1 as usize, 1 as u8, &stdout, &stderr));
+        let result = add(FunctionParameter::new(&[arc_text!("dünya")].to_vec(), Some(obj), 1 as usize, 1 as u8, &io));
         assert!(result.is_ok());
 
 
-        let parameter = FunctionParameter::new(&stack, Some(obj), stack.len() as usize, stack.len() as u8, &stdout, &stderr);
+        let parameter = FunctionParameter::new(&stack, Some(obj), stack.len() as usize, stack.len() as u8, &io);
         let result = clear(parameter);
         assert!(result.is_ok());
 
@@ -4,8 +4,10 @@ pub mod list;
 pub mod dict;
 pub mod baseclass;
 pub mod proxy;
+pub mod instance;
 
 use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::error::KaramelErrorType;
 use std::{collections::HashSet, rc::Rc};
 use lazy_static::*;
 
@@ -23,18 +25,65 @@ pub fn get_empty_class() -> Rc<dyn Class> {
     Rc::new(opcode)
 }
 
+/// Negative index counts from the end. Returns None if index is out of range.
+pub fn normalize_index(index: f64, length: usize) -> Option<usize> {
+    let index = index.trunc() as i64;
+    let index = match index < 0 {
+        true => index + length as i64,
+        false => index
+    };
+
+    match index >= 0 && (index as usize) < length {
+        true => Some(index as usize),
+        false => None
+    }
+}
+
+/// Calculate item positions for 'x[start:end:step]'. Empty parts are defaulted by step direction.
+pub fn slice_indices(length: usize, start: Option<f64>, end: Option<f64>, step: Option<f64>) -> Result<Vec<usize>, KaramelErrorType> {
+    let length = length as i64;
+    let step = step.map(|step| step.trunc() as i64).unwrap_or(1);
+    if step == 0 {
+        return Err(KaramelErrorType::SliceStepCannotBeZero);
+    }
+
+    let limit = |position: Option<f64>, default: i64| -> i64 {
+        match position {
+            Some(position) => {
+                let position = position.trunc() as i64;
+                let position = if position < 0 { position + length } else { position };
+                match step > 0 {
+                    true => position.clamp(0, length),
+                    false => position.clamp(-1, length - 1)
+                }
+            },
+            None => default
+        }
+    };
+
+    let (mut index, end) = match step > 0 {
+        true => (limit(start, 0), limit(end, length)),
+        false => (limit(start, length - 1), limit(end, -1))
+    };
+
+    let mut indices = Vec::new();
+    while (step > 0 && index < end) || (step < 0 && index > end) {
+        indices.push(index as usize);
+        index += step;
+    }
+
+    Ok(indices)
+}
 
 #[macro_export]
 macro_rules! nativecall_test {
     ($name:ident, $function_name:ident, $query:expr, $result:expr) => {
         #[test]
         fn $name () {
-            use std::cell::RefCell;
             let stack: Vec<VmObject> = Vec::new();
-            let stdout = Some(RefCell::new(String::new()));
-            let stderr = Some(RefCell::new(String::new()));
+            let io = $crate::vm::io::BufferedIo::new();
             
-            let parameter = FunctionParameter::new(&stack, Some(VmObject::native_convert($query)), 0, 0, &stdout, &stderr);
+            let parameter = FunctionParameter::new(&stack, Some(VmObject::native_convert($query)), 0, 0, &io);
             let result = $function_name(parameter);
             assert!(result.is_ok());
             let object = result.unwrap().deref();
@@ -60,7 +109,7 @@ macro_rules! primative_number {
 #[macro_export]
 macro_rules! primative_list {
     ($list:expr) => {
-        KaramelPrimative::List(RefCell::new($list))
+        KaramelPrimative::List(std::cell::RefCell::new($list))
     };
 }
 
@@ -97,12 +146,10 @@ macro_rules! nativecall_test_with_params {
     ($name:ident, $function_name:ident, $query:expr, $params:expr, $result:expr) => {
         #[test]
         fn $name () {
-            use std::cell::RefCell;
             let stack: Vec<VmObject> = $params.to_vec();
-            let stdout = Some(RefCell::new(String::new()));
-            let stderr = Some(RefCell::new(String::new()));
+            let io = $crate::vm::io::BufferedIo::new();
             
-            let parameter = FunctionParameter::new(&stack, Some(VmObject::native_convert($query)), stack.len() as usize, stack.len() as u8, &stdout, &stderr);
+            let parameter = FunctionParameter::new(&stack, Some(VmObject::native_convert($query)), stack.len() as usize, stack.len() as u8, &io);
             let result = $function_name(parameter);
             assert!(result.is_ok());
             let object = result.unwrap().deref();
@@ -141,3 +188,33 @@ macro_rules! expected_parameter_type {
         expected: $expected_type
     }) };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::error::KaramelErrorType;
+    use super::{normalize_index, slice_indices};
+
+    #[test]
+    fn index_test() {
+        assert_eq!(normalize_index(0.0, 3), Some(0));
+        assert_eq!(normalize_index(-1.0, 3), Some(2));
+        assert_eq!(normalize_index(-3.0, 3), Some(0));
+        assert_eq!(normalize_index(-4.0, 3), None);
+        assert_eq!(normalize_index(3.0, 3), None);
+        assert_eq!(normalize_index(0.0, 0), None);
+    }
+
+    #[test]
+    fn slice_test() {
+        assert_eq!(slice_indices(5, Some(1.0), Some(3.0), None), Ok(vec![1, 2]));
+        assert_eq!(slice_indices(5, None, None, None), Ok(vec![0, 1, 2, 3, 4]));
+        assert_eq!(slice_indices(5, Some(-2.0), None, None), Ok(vec![3, 4]));
+        assert_eq!(slice_indices(5, None, Some(-1.0), Some(2.0)), Ok(vec![0, 2]));
+        assert_eq!(slice_indices(5, None, None, Some(-1.0)), Ok(vec![4, 3, 2, 1, 0]));
+        assert_eq!(slice_indices(5, Some(3.0), Some(0.0), Some(-2.0)), Ok(vec![3, 1]));
+        assert_eq!(slice_indices(5, Some(10.0), Some(20.0), None), Ok(vec![]));
+        assert_eq!(slice_indices(5, Some(-10.0), Some(2.0), None), Ok(vec![0, 1]));
+        assert_eq!(slice_indices(0, None, None, Some(-1.0)), Ok(vec![]));
+        assert_eq!(slice_indices(5, None, None, Some(0.0)), Err(KaramelErrorType::SliceStepCannotBeZero));
+    }
+}
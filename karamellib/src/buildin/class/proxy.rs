@@ -45,6 +45,16 @@ fn get_element(&self, source: Option<VmObject>, field: Rc<String>) -> Option<Cla
         }
     }
 
+    fn set_element(&self, source: Option<VmObject>, field: Rc<String>, data: VmObject) -> bool {
+        match source {
+            Some(source_object) => match &*source_object.deref() {
+                KaramelPrimative::Class(class) => class.set_element(source, field, data),
+                _ => false
+            },
+            None => false,
+        }
+    }
+
     fn property_count(&self) -> usize {
         0
     }
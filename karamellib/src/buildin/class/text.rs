@@ -2,10 +2,10 @@ use crate::{buildin::Class, compiler::function::{FunctionParameter, NativeCallRe
 use crate::compiler::value::EMPTY_OBJECT;
 use crate::buildin::class::baseclass::BasicInnerClass;
 use crate::compiler::value::KaramelPrimative;
-use crate::types::VmObject;
+use crate::types::{VmObject, POINTER_MASK};
 use crate::{n_parameter_expected, expected_parameter_type, arc_text};
 use crate::primative_text;
-use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+use crate::buildin::class::{PRIMATIVE_CLASS_NAMES, normalize_index};
 use crate::error::KaramelErrorType;
 
 use unicode_width::UnicodeWidthStr;
@@ -50,12 +50,11 @@ pub fn get_primative_class() -> Rc<dyn Class> {
 
 
 fn getter(source: VmObject, index: f64) -> NativeCallResult {
-    let index = match index >= 0.0 {
-        true => index as usize,
-        false =>  return Ok(EMPTY_OBJECT)
-    };
-    
     if let KaramelPrimative::Text(text) = &*source.deref() {
+        let index = match normalize_index(index, text.chars().count()) {
+            Some(index) => index,
+            None => return Ok(EMPTY_OBJECT)
+        };
 
         return match text.chars().nth(index) {
             Some(item) => Ok(arc_text!(item.to_string())),
@@ -66,12 +65,12 @@ fn getter(source: VmObject, index: f64) -> NativeCallResult {
 }
 
 fn setter(source: VmObject, index: f64, item: VmObject) -> NativeCallResult {
-    let index = match index >= 0.0 {
-        true => index as usize,
-        false =>  return Ok(EMPTY_OBJECT)
-    };
-
     if let KaramelPrimative::Text(text) = &*source.deref() {
+        let index = match normalize_index(index, text.chars().count()) {
+            Some(index) => index,
+            None => return Ok(EMPTY_OBJECT)
+        };
+
         return match text.chars().nth(index) {
             Some(old_char) => {
                 match &*item.deref() {
@@ -99,8 +98,10 @@ fn setter(source: VmObject, index: f64, item: VmObject) -> NativeCallResult {
 
                         unsafe {
                             /* Update text with new one */
-                            let text_ptr = text as *const Rc<String> as *mut Rc<String>;
-                            *Rc::make_mut(&mut *text_ptr) = new_string;
+                            let primative = &mut *((source.0 & POINTER_MASK) as *mut KaramelPrimative);
+                            if let KaramelPrimative::Text(text) = primative {
+                                *Rc::make_mut(text) = new_string;
+                            }
                         }
 
                         Ok(EMPTY_OBJECT)
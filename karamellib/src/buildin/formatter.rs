@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::compiler::value::KaramelPrimative;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+
+/* Bigger width and precision values could not be allocated */
+const MAX_FORMAT_SIZE: usize = u16::MAX as usize;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Alignment {
+    Left,
+    Right,
+    Center
+}
+
+/* Placeholder format information. Syntax is '[[fill]align][width][.precision]' */
+#[derive(Debug, Default, PartialEq)]
+struct FormatSpec {
+    fill: Option<char>,
+    alignment: Option<Alignment>,
+    width: Option<usize>,
+    precision: Option<usize>
+}
+
+impl FormatSpec {
+    fn parse(spec: &str) -> Result<FormatSpec, KaramelErrorType> {
+        let mut result = FormatSpec::default();
+        let chars = spec.chars().collect::<Vec<char>>();
+        let mut index = 0;
+
+        /* Fill character could be used only with alignment */
+        if chars.len() > 1 && Self::to_alignment(chars[1]).is_some() {
+            result.fill = Some(chars[0]);
+            result.alignment = Self::to_alignment(chars[1]);
+            index = 2;
+        }
+        else if !chars.is_empty() && Self::to_alignment(chars[0]).is_some() {
+            result.alignment = Self::to_alignment(chars[0]);
+            index = 1;
+        }
+
+        let width = Self::read_number(&chars, &mut index);
+        if !width.is_empty() {
+            result.width = Some(Self::parse_size(&width)?);
+        }
+
+        if index < chars.len() && chars[index] == '.' {
+            index += 1;
+            let precision = Self::read_number(&chars, &mut index);
+            if precision.is_empty() {
+                return Err(KaramelErrorType::FormatNotValid);
+            }
+            result.precision = Some(Self::parse_size(&precision)?);
+        }
+
+        match index == chars.len() {
+            true => Ok(result),
+            false => Err(KaramelErrorType::FormatNotValid)
+        }
+    }
+
+    fn read_number(chars: &[char], index: &mut usize) -> String {
+        let mut number = String::new();
+        while *index < chars.len() && chars[*index].is_ascii_digit() {
+            number.push(chars[*index]);
+            *index += 1;
+        }
+        number
+    }
+
+    fn parse_size(number: &str) -> Result<usize, KaramelErrorType> {
+        match number.parse::<usize>() {
+            Ok(size) if size <= MAX_FORMAT_SIZE => Ok(size),
+            _ => Err(KaramelErrorType::FormatNotValid)
+        }
+    }
+
+    fn to_alignment(ch: char) -> Option<Alignment> {
+        match ch {
+            '<' => Some(Alignment::Left),
+            '>' => Some(Alignment::Right),
+            '^' => Some(Alignment::Center),
+            _ => None
+        }
+    }
+
+    fn apply(&self, value: &KaramelPrimative) -> String {
+        let text = match (value, self.precision) {
+            (KaramelPrimative::Number(number), Some(precision)) => format!("{:.*}", precision, number),
+            (KaramelPrimative::Text(text), Some(precision)) => text.chars().take(precision).collect(),
+            _ => value.to_display_text()
+        };
+
+        let width = match self.width {
+            Some(width) => width,
+            None => return text
+        };
+
+        let length = text.chars().count();
+        if length >= width {
+            return text;
+        }
+
+        /* Numbers are aligned to right by default */
+        let alignment = match (self.alignment, value) {
+            (Some(alignment), _) => alignment,
+            (None, KaramelPrimative::Number(_)) => Alignment::Right,
+            (None, _) => Alignment::Left
+        };
+
+        let fill = self.fill.unwrap_or(' ').to_string();
+        let padding = width - length;
+        match alignment {
+            Alignment::Left => format!("{}{}", text, fill.repeat(padding)),
+            Alignment::Right => format!("{}{}", fill.repeat(padding), text),
+            Alignment::Center => format!("{}{}{}", fill.repeat(padding / 2), text, fill.repeat(padding - padding / 2))
+        }
+    }
+}
+
+/// Format text with positional ('{}', '{0}') and named ('{ad}') placeholders.
+/// Named placeholders are searched in the last argument, if it is a dictionary.
+pub fn format_text(template: &str, arguments: &[VmObject]) -> Result<String, KaramelErrorType> {
+    let named = match arguments.last().map(|argument| argument.deref()) {
+        Some(last) => match &*last {
+            KaramelPrimative::Dict(dict) => Some(dict.borrow().clone()),
+            _ => None
+        },
+        None => None
+    };
+
+    let mut buffer = String::new();
+    let mut chars = template.chars().peekable();
+    let mut next_position = 0;
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                buffer.push('{');
+            },
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                buffer.push('}');
+            },
+            '}' => return Err(KaramelErrorType::FormatNotValid),
+            '{' => {
+                let placeholder = read_placeholder(&mut chars)?;
+                let (name, spec) = match placeholder.find(':') {
+                    Some(index) => (&placeholder[..index], &placeholder[index + 1..]),
+                    None => (&placeholder[..], "")
+                };
+
+                let value = find_argument(name.trim(), arguments, &named, &mut next_position)?;
+                buffer.push_str(&FormatSpec::parse(spec)?.apply(&value.deref()));
+            },
+            _ => buffer.push(ch)
+        };
+    }
+
+    Ok(buffer)
+}
+
+fn read_placeholder(chars: &mut Peekable<Chars>) -> Result<String, KaramelErrorType> {
+    let mut placeholder = String::new();
+    for ch in chars {
+        match ch {
+            '}' => return Ok(placeholder),
+            '{' => return Err(KaramelErrorType::FormatNotValid),
+            _ => placeholder.push(ch)
+        };
+    }
+
+    Err(KaramelErrorType::FormatNotValid)
+}
+
+fn find_argument(name: &str, arguments: &[VmObject], named: &Option<HashMap<String, VmObject>>, next_position: &mut usize) -> Result<VmObject, KaramelErrorType> {
+    let position = match name.is_empty() {
+        true => {
+            *next_position += 1;
+            Some(*next_position - 1)
+        },
+        false => name.parse::<usize>().ok()
+    };
+
+    let value = match position {
+        Some(position) => arguments.get(position).copied(),
+        None => named.as_ref().and_then(|named| named.get(name).copied())
+    };
+
+    /* Implicit placeholders are reported with their positions */
+    match (value, position) {
+        (Some(value), _) => Ok(value),
+        (None, Some(position)) => Err(KaramelErrorType::FormatArgumentNotFound(position.to_string())),
+        (None, None) => Err(KaramelErrorType::FormatArgumentNotFound(name.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use crate::error::KaramelErrorType;
+    use crate::types::VmObject;
+    use super::format_text;
+
+    fn text(value: &str) -> VmObject {
+        VmObject::from(Rc::new(value.to_string()))
+    }
+
+    #[test]
+    fn positional() {
+        assert_eq!(format_text("{} {}", &[text("merhaba"), VmObject::from(1.0)]), Ok("merhaba 1".to_string()));
+        assert_eq!(format_text("{1} {0}", &[text("dünya"), text("merhaba")]), Ok("merhaba dünya".to_string()));
+        assert_eq!(format_text("{{}} {}", &[VmObject::from(true)]), Ok("{} doğru".to_string()));
+    }
+
+    #[test]
+    fn named() {
+        let mut values = HashMap::new();
+        values.insert("ad".to_string(), text("erhan"));
+        assert_eq!(format_text("{ad} {0:.2}", &[VmObject::from(3.14159), VmObject::from(values)]), Ok("erhan 3.14".to_string()));
+    }
+
+    #[test]
+    fn width_and_alignment() {
+        assert_eq!(format_text("[{:5}]", &[VmObject::from(42.0)]), Ok("[   42]".to_string()));
+        assert_eq!(format_text("[{:5}]", &[text("ab")]), Ok("[ab   ]".to_string()));
+        assert_eq!(format_text("[{:^6}]", &[text("ab")]), Ok("[  ab  ]".to_string()));
+        assert_eq!(format_text("[{:0>6.1}]", &[VmObject::from(2.26)]), Ok("[0002.3]".to_string()));
+        assert_eq!(format_text("[{:*<4}]", &[VmObject::from(7.0)]), Ok("[7***]".to_string()));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(format_text("{", &[]), Err(KaramelErrorType::FormatNotValid));
+        assert_eq!(format_text("}", &[]), Err(KaramelErrorType::FormatNotValid));
+        assert_eq!(format_text("{:x}", &[VmObject::from(1.0)]), Err(KaramelErrorType::FormatNotValid));
+        assert_eq!(format_text("{ad}", &[VmObject::from(1.0)]), Err(KaramelErrorType::FormatArgumentNotFound("ad".to_string())));
+        assert_eq!(format_text("{2}", &[VmObject::from(1.0)]), Err(KaramelErrorType::FormatArgumentNotFound("2".to_string())));
+        assert_eq!(format_text("{} {}", &[VmObject::from(1.0)]), Err(KaramelErrorType::FormatArgumentNotFound("1".to_string())));
+        assert_eq!(format_text("{:>100000000000}", &[VmObject::from(1.0)]), Err(KaramelErrorType::FormatNotValid));
+        assert_eq!(format_text("{:.65536}", &[VmObject::from(1.0)]), Err(KaramelErrorType::FormatNotValid));
+        assert_eq!(format_text("{:65535}", &[VmObject::from(1.0)]).map(|text| text.len()), Ok(65535));
+    }
+}
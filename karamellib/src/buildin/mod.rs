@@ -2,6 +2,8 @@ pub mod debug;
 pub mod io;
 pub mod num;
 pub mod base_functions;
+pub mod formatter;
+pub mod native_module;
 
 use std::collections::hash_map::Iter;
 
@@ -33,6 +35,7 @@ pub struct ModuleCollectionIterator<'a> {
     iter: Iter<'a, String, Rc<dyn Module>>
 }
 
+#[derive(Clone)]
 pub struct ModuleCollection {
     modules: HashMap<String, Rc<dyn Module>>
 }
@@ -98,6 +101,7 @@ pub trait Class: GetType {
     
     fn has_element(&self, source: Option<VmObject>, field: Rc<String>) -> bool;
     fn get_element(&self, source: Option<VmObject>, field: Rc<String>) -> Option<ClassProperty>;
+    fn set_element(&self, source: Option<VmObject>, field: Rc<String>, data: VmObject) -> bool;
     fn property_count(&self) -> usize;
     fn properties(&self) -> std::collections::hash_map::Iter<'_, String, ClassProperty>;
     
@@ -109,6 +113,9 @@ pub trait Class: GetType {
     
     fn set_setter(&mut self, indexer: IndexerSetCall);
     fn get_setter(&self) -> Option<IndexerSetCall>;
+
+    /* Values that kept by the object, used by the garbage collector */
+    fn references(&self) -> Vec<VmObject> { Vec::new() }
 }
 
 pub struct DummyModule {
@@ -0,0 +1,226 @@
+use crate::buildin::{Module, Class};
+use crate::compiler::function::{FunctionParameter, FunctionReference, NativeCallResult, NativeClosure};
+use crate::error::KaramelErrorType;
+use std::{cell::RefCell, collections::HashMap};
+use std::rc::Rc;
+
+/// Number of the arguments that a native function accepts. Calls with a different argument count
+/// fail before the function is executed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArgumentCount {
+    Any,
+    Exact(u8),
+    AtLeast(u8),
+    Between(u8, u8)
+}
+
+impl ArgumentCount {
+    /* Argument count that should be reported with the error */
+    fn expected(&self, found: u8) -> Option<u8> {
+        match *self {
+            ArgumentCount::Any => None,
+            ArgumentCount::Exact(count) if count != found => Some(count),
+            ArgumentCount::AtLeast(min) if found < min => Some(min),
+            ArgumentCount::Between(min, _) if found < min => Some(min),
+            ArgumentCount::Between(_, max) if found > max => Some(max),
+            _ => None
+        }
+    }
+}
+
+/// ASCII spelling of the name, 'satıryaz' is written as 'satiryaz'
+pub fn ascii_name(name: &str) -> String {
+    name.chars().map(|ch| match ch {
+        'ı' => 'i', 'İ' => 'I',
+        'ğ' => 'g', 'Ğ' => 'G',
+        'ü' => 'u', 'Ü' => 'U',
+        'ş' => 's', 'Ş' => 'S',
+        'ö' => 'o', 'Ö' => 'O',
+        'ç' => 'c', 'Ç' => 'C',
+        ch => ch
+    }).collect()
+}
+
+/// Module that made of native functions. Modules are created with 'ModuleBuilder'.
+pub struct NativeModule {
+    name: String,
+    path: Vec<String>,
+    methods: RefCell<HashMap<String, Rc<FunctionReference>>>,
+    modules: HashMap<String, Rc<dyn Module>>,
+    classes: Vec<Rc<dyn Class>>
+}
+
+impl NativeModule {
+    /// Add the function with its ASCII spelling. Argument count is checked before the function is called.
+    pub fn add_function<F>(self: &Rc<Self>, name: &str, arguments: ArgumentCount, function: F) where F: Fn(FunctionParameter) -> NativeCallResult + 'static {
+        self.insert_function(name, arguments, Rc::new(function));
+    }
+
+    fn insert_function(self: &Rc<Self>, name: &str, arguments: ArgumentCount, function: NativeClosure) {
+        let function_name = name.to_string();
+        let function: NativeClosure = Rc::new(move |parameter: FunctionParameter| {
+            match arguments.expected(parameter.length()) {
+                Some(expected) => Err(KaramelErrorType::FunctionArgumentNotMatching {
+                    function: function_name.to_string(),
+                    expected,
+                    found: parameter.length()
+                }),
+                None => function(parameter)
+            }
+        });
+
+        let mut methods = self.methods.borrow_mut();
+        methods.insert(name.to_string(), FunctionReference::native_closure(function.clone(), name.to_string(), self.clone()));
+
+        let ascii = ascii_name(name);
+        if ascii != name {
+            methods.insert(ascii.to_string(), FunctionReference::native_closure(function, ascii, self.clone()));
+        }
+    }
+}
+
+impl Module for NativeModule {
+    fn get_module_name(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn get_path(&self) -> &Vec<String> {
+        &self.path
+    }
+
+    fn get_method(&self, name: &str) -> Option<Rc<FunctionReference>> {
+        self.methods.borrow().get(name).cloned()
+    }
+
+    fn get_module(&self, name: &str) -> Option<Rc<dyn Module>> {
+        self.modules.get(name).cloned()
+    }
+
+    fn get_methods(&self) -> Vec<Rc<FunctionReference>> {
+        self.methods.borrow().values().cloned().collect()
+    }
+
+    fn get_modules(&self) -> HashMap<String, Rc<dyn Module>> {
+        self.modules.clone()
+    }
+
+    fn get_classes(&self) -> Vec<Rc<dyn Class>> {
+        self.classes.clone()
+    }
+}
+
+struct FunctionDefinition {
+    name: String,
+    arguments: ArgumentCount,
+    function: NativeClosure
+}
+
+/// Declare the module's functions in one place and create the module.
+///
+/// ```
+/// use karamellib::buildin::native_module::{ArgumentCount, ModuleBuilder};
+/// use karamellib::compiler::value::EMPTY_OBJECT;
+///
+/// let module = ModuleBuilder::new("ölçüm")
+///     .function("başlat", ArgumentCount::Exact(0), |_| Ok(EMPTY_OBJECT))
+///     .build();
+/// ```
+pub struct ModuleBuilder {
+    name: String,
+    path: Vec<String>,
+    functions: Vec<FunctionDefinition>,
+    modules: HashMap<String, Rc<dyn Module>>,
+    classes: Vec<Rc<dyn Class>>
+}
+
+impl ModuleBuilder {
+    /// Module is used with its name, e.g. 'gç::satıryaz'
+    pub fn new(name: &str) -> Self {
+        ModuleBuilder {
+            name: name.to_string(),
+            path: vec![name.to_string()],
+            functions: Vec::new(),
+            modules: HashMap::new(),
+            classes: Vec::new()
+        }
+    }
+
+    /// Functions of the module that has an empty path are called without the module name
+    pub fn path(mut self, path: &[&str]) -> Self {
+        self.path = path.iter().map(|item| item.to_string()).collect();
+        self
+    }
+
+    pub fn function<F>(mut self, name: &str, arguments: ArgumentCount, function: F) -> Self where F: Fn(FunctionParameter) -> NativeCallResult + 'static {
+        self.functions.push(FunctionDefinition {
+            name: name.to_string(),
+            arguments,
+            function: Rc::new(function)
+        });
+        self
+    }
+
+    pub fn module(mut self, module: Rc<dyn Module>) -> Self {
+        self.modules.insert(module.get_module_name(), module);
+        self
+    }
+
+    pub fn class(mut self, class: Rc<dyn Class>) -> Self {
+        self.classes.push(class);
+        self
+    }
+
+    pub fn build(self) -> Rc<NativeModule> {
+        let module = Rc::new(NativeModule {
+            name: self.name,
+            path: self.path,
+            methods: RefCell::new(HashMap::new()),
+            modules: self.modules,
+            classes: self.classes
+        });
+
+        for definition in self.functions.into_iter() {
+            module.insert_function(&definition.name, definition.arguments, definition.function);
+        }
+
+        module
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_names() {
+        assert_eq!(ascii_name("satıryaz"), "satiryaz");
+        assert_eq!(ascii_name("ÇĞİÖŞÜçğıöşü"), "CGIOSUcgiosu");
+        assert_eq!(ascii_name("yaz"), "yaz");
+    }
+
+    #[test]
+    fn argument_count() {
+        assert_eq!(ArgumentCount::Any.expected(10), None);
+        assert_eq!(ArgumentCount::Exact(1).expected(1), None);
+        assert_eq!(ArgumentCount::Exact(1).expected(2), Some(1));
+        assert_eq!(ArgumentCount::AtLeast(1).expected(0), Some(1));
+        assert_eq!(ArgumentCount::AtLeast(1).expected(5), None);
+        assert_eq!(ArgumentCount::Between(1, 2).expected(0), Some(1));
+        assert_eq!(ArgumentCount::Between(1, 2).expected(3), Some(2));
+        assert_eq!(ArgumentCount::Between(1, 2).expected(2), None);
+    }
+
+    #[test]
+    fn aliases() {
+        let module = ModuleBuilder::new("ölçüm")
+            .function("başlat", ArgumentCount::Exact(0), |_| Ok(crate::compiler::value::EMPTY_OBJECT))
+            .function("dur", ArgumentCount::Any, |_| Ok(crate::compiler::value::EMPTY_OBJECT))
+            .build();
+
+        assert_eq!(module.get_methods().len(), 3);
+        assert_eq!(module.get_method("baslat").map(|function| function.name.to_string()), Some("baslat".to_string()));
+        assert!(module.get_method("başlat").is_some());
+        assert!(module.get_method("dur").is_some());
+        assert_eq!(module.get_path(), &vec!["ölçüm".to_string()]);
+    }
+}
@@ -1,70 +1,22 @@
-use crate::compiler::{function::{FunctionParameter, FunctionReference, NativeCall, NativeCallResult}};
+use crate::compiler::{function::{FunctionParameter, NativeCallResult}};
 use crate::types::VmObject;
 use crate::compiler::value::KaramelPrimative;
 use crate::compiler::value::EMPTY_OBJECT;
 use crate::error::KaramelErrorType;
-use crate::buildin::{Module, Class};
-use crate::{n_parameter_expected, expected_parameter_type};
-use std::{cell::RefCell, collections::HashMap};
+use crate::buildin::native_module::{ArgumentCount, ModuleBuilder, NativeModule};
+use crate::expected_parameter_type;
 use std::rc::Rc;
 
-pub struct NumModule {
-    methods: RefCell<HashMap<String, Rc<FunctionReference>>>,
-    path: Vec<String>
-}
-
-impl Module for NumModule {
-    fn get_module_name(&self) -> String {
-        "sayı".to_string()
-    }
-
-    fn get_path(&self) -> &Vec<String> {
-        &self.path
-    }
-
-    fn get_method(&self, name: &str) -> Option<Rc<FunctionReference>> {
-        match self.methods.borrow().get(name) {
-            Some(method) => Some(method.clone()),
-            None => None
-        }
-    }
-
-    fn get_module(&self, _: &str) -> Option<Rc<dyn Module>> {
-        None
-    }
-
-    fn get_methods(&self) -> Vec<Rc<FunctionReference>> {
-        let mut response = Vec::new();
-        self.methods.borrow().iter().for_each(|(_, reference)| response.push(reference.clone()));
-        response
-    }
-
-    fn get_modules(&self) -> HashMap<String, Rc<dyn Module>> {
-        HashMap::new()
-    }
-    
-    fn get_classes(&self) -> Vec<Rc<dyn Class>> {
-        Vec::new()
-    }
-}
+pub struct NumModule;
 
 impl NumModule {
-    pub fn new() -> Rc<NumModule> {
-        let module = NumModule {
-            methods: RefCell::new(HashMap::new()),
-            path: vec!["sayı".to_string()]
-        };
-
-        let rc_module = Rc::new(module);
-        rc_module.methods.borrow_mut().insert("oku".to_string(), FunctionReference::native_function(Self::parse as NativeCall, "tür_bilgisi".to_string(), rc_module.clone()));
-        rc_module.clone()
+    pub fn new() -> Rc<NativeModule> {
+        ModuleBuilder::new("sayı")
+            .function("oku", ArgumentCount::Between(0, 1), Self::parse)
+            .build()
     }
 
     pub fn parse(parameter: FunctionParameter) -> NativeCallResult {
-        if parameter.length() > 1 {
-            return n_parameter_expected!("oku".to_string(), 1);
-        }
-
         let arg = match parameter.iter().next() {
             Some(arg) => arg.deref(),
             None => return Ok(EMPTY_OBJECT)
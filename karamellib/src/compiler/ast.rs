@@ -24,6 +24,68 @@ pub struct KaramelDictItem {
     pub value: Rc<KaramelAstType>
 }
 
+#[repr(C)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct KaramelFunctionArgument {
+    pub name: String,
+    pub default: Option<Rc<KaramelAstType>>,
+    pub variadic: bool
+}
+
+impl KaramelFunctionArgument {
+    pub fn new<T: AsRef<str>>(name: T) -> KaramelFunctionArgument {
+        KaramelFunctionArgument {
+            name: name.as_ref().to_string(),
+            default: None,
+            variadic: false
+        }
+    }
+
+    pub fn with_default<T: AsRef<str>>(name: T, default: Rc<KaramelAstType>) -> KaramelFunctionArgument {
+        KaramelFunctionArgument {
+            name: name.as_ref().to_string(),
+            default: Some(default),
+            variadic: false
+        }
+    }
+
+    pub fn variadic<T: AsRef<str>>(name: T) -> KaramelFunctionArgument {
+        KaramelFunctionArgument {
+            name: name.as_ref().to_string(),
+            default: None,
+            variadic: true
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum KaramelPattern {
+    /* '_' matches everything */
+    Wildcard,
+    Variable(String),
+    Literal(Rc<KaramelAstType>),
+    List {
+        items: Vec<Rc<KaramelPattern>>,
+        rest: Option<String>
+    },
+    Dict(Vec<(Rc<KaramelPrimative>, Rc<KaramelPattern>)>)
+}
+
+#[repr(C)]
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub struct KaramelMatchCase {
+    pub pattern: Rc<KaramelPattern>,
+    pub guard: Option<Rc<KaramelAstType>>,
+    pub body: Rc<KaramelAstType>
+}
+
 impl KaramelIfStatementElseItem {
     pub fn new(condition: Rc<KaramelAstType>, body: Rc<KaramelAstType>) -> KaramelIfStatementElseItem {
         KaramelIfStatementElseItem {
@@ -82,20 +144,51 @@ pub enum KaramelAstType {
     },
     FunctionDefination {
         name: String,
-        arguments: Vec<String>,
+        arguments: Vec<KaramelFunctionArgument>,
         body: Rc<KaramelAstType>
     },
+    Lambda {
+        arguments: Vec<KaramelFunctionArgument>,
+        body: Rc<KaramelAstType>,
+        storage_index: Cell<usize>
+    },
+    ClassDefination {
+        name: String,
+        fields: Vec<Rc<KaramelDictItem>>,
+        methods: Vec<Rc<KaramelAstType>>
+    },
     Symbol(String),
     ModulePath(Vec<String>),
     Load(Vec<String>),
     List(Vec<Rc<KaramelAstType>>),
     Dict(Vec<Rc<KaramelDictItem>>),
     Indexer { body: Rc<KaramelAstType>, indexer: Rc<KaramelAstType> },
+    Slice { body: Rc<KaramelAstType>, start: Rc<KaramelAstType>, end: Rc<KaramelAstType>, step: Rc<KaramelAstType> },
     Return(Rc<KaramelAstType>),
     Break,
     Continue,
     Loop {
         loop_type: LoopType,
         body: Rc<KaramelAstType>
-    }
+    },
+    TryCatch {
+        body: Rc<KaramelAstType>,
+        variable: Option<String>,
+        catch_body: Rc<KaramelAstType>
+    },
+    Throw(Rc<KaramelAstType>),
+    Match {
+        expression: Rc<KaramelAstType>,
+        cases: Vec<Rc<KaramelMatchCase>>
+    },
+    NamedArgument {
+        name: String,
+        expression: Rc<KaramelAstType>
+    },
+    Conditional {
+        condition: Rc<KaramelAstType>,
+        true_expression: Rc<KaramelAstType>,
+        false_expression: Rc<KaramelAstType>
+    },
+    Interpolation(Vec<Rc<KaramelAstType>>)
 }
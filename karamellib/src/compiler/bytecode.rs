@@ -0,0 +1,910 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::buildin::{Class, ClassConfig, ClassProperty, Module};
+use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::constants::{BYTECODE_MAGIC, BYTECODE_VERSION, OPCODE_LOCATION_SIZE};
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+
+use super::ast::{KaramelAstType, KaramelFunctionArgument};
+use super::context::KaramelCompilerContext;
+use super::function::{FunctionFlag, FunctionReference, FunctionType};
+use super::generator::call::CallFlag;
+use super::module::OpcodeModule;
+use super::{KaramelPrimative, StaticStorage, VmOpCode};
+
+/*
+╔════════════════════╗
+║   HEADER           ║  Magic and format version
+╠════════════════════╣
+║   OPCODES          ║
+╠════════════════════╣
+║   MODULES          ║  Name and path of the modules that own the functions
+╠════════════════════╣
+║   FUNCTIONS        ║  Native functions are written with the module path and name
+╠════════════════════╣
+║   CLASSES          ║  Buildin classes are written with the name
+╠════════════════════╣
+║   STORAGES         ║  Variables, constants and parent links
+╚════════════════════╝
+*/
+
+const VALUE_EMPTY: u8    = 0;
+const VALUE_NUMBER: u8   = 1;
+const VALUE_BOOL: u8     = 2;
+const VALUE_TEXT: u8     = 3;
+const VALUE_LIST: u8     = 4;
+const VALUE_DICT: u8     = 5;
+const VALUE_FUNCTION: u8 = 6;
+const VALUE_CLASS: u8    = 7;
+
+const FUNCTION_OPCODE: u8 = 0;
+const FUNCTION_NATIVE: u8 = 1;
+
+const CLASS_BUILDIN: u8 = 0;
+const CLASS_USER: u8    = 1;
+
+const PROPERTY_FUNCTION: u8 = 0;
+const PROPERTY_FIELD: u8    = 1;
+
+/// Bytecode files start with the magic bytes
+pub fn is_bytecode(data: &[u8]) -> bool {
+    data.starts_with(BYTECODE_MAGIC)
+}
+
+/// Serialize the compiled program. Context must be compiled before.
+pub fn save_bytecode(context: &KaramelCompilerContext) -> Result<Vec<u8>, KaramelErrorType> {
+    let mut writer = BytecodeWriter::default();
+
+    /* Functions and classes are written before the storages that use them */
+    for storage in context.storages.iter() {
+        for constant in storage.constants.iter() {
+            writer.collect(&*constant.deref(), context)?;
+        }
+    }
+
+    writer.buffer.extend_from_slice(BYTECODE_MAGIC);
+    writer.write_u16(BYTECODE_VERSION);
+    writer.write_u32(context.opcodes.len() as u32);
+    writer.buffer.extend_from_slice(&context.opcodes);
+
+    writer.write_u32(writer.modules.len() as u32);
+    for module in writer.modules.clone().iter() {
+        writer.write_text(&module.get_module_name());
+        writer.write_texts(module.get_path());
+    }
+
+    writer.write_u32(writer.functions.len() as u32);
+    for function in writer.functions.clone().iter() {
+        writer.write_function(function)?;
+    }
+
+    writer.write_u32(writer.classes.len() as u32);
+    for (class, buildin) in writer.classes.clone().iter() {
+        writer.write_class(class, *buildin)?;
+    }
+
+    writer.write_u32(context.storages.len() as u32);
+    for storage in context.storages.iter() {
+        writer.write_u32(storage.index as u32);
+        match storage.parent_location {
+            Some(parent) => {
+                writer.write_u8(1);
+                writer.write_u32(parent as u32);
+            },
+            None => writer.write_u8(0)
+        };
+
+        writer.write_texts(&storage.variables);
+        writer.write_u32(storage.constants.len() as u32);
+        for constant in storage.constants.iter() {
+            writer.write_value(&*constant.deref())?;
+        }
+    }
+
+    Ok(writer.buffer)
+}
+
+/// Load the serialized program into the context, context could be executed with 'run_vm' after that.
+pub fn load_bytecode(data: &[u8], context: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    let mut reader = BytecodeReader { data, position: 0, functions: Vec::new(), classes: Vec::new() };
+
+    if reader.read_bytes(BYTECODE_MAGIC.len())? != BYTECODE_MAGIC {
+        return Err(KaramelErrorType::InvalidBytecode);
+    }
+
+    let version = reader.read_u16()?;
+    if version != BYTECODE_VERSION {
+        return Err(KaramelErrorType::BytecodeVersionNotSupported(version));
+    }
+
+    let opcode_size = reader.read_u32()? as usize;
+    let opcodes = reader.read_bytes(opcode_size)?.to_vec();
+
+    let mut modules = Vec::new();
+    for _ in 0..reader.read_u32()? {
+        let name = reader.read_text()?;
+        let mut module = OpcodeModule::new(name, String::new(), Rc::new(KaramelAstType::None));
+        module.path = reader.read_texts()?;
+        modules.push(Rc::new(module));
+    }
+
+    for _ in 0..reader.read_u32()? {
+        let function = reader.read_function(&modules, context)?;
+        reader.functions.push(function);
+    }
+
+    for _ in 0..reader.read_u32()? {
+        let class = reader.read_class(context)?;
+        reader.classes.push(class);
+    }
+
+    let mut storages = Vec::new();
+    for _ in 0..reader.read_u32()? {
+        let mut storage = StaticStorage::new(reader.read_u32()? as usize);
+        if reader.read_u8()? == 1 {
+            storage.set_parent_location(reader.read_u32()? as usize);
+        }
+
+        storage.variables = reader.read_texts()?;
+        for _ in 0..reader.read_u32()? {
+            let value = reader.read_value()?;
+            storage.constants.push(VmObject::convert(value));
+        }
+        storages.push(storage);
+    }
+
+    /* Wrong locations could not be executed */
+    let valid_storage = |index: usize| index < storages.len();
+    let invalid_function = reader.functions.iter().any(|function| match function.callback {
+        FunctionType::Opcode => !valid_storage(function.storage_index) || function.opcode_location.get() >= opcodes.len(),
+        FunctionType::Native(_) | FunctionType::NativeClosure(_) => false
+    });
+
+    if storages.is_empty() || opcodes.is_empty() || invalid_function || reader.position != data.len() {
+        return Err(KaramelErrorType::InvalidBytecode);
+    }
+
+    verify_opcodes(&opcodes, &reader.functions, &storages)?;
+
+    /* Functions could be found by the module */
+    for function in reader.functions.iter().filter(|function| matches!(function.callback, FunctionType::Opcode) && !function.flags.intersects(FunctionFlag::IN_CLASS | FunctionFlag::CLOSURE)) {
+        if let Some(module) = modules.iter().find(|module| module.path == *function.module.get_path() && module.name == function.module.get_module_name()) {
+            module.functions.borrow_mut().insert(function.name.to_string(), function.clone());
+        }
+    }
+
+    for module in modules.into_iter() {
+        context.add_module(module);
+    }
+
+    context.storages        = storages;
+    context.storages_ptr    = context.storages.as_mut_ptr();
+    context.opcodes         = opcodes;
+    context.opcodes_ptr     = context.opcodes.as_mut_ptr();
+    context.opcodes_top_ptr = context.opcodes_ptr;
+    Ok(())
+}
+
+/*
+Interpreter does not check the opcodes, so every opcode is checked before the execution.
+Functions start with the argument count and use their own storage, main code starts at the first jump location.
+Stack depth is calculated for the every reachable opcode, opcodes could not use the values below the function memory
+and every path must reach to the same location with the same stack depth.
+*/
+fn verify_opcodes(opcodes: &[u8], functions: &[Rc<FunctionReference>], storages: &[StaticStorage]) -> Result<(), KaramelErrorType> {
+    let mut function_locations: HashMap<usize, usize> = functions.iter()
+        .filter(|function| matches!(function.callback, FunctionType::Opcode))
+        .map(|function| (function.opcode_location.get(), function.storage_index))
+        .collect();
+
+    let main_location = match VmOpCode::from_byte(opcodes[0]) {
+        Some(VmOpCode::Jump) => opcodes.get(1..1 + OPCODE_LOCATION_SIZE).map(read_location),
+        _ => None
+    };
+
+    /* Main code is the segment zero, function segments are named with the function location */
+    let mut instructions: HashMap<usize, Instruction> = HashMap::new();
+    let mut entries = vec![0];
+    let mut storage = &storages[0];
+    let mut segment = 0;
+    let mut position = 0;
+
+    while position < opcodes.len() {
+        if let Some(storage_index) = function_locations.remove(&position) {
+            storage = &storages[storage_index];
+            segment = position;
+            position += 1;
+            entries.push(position);
+            continue;
+        }
+
+        if Some(position) == main_location {
+            storage = &storages[0];
+            segment = 0;
+        }
+
+        let start = position;
+        let wide = opcodes[position] == VmOpCode::Wide.into();
+        if wide {
+            position += 1;
+        }
+
+        let opcode = opcodes.get(position).and_then(|opcode| VmOpCode::from_byte(*opcode)).ok_or(KaramelErrorType::InvalidBytecode)?;
+        let operand_size = if wide { 2 } else { 1 };
+
+        /* Size of the opcode with the operands and the location of the each operand type */
+        let (size, variables, constants, location): (usize, &[usize], &[usize], Option<usize>) = match opcode {
+            VmOpCode::Load | VmOpCode::Store | VmOpCode::CopyToStore => (1 + operand_size, &[1], &[], None),
+            VmOpCode::Constant => (1 + operand_size, &[], &[1], None),
+            VmOpCode::Call => (3 + operand_size, &[], &[1], None),
+            VmOpCode::FastStore => (1 + operand_size * 2, &[1], &[1 + operand_size], None),
+            VmOpCode::Move => (1 + operand_size * 2, &[1, 1 + operand_size], &[], None),
+            VmOpCode::Concat | VmOpCode::MatchDict => (1 + operand_size, &[], &[], None),
+            VmOpCode::MatchList => (2 + operand_size, &[], &[], None),
+            VmOpCode::Init => match opcodes.get(position + 1) {
+                Some(0..=2) | Some(4) => (2 + operand_size, &[], &[], None),
+                Some(3) if !wide => (3, &[], &[], None),
+                _ => return Err(KaramelErrorType::InvalidBytecode)
+            },
+            VmOpCode::Iterate => (2 + operand_size * 2 + OPCODE_LOCATION_SIZE, &[1, 1 + operand_size], &[], Some(2 + operand_size * 2)),
+            _ if wide => return Err(KaramelErrorType::InvalidBytecode),
+            VmOpCode::Compare | VmOpCode::Jump => (1 + OPCODE_LOCATION_SIZE, &[], &[], Some(1)),
+            VmOpCode::TryStart => (2 + OPCODE_LOCATION_SIZE, &[], &[], Some(2)),
+            VmOpCode::CallStack => (3, &[], &[], None),
+            VmOpCode::DefaultArgument => match opcodes.get(position + 1) {
+                Some(location) if (*location as u32) < u64::BITS => (2, &[], &[], None),
+                _ => return Err(KaramelErrorType::InvalidBytecode)
+            },
+            _ => (1, &[], &[], None)
+        };
+
+        let instruction = opcodes.get(position..position + size).ok_or(KaramelErrorType::InvalidBytecode)?;
+        let operand = |offset: usize| match wide {
+            true => u16::from_le_bytes([instruction[offset], instruction[offset + 1]]) as usize,
+            false => instruction[offset] as usize
+        };
+
+        if variables.iter().any(|offset| operand(*offset) >= storage.variables.len()) || constants.iter().any(|offset| operand(*offset) >= storage.constants.len()) {
+            return Err(KaramelErrorType::InvalidBytecode);
+        }
+
+        /* Main code does not have a caller */
+        if opcode == VmOpCode::Return && segment == 0 {
+            return Err(KaramelErrorType::InvalidBytecode);
+        }
+
+        /* Compare and iterate locations are relative to the opcode */
+        let target = match location {
+            Some(offset) => {
+                let value = read_location(&instruction[offset..offset + OPCODE_LOCATION_SIZE]);
+                match opcode {
+                    VmOpCode::Compare | VmOpCode::Iterate => start.checked_add(value).ok_or(KaramelErrorType::InvalidBytecode)?,
+                    _ => value
+                }
+            },
+            None => 0
+        };
+
+        /* Values that removed from the stack and values that added to the stack */
+        let assign_to_temp = |flags: u8| CallFlag::from_bits_truncate(flags).contains(CallFlag::ASSIGN_TO_TEMP) as usize;
+        let (pops, pushes) = match opcode {
+            VmOpCode::Addition | VmOpCode::Subraction | VmOpCode::Multiply | VmOpCode::Division | VmOpCode::Module |
+            VmOpCode::And | VmOpCode::Or | VmOpCode::Equal | VmOpCode::NotEqual | VmOpCode::GreaterThan |
+            VmOpCode::GreaterEqualThan | VmOpCode::GetItem => (2, 1),
+            VmOpCode::Load | VmOpCode::Constant | VmOpCode::DefaultArgument => (0, 1),
+            VmOpCode::Store | VmOpCode::Compare | VmOpCode::Return | VmOpCode::Throw => (1, 0),
+            VmOpCode::CopyToStore | VmOpCode::Not | VmOpCode::Increment | VmOpCode::Decrement | VmOpCode::MatchList => (1, 1),
+            VmOpCode::Dublicate => (1, 2),
+            VmOpCode::SetItem => (3, 0),
+            VmOpCode::Slice => (4, 1),
+            VmOpCode::Call => (instruction[1 + operand_size] as usize, assign_to_temp(instruction[2 + operand_size])),
+            VmOpCode::CallStack => (instruction[1] as usize + 1, assign_to_temp(instruction[2])),
+            VmOpCode::Init => match instruction[1] {
+                0 => (operand(2) * 2, 1),
+                1 => (operand(2), 1),
+                2 => (operand(2) * 2 + 1, 1),
+                3 => (1, 1),
+                _ => (operand(2) + 1, 1)
+            },
+            VmOpCode::Concat => (operand(1), 1),
+            VmOpCode::MatchDict => (operand(1) + 1, 1),
+            VmOpCode::Iterate => (0, if instruction[1 + operand_size * 2] == 1 { 2 } else { 1 }),
+            _ => (0, 0)
+        };
+
+        let next = position + size;
+        let successors = match opcode {
+            VmOpCode::Jump => vec![(target, 0)],
+            VmOpCode::Halt | VmOpCode::Return | VmOpCode::Throw => Vec::new(),
+            VmOpCode::Compare => vec![(next, 0), (target, 0)],
+            VmOpCode::Iterate => vec![(next, pushes), (target, 0)],
+            VmOpCode::TryStart => vec![(next, 0), (target, (instruction[1] != 0) as usize)],
+            _ => vec![(next, pushes)]
+        };
+
+        instructions.insert(start, Instruction { segment, pops, successors });
+        position = next;
+    }
+
+    if !function_locations.is_empty() {
+        return Err(KaramelErrorType::InvalidBytecode);
+    }
+
+    /* Every segment starts with an empty stack, execution could not continue at an another segment */
+    let mut depths: HashMap<usize, usize> = HashMap::new();
+    let mut pending = entries.into_iter().map(|entry| (entry, 0)).collect::<Vec<(usize, usize)>>();
+
+    while let Some((location, depth)) = pending.pop() {
+        match depths.get(&location) {
+            Some(known) if *known == depth => continue,
+            Some(_) => return Err(KaramelErrorType::InvalidBytecode),
+            None => depths.insert(location, depth)
+        };
+
+        let instruction = instructions.get(&location).ok_or(KaramelErrorType::InvalidBytecode)?;
+        let depth = depth.checked_sub(instruction.pops).ok_or(KaramelErrorType::InvalidBytecode)?;
+
+        for (next, pushes) in instruction.successors.iter() {
+            match instructions.get(next) {
+                Some(next_instruction) if next_instruction.segment == instruction.segment => pending.push((*next, depth + pushes)),
+                _ => return Err(KaramelErrorType::InvalidBytecode)
+            };
+        }
+    }
+    Ok(())
+}
+
+/* Decoded opcode, next locations are kept with the count of the values that added to the stack */
+struct Instruction {
+    segment: usize,
+    pops: usize,
+    successors: Vec<(usize, usize)>
+}
+
+fn read_location(bytes: &[u8]) -> usize {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize
+}
+
+#[derive(Default)]
+struct BytecodeWriter {
+    buffer: Vec<u8>,
+    modules: Vec<Rc<dyn Module>>,
+    functions: Vec<Rc<FunctionReference>>,
+    classes: Vec<(Rc<dyn Class>, bool)>
+}
+
+impl BytecodeWriter {
+    fn collect(&mut self, value: &KaramelPrimative, context: &KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+        match value {
+            KaramelPrimative::List(items) => {
+                for item in items.borrow().iter() {
+                    self.collect(&*item.deref(), context)?;
+                }
+            },
+            KaramelPrimative::Dict(items) => {
+                let items = items.borrow();
+                let mut keys = items.keys().collect::<Vec<&String>>();
+                keys.sort();
+
+                for key in keys.into_iter() {
+                    self.collect(&*items[key].deref(), context)?;
+                }
+            },
+            KaramelPrimative::Function(function, None) => self.collect_function(function),
+            KaramelPrimative::Function(_, Some(_)) => return Err(KaramelErrorType::BytecodeValueNotSupported(value.to_display_text())),
+            KaramelPrimative::Class(class) => {
+                if self.find_class(class).is_some() {
+                    return Ok(());
+                }
+
+                let buildin = context.primative_classes.iter().any(|primative| Rc::as_ptr(primative) as *const u8 == Rc::as_ptr(class) as *const u8);
+                if !buildin {
+                    for (_, property) in class.properties() {
+                        match property {
+                            ClassProperty::Function(function) => self.collect_function(function),
+                            ClassProperty::Field(field) => self.collect(field, context)?
+                        };
+                    }
+                }
+
+                self.classes.push((class.clone(), buildin));
+            },
+            _ => ()
+        };
+        Ok(())
+    }
+
+    fn collect_function(&mut self, function: &Rc<FunctionReference>) {
+        if self.find_function(function).is_some() {
+            return;
+        }
+
+        if let FunctionType::Opcode = function.callback {
+            let module = Rc::as_ptr(&function.module) as *const u8;
+            if !self.modules.iter().any(|item| Rc::as_ptr(item) as *const u8 == module) {
+                self.modules.push(function.module.clone());
+            }
+        }
+
+        self.functions.push(function.clone());
+    }
+
+    fn find_function(&self, function: &Rc<FunctionReference>) -> Option<usize> {
+        self.functions.iter().position(|item| Rc::ptr_eq(item, function))
+    }
+
+    fn find_class(&self, class: &Rc<dyn Class>) -> Option<usize> {
+        self.classes.iter().position(|(item, _)| Rc::as_ptr(item) as *const u8 == Rc::as_ptr(class) as *const u8)
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_text(&mut self, text: &str) {
+        self.write_u32(text.len() as u32);
+        self.buffer.extend_from_slice(text.as_bytes());
+    }
+
+    fn write_texts(&mut self, texts: &[String]) {
+        self.write_u32(texts.len() as u32);
+        for text in texts.iter() {
+            self.write_text(text);
+        }
+    }
+
+    fn write_function(&mut self, function: &FunctionReference) -> Result<(), KaramelErrorType> {
+        match function.callback {
+            FunctionType::Native(_) | FunctionType::NativeClosure(_) => {
+                self.write_u8(FUNCTION_NATIVE);
+                self.write_texts(function.module.get_path());
+                self.write_text(&function.name);
+            },
+            FunctionType::Opcode => {
+                let module = Rc::as_ptr(&function.module) as *const u8;
+                let module = match self.modules.iter().position(|item| Rc::as_ptr(item) as *const u8 == module) {
+                    Some(index) => index,
+                    None => return Err(KaramelErrorType::BytecodeValueNotSupported(function.name.to_string()))
+                };
+
+                self.write_u8(FUNCTION_OPCODE);
+                self.write_u32(module as u32);
+                self.write_text(&function.name);
+                self.write_u32(function.flags.bits());
+                self.write_texts(&function.arguments);
+
+                self.write_u32(function.parameters.len() as u32);
+                for parameter in function.parameters.iter() {
+                    self.write_text(&parameter.name);
+                    self.write_u8(parameter.default.is_some() as u8);
+                    self.write_u8(parameter.variadic as u8);
+                }
+
+                self.write_u32(function.storage_index as u32);
+                self.write_u32(function.defined_storage_index as u32);
+                self.write_u32(function.opcode_location.get() as u32);
+            }
+        };
+        Ok(())
+    }
+
+    fn write_class(&mut self, class: &Rc<dyn Class>, buildin: bool) -> Result<(), KaramelErrorType> {
+        if buildin {
+            self.write_u8(CLASS_BUILDIN);
+            self.write_text(&class.get_class_name());
+            return Ok(());
+        }
+
+        self.write_u8(CLASS_USER);
+        self.write_text(&class.get_class_name());
+        self.write_u32(class.property_count() as u32);
+        for (name, property) in class.properties() {
+            self.write_text(name);
+            match property {
+                ClassProperty::Function(function) => match self.find_function(function) {
+                    Some(index) => {
+                        self.write_u8(PROPERTY_FUNCTION);
+                        self.write_u32(index as u32);
+                    },
+                    None => return Err(KaramelErrorType::BytecodeValueNotSupported(function.name.to_string()))
+                },
+                ClassProperty::Field(field) => {
+                    self.write_u8(PROPERTY_FIELD);
+                    self.write_value(field)?;
+                }
+            };
+        }
+        Ok(())
+    }
+
+    fn write_value(&mut self, value: &KaramelPrimative) -> Result<(), KaramelErrorType> {
+        match value {
+            KaramelPrimative::Empty => self.write_u8(VALUE_EMPTY),
+            KaramelPrimative::Number(number) => {
+                self.write_u8(VALUE_NUMBER);
+                self.buffer.extend_from_slice(&number.to_le_bytes());
+            },
+            KaramelPrimative::Bool(value) => {
+                self.write_u8(VALUE_BOOL);
+                self.write_u8(*value as u8);
+            },
+            KaramelPrimative::Text(text) => {
+                self.write_u8(VALUE_TEXT);
+                self.write_text(text);
+            },
+            KaramelPrimative::List(items) => {
+                self.write_u8(VALUE_LIST);
+                self.write_u32(items.borrow().len() as u32);
+                for item in items.borrow().iter() {
+                    self.write_value(&*item.deref())?;
+                }
+            },
+            KaramelPrimative::Dict(items) => {
+                /* Same dictionary is always written with the same bytes */
+                let items = items.borrow();
+                let mut keys = items.keys().collect::<Vec<&String>>();
+                keys.sort();
+
+                self.write_u8(VALUE_DICT);
+                self.write_u32(keys.len() as u32);
+                for key in keys.into_iter() {
+                    let item = &items[key];
+                    self.write_text(key);
+                    self.write_value(&*item.deref())?;
+                }
+            },
+            KaramelPrimative::Function(function, None) => match self.find_function(function) {
+                Some(index) => {
+                    self.write_u8(VALUE_FUNCTION);
+                    self.write_u32(index as u32);
+                },
+                None => return Err(KaramelErrorType::BytecodeValueNotSupported(value.to_display_text()))
+            },
+            KaramelPrimative::Class(class) => match self.find_class(class) {
+                Some(index) => {
+                    self.write_u8(VALUE_CLASS);
+                    self.write_u32(index as u32);
+                },
+                None => return Err(KaramelErrorType::BytecodeValueNotSupported(value.to_display_text()))
+            },
+            _ => return Err(KaramelErrorType::BytecodeValueNotSupported(value.to_display_text()))
+        };
+        Ok(())
+    }
+}
+
+struct BytecodeReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    functions: Vec<Rc<FunctionReference>>,
+    classes: Vec<Rc<dyn Class>>
+}
+
+impl<'a> BytecodeReader<'a> {
+    fn read_bytes(&mut self, size: usize) -> Result<&'a [u8], KaramelErrorType> {
+        match self.data.get(self.position..self.position + size) {
+            Some(bytes) => {
+                self.position += size;
+                Ok(bytes)
+            },
+            None => Err(KaramelErrorType::InvalidBytecode)
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8, KaramelErrorType> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, KaramelErrorType> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, KaramelErrorType> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, KaramelErrorType> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.read_bytes(8)?);
+        Ok(f64::from_le_bytes(bytes))
+    }
+
+    fn read_text(&mut self) -> Result<String, KaramelErrorType> {
+        let size = self.read_u32()? as usize;
+        match std::str::from_utf8(self.read_bytes(size)?) {
+            Ok(text) => Ok(text.to_string()),
+            Err(_) => Err(KaramelErrorType::InvalidBytecode)
+        }
+    }
+
+    fn read_texts(&mut self) -> Result<Vec<String>, KaramelErrorType> {
+        let mut texts = Vec::new();
+        for _ in 0..self.read_u32()? {
+            texts.push(self.read_text()?);
+        }
+        Ok(texts)
+    }
+
+    fn read_function(&mut self, modules: &[Rc<OpcodeModule>], context: &KaramelCompilerContext) -> Result<Rc<FunctionReference>, KaramelErrorType> {
+        match self.read_u8()? {
+            FUNCTION_NATIVE => {
+                let path = self.read_texts()?;
+                let name = self.read_text()?;
+                let modules = context.modules.iter().map(|(_, module)| module.clone()).collect::<Vec<Rc<dyn Module>>>();
+                match find_native_function(&modules, &path, &name) {
+                    Some(function) => Ok(function),
+                    None => Err(KaramelErrorType::FunctionNotFound(name))
+                }
+            },
+            FUNCTION_OPCODE => {
+                let module = match modules.get(self.read_u32()? as usize) {
+                    Some(module) => module.clone(),
+                    None => return Err(KaramelErrorType::InvalidBytecode)
+                };
+
+                let name = self.read_text()?;
+                let flags = FunctionFlag::from_bits_truncate(self.read_u32()?);
+                let arguments = self.read_texts()?;
+
+                /* Default value expressions are inside the opcodes, only the existence is used */
+                let mut parameters = Vec::new();
+                for _ in 0..self.read_u32()? {
+                    let name = self.read_text()?;
+                    let default = match self.read_u8()? {
+                        1 => Some(Rc::new(KaramelAstType::None)),
+                        _ => None
+                    };
+                    parameters.push(KaramelFunctionArgument { name, default, variadic: self.read_u8()? == 1 });
+                }
+
+                Ok(Rc::new(FunctionReference {
+                    callback: FunctionType::Opcode,
+                    flags,
+                    name,
+                    arguments,
+                    parameters,
+                    storage_index: self.read_u32()? as usize,
+                    defined_storage_index: self.read_u32()? as usize,
+                    opcode_location: Cell::new(self.read_u32()? as usize),
+                    used_locations: RefCell::new(Vec::new()),
+                    opcode_body: None,
+                    module
+                }))
+            },
+            _ => Err(KaramelErrorType::InvalidBytecode)
+        }
+    }
+
+    fn read_class(&mut self, context: &KaramelCompilerContext) -> Result<Rc<dyn Class>, KaramelErrorType> {
+        match self.read_u8()? {
+            CLASS_BUILDIN => {
+                let name = self.read_text()?;
+                match context.primative_classes.iter().find(|class| class.get_class_name() == name) {
+                    Some(class) => Ok(class.clone()),
+                    None => Err(KaramelErrorType::InvalidBytecode)
+                }
+            },
+            CLASS_USER => {
+                let mut config = ClassConfig::default();
+                config.name = self.read_text()?;
+                let mut properties = HashMap::new();
+
+                for _ in 0..self.read_u32()? {
+                    let name = self.read_text()?;
+                    let property = match self.read_u8()? {
+                        PROPERTY_FUNCTION => ClassProperty::Function(self.read_function_index()?),
+                        PROPERTY_FIELD => ClassProperty::Field(self.read_value()?),
+                        _ => return Err(KaramelErrorType::InvalidBytecode)
+                    };
+                    properties.insert(name, property);
+                }
+
+                config.properties = properties;
+                let mut class = BasicInnerClass::default();
+                class.set_class_config(config);
+                Ok(Rc::new(class))
+            },
+            _ => Err(KaramelErrorType::InvalidBytecode)
+        }
+    }
+
+    fn read_function_index(&mut self) -> Result<Rc<FunctionReference>, KaramelErrorType> {
+        let index = self.read_u32()? as usize;
+        match self.functions.get(index) {
+            Some(function) => Ok(function.clone()),
+            None => Err(KaramelErrorType::InvalidBytecode)
+        }
+    }
+
+    fn read_value(&mut self) -> Result<Rc<KaramelPrimative>, KaramelErrorType> {
+        let value = match self.read_u8()? {
+            VALUE_EMPTY => KaramelPrimative::Empty,
+            VALUE_NUMBER => KaramelPrimative::Number(self.read_f64()?),
+            VALUE_BOOL => KaramelPrimative::Bool(self.read_u8()? == 1),
+            VALUE_TEXT => KaramelPrimative::Text(Rc::new(self.read_text()?)),
+            VALUE_LIST => {
+                let mut items = Vec::new();
+                for _ in 0..self.read_u32()? {
+                    items.push(VmObject::convert(self.read_value()?));
+                }
+                KaramelPrimative::List(RefCell::new(items))
+            },
+            VALUE_DICT => {
+                let mut items = HashMap::new();
+                for _ in 0..self.read_u32()? {
+                    let key = self.read_text()?;
+                    items.insert(key, VmObject::convert(self.read_value()?));
+                }
+                KaramelPrimative::Dict(RefCell::new(items))
+            },
+            VALUE_FUNCTION => KaramelPrimative::Function(self.read_function_index()?, None),
+            VALUE_CLASS => {
+                let index = self.read_u32()? as usize;
+                match self.classes.get(index) {
+                    Some(class) => KaramelPrimative::Class(class.clone()),
+                    None => return Err(KaramelErrorType::InvalidBytecode)
+                }
+            },
+            _ => return Err(KaramelErrorType::InvalidBytecode)
+        };
+        Ok(Rc::new(value))
+    }
+}
+
+/* Native functions are searched inside the sub modules too */
+fn find_native_function(modules: &[Rc<dyn Module>], path: &[String], name: &str) -> Option<Rc<FunctionReference>> {
+    for module in modules.iter() {
+        if module.get_path() == path {
+            if let Some(function) = module.get_method(name) {
+                return Some(function);
+            }
+        }
+
+        let sub_modules = module.get_modules().into_iter().map(|(_, module)| module).collect::<Vec<Rc<dyn Module>>>();
+        if let Some(function) = find_native_function(&sub_modules, path, name) {
+            return Some(function);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_check() {
+        let mut data = BYTECODE_MAGIC.to_vec();
+        data.extend_from_slice(&(BYTECODE_VERSION + 1).to_le_bytes());
+
+        let mut context = KaramelCompilerContext::new();
+        assert_eq!(load_bytecode(&data, &mut context).err(), Some(KaramelErrorType::BytecodeVersionNotSupported(BYTECODE_VERSION + 1)));
+        assert_eq!(load_bytecode(b"karamel", &mut context).err(), Some(KaramelErrorType::InvalidBytecode));
+    }
+
+    #[test]
+    fn truncated_data() {
+        let mut data = BYTECODE_MAGIC.to_vec();
+        data.extend_from_slice(&BYTECODE_VERSION.to_le_bytes());
+        data.extend_from_slice(&100_u32.to_le_bytes());
+
+        let mut context = KaramelCompilerContext::new();
+        assert_eq!(load_bytecode(&data, &mut context).err(), Some(KaramelErrorType::InvalidBytecode));
+    }
+
+    /* Main code has one variable and one constant */
+    fn load(opcodes: &[u8]) -> Result<(), KaramelErrorType> {
+        let mut context = KaramelCompilerContext::new();
+        context.storages[0].add_variable("değer");
+        context.storages[0].add_constant(Rc::new(KaramelPrimative::Number(1.0)));
+        context.opcodes = opcodes.to_vec();
+
+        let data = save_bytecode(&context)?;
+        load_bytecode(&data, &mut KaramelCompilerContext::new())
+    }
+
+    const JUMP: u8     = VmOpCode::Jump as u8;
+    const CONSTANT: u8 = VmOpCode::Constant as u8;
+    const STORE: u8    = VmOpCode::Store as u8;
+    const LOAD: u8     = VmOpCode::Load as u8;
+    const WIDE: u8     = VmOpCode::Wide as u8;
+    const COMPARE: u8  = VmOpCode::Compare as u8;
+    const HALT: u8     = VmOpCode::Halt as u8;
+    const RETURN: u8   = VmOpCode::Return as u8;
+    const ADDITION: u8 = VmOpCode::Addition as u8;
+
+    #[test]
+    fn valid_opcodes() {
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, CONSTANT, 0, STORE, 0, HALT]), Ok(()));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, WIDE, LOAD, 0, 0, COMPARE, 5, 0, 0, 0, HALT]), Ok(()));
+    }
+
+    #[test]
+    fn unknown_opcode() {
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, 11, HALT]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, 200, HALT]), Err(KaramelErrorType::InvalidBytecode));
+    }
+
+    #[test]
+    fn invalid_jump_location() {
+        assert_eq!(load(&[JUMP, 100, 0, 0, 0, HALT]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 6, 0, 0, 0, CONSTANT, 0, HALT]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, CONSTANT, 0, COMPARE, 0, 1, 0, 0, HALT]), Err(KaramelErrorType::InvalidBytecode));
+    }
+
+    #[test]
+    fn invalid_operands() {
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, CONSTANT, 1, HALT]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, LOAD, 1, HALT]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, WIDE, LOAD, 0, 1, HALT]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, CONSTANT]), Err(KaramelErrorType::InvalidBytecode));
+    }
+
+    #[test]
+    fn invalid_wide_prefix() {
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, WIDE, HALT]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, WIDE, WIDE, LOAD, 0, 0, HALT]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, WIDE]), Err(KaramelErrorType::InvalidBytecode));
+    }
+
+    #[test]
+    fn return_at_main_code() {
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, RETURN]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, CONSTANT, 0, RETURN]), Err(KaramelErrorType::InvalidBytecode));
+    }
+
+    #[test]
+    fn invalid_stack_depth() {
+        let mut opcodes = vec![JUMP, 5, 0, 0, 0];
+        opcodes.extend_from_slice(&[ADDITION; 200]);
+        opcodes.push(HALT);
+        assert_eq!(load(&opcodes), Err(KaramelErrorType::InvalidBytecode));
+
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, CONSTANT, 0, ADDITION, HALT]), Err(KaramelErrorType::InvalidBytecode));
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, CONSTANT, 0, CONSTANT, 0, ADDITION, STORE, 0, HALT]), Ok(()));
+
+        /* Loop adds a value at the every iteration */
+        assert_eq!(load(&[JUMP, 5, 0, 0, 0, CONSTANT, 0, JUMP, 5, 0, 0, 0]), Err(KaramelErrorType::InvalidBytecode));
+    }
+
+    #[test]
+    fn function_without_module() {
+        let module = Rc::new(OpcodeModule::new("modül".to_string(), String::new(), Rc::new(KaramelAstType::None)));
+        let function = FunctionReference::opcode_function("topla".to_string(), Vec::new(), Rc::new(KaramelAstType::None), module, 1, 0, true);
+
+        let mut writer = BytecodeWriter::default();
+        assert_eq!(writer.write_function(&function), Err(KaramelErrorType::BytecodeValueNotSupported("topla".to_string())));
+    }
+
+    #[test]
+    fn sorted_dictionary() {
+        let mut items = HashMap::new();
+        for (index, key) in ["c", "a", "d", "b"].iter().enumerate() {
+            items.insert(key.to_string(), VmObject::from(index as f64));
+        }
+
+        let mut writer = BytecodeWriter::default();
+        writer.write_value(&KaramelPrimative::Dict(RefCell::new(items))).unwrap();
+
+        let mut reader = BytecodeReader { data: &writer.buffer, position: 1, functions: Vec::new(), classes: Vec::new() };
+        let mut keys = Vec::new();
+        for _ in 0..reader.read_u32().unwrap() {
+            keys.push(reader.read_text().unwrap());
+            reader.read_value().unwrap();
+        }
+        assert_eq!(keys, vec!["a", "b", "c", "d"]);
+    }
+}
@@ -6,7 +6,8 @@ use std::path::PathBuf;
 use std::cell::RefCell;
 
 use ast::KaramelDictItem;
-use crate::buildin::Module;
+use crate::buildin::{ClassProperty, Module, ModuleCollection};
+use crate::constants::{CLASS_CONSTRUCTOR_NAME, CLASS_INSTANCE_NAME, ITERATOR_INDEX_NAME, ITERATOR_SOURCE_NAME, LAMBDA_NAME_PREFIX, MATCH_VALUE_NAME, MAX_ARGUMENT_SIZE, MAX_ITEM_SIZE};
 use crate::file::read_module_or_script;
 use crate::syntax::loops::LoopType;
 use crate::types::*;
@@ -15,11 +16,14 @@ use crate::compiler::*;
 use crate::parser::*;
 use crate::syntax::SyntaxParser;
 use crate::compiler::value::KaramelPrimative;
-use crate::compiler::ast::{KaramelAstType, KaramelIfStatementElseItem};
+use crate::compiler::ast::{KaramelAstType, KaramelFunctionArgument, KaramelIfStatementElseItem, KaramelMatchCase, KaramelPattern};
 use crate::compiler::storage_builder::StorageBuilder;
 use crate::compiler::function::FunctionReference;
 use crate::buildin::class::PRIMATIVE_CLASS_NAMES;
+use super::generator::OpcodeGenerator;
 use super::generator::location::OpcodeLocation;
+use super::generator::call::CallFlag;
+use super::generator::pattern::PatternType;
 
 use log;
 
@@ -28,10 +32,61 @@ use super::function::find_function_definition_type;
 use super::module::{OpcodeModule, get_modules};
 
 
+/* State of the context before the compilation. Previously compiled codes of the session are kept if the new code fails. */
+pub(crate) struct CompilationSnapshot {
+    modules: ModuleCollection,
+    main_module: *mut OpcodeModule,
+    storages: usize,
+    variables: usize,
+    constants: usize,
+    opcodes: usize,
+    source_files: usize,
+    source_locations: usize
+}
+
+impl CompilationSnapshot {
+    pub(crate) fn new(context: &KaramelCompilerContext) -> Self {
+        CompilationSnapshot {
+            modules: context.modules.clone(),
+            main_module: context.main_module,
+            storages: context.storages.len(),
+            variables: context.storages[0].variables.len(),
+            constants: context.storages[0].constants.len(),
+            opcodes: context.opcodes.len(),
+            source_files: context.source_map.file_count(),
+            source_locations: context.source_map.location_count()
+        }
+    }
+
+    pub(crate) fn restore(self, context: &mut KaramelCompilerContext) {
+        context.modules = self.modules;
+        context.main_module = self.main_module;
+        context.storages.truncate(self.storages);
+        context.storages[0].variables.truncate(self.variables);
+        context.storages[0].constants.truncate(self.constants);
+        context.opcodes.truncate(self.opcodes);
+        context.source_map.truncate(self.source_files, self.source_locations);
+    }
+}
+
 pub struct InterpreterCompiler;
 impl InterpreterCompiler {   
+    /// Compile the code into the context. Code could be compiled into a context that already has a compiled code, new
+    /// code is appended and uses the variables, functions and modules of the previous ones.
     pub fn compile(&self, main_ast: Rc<KaramelAstType>, context: &mut KaramelCompilerContext) -> CompilerResult {
+        let snapshot = CompilationSnapshot::new(context);
+        let result = self.compile_code(main_ast, context);
+        if result.is_err() {
+            snapshot.restore(context);
+        }
+        result
+    }
+
+    /// Compile without restoring the context, caller restores it if the compilation fails
+    pub(crate) fn compile_code(&self, main_ast: Rc<KaramelAstType>, context: &mut KaramelCompilerContext) -> CompilerResult {
         let storage_builder: StorageBuilder = StorageBuilder::new();
+        let entry_point = context.opcodes.len();
+        context.opcode_generator = OpcodeGenerator::new();
         let main_location = context.opcode_generator.create_location();
 
         context.opcode_generator.create_jump(main_location.clone());
@@ -43,6 +98,11 @@ impl InterpreterCompiler {
 
         storage_builder.prepare(main_module.clone(), &*main_ast, 0, context)?;
 
+        /* Variable and constant locations must fit into the wide operands */
+        for storage in context.storages.iter() {
+            storage.validate_size()?;
+        }
+
         /* First part of the codes are functions */
         let mut functions = Vec::new();
         for module in modules.iter() {
@@ -51,18 +111,38 @@ impl InterpreterCompiler {
 
         self.get_function_definations(main_module.clone(), main_ast.clone(), &mut functions, context, 0)?;
 
+        /* Functions without name are detected while building storages */
+        for module in modules.iter().chain(std::iter::once(&main_module)) {
+            functions.extend(module.lambdas.borrow().iter().cloned());
+        }
+
         self.generate_functions(main_module.clone(), &mut functions, context)?;
 
         /* Prepare jump code for main function */
         context.opcode_generator.set_current_location(main_location.clone());
+        context.opcode_generator.set_storage(0);
 
         /* Generate main function code */
         self.generate_opcode(main_module.clone(), &*main_ast, &KaramelAstType::None, context, 0)?;
         context.opcode_generator.add_opcode(VmOpCode::Halt);
+
+        if context.optimize {
+            context.opcode_generator.optimize(&mut context.storages);
+        }
+
         context.opcode_generator.generate(&mut context.opcodes);
 
-        context.opcodes_ptr     = context.opcodes.as_mut_ptr();
-        context.opcodes_top_ptr = context.opcodes_ptr;
+        if context.opcodes.len() > u32::MAX as usize {
+            return Err(KaramelErrorType::OpcodeLimitExceeded);
+        }
+
+        for (opcode_location, location) in context.opcode_generator.source_locations() {
+            context.source_map.add_location(opcode_location, location);
+        }
+
+        /* Execution starts from the new code */
+        context.opcodes_top_ptr = context.opcodes.as_mut_ptr();
+        context.opcodes_ptr     = unsafe { context.opcodes_top_ptr.add(entry_point) };
 
         Ok(())
     }
@@ -73,6 +153,13 @@ impl InterpreterCompiler {
 
     pub fn prepare_main_module(&self, main_ast: Rc<KaramelAstType>, context: &mut KaramelCompilerContext) -> Result<Rc<OpcodeModule>, KaramelErrorType> {
         let module = OpcodeModule::new("!baz".to_string(), String::new(), main_ast.clone());
+
+        /* Functions and classes of the previously compiled codes */
+        if let Some(previous) = unsafe { context.main_module.as_ref() } {
+            module.functions.borrow_mut().extend(previous.functions.borrow().iter().map(|(name, function)| (name.to_string(), function.clone())));
+            module.classes.borrow_mut().extend(previous.classes.borrow().iter().map(|(name, class)| (name.to_string(), class.clone())));
+        }
+
         let module = Rc::new(module);
         context.main_module = module.as_ref() as *const OpcodeModule as *mut OpcodeModule;
         context.add_module(module.clone());
@@ -121,6 +208,30 @@ impl InterpreterCompiler {
                     None => return Err(KaramelErrorType::FunctionNotFound(name.to_string()))
                 };
             },
+            KaramelAstType::ClassDefination { name, fields: _, methods } => {
+                let class = match module.classes.borrow().get(name) {
+                    Some(class) => class.clone(),
+                    None => return Err(KaramelErrorType::FunctionNotFound(name.to_string()))
+                };
+
+                for method in methods {
+                    if let KaramelAstType::FunctionDefination { name: method_name, arguments: _, body } = &**method {
+                        match class.get_element(None, Rc::new(method_name.to_string())) {
+                            Some(ClassProperty::Function(reference)) => {
+                                functions.push(reference.clone());
+                                self.get_function_definations(module.clone(), body.clone(), functions, context, reference.storage_index)?;
+                            },
+                            _ => return Err(KaramelErrorType::FunctionNotFound(method_name.to_string()))
+                        };
+                    }
+                }
+
+                /* Constructor function */
+                match context.get_function(name.to_string(), module.get_path(), storage_index) {
+                    Some(reference) => functions.push(reference.clone()),
+                    None => return Err(KaramelErrorType::FunctionNotFound(name.to_string()))
+                };
+            },
             KaramelAstType::Block(blocks) => {
                 for block in blocks {
                     self.get_function_definations(module.clone(), block.clone(), functions, context, storage_index)?;
@@ -140,15 +251,120 @@ impl InterpreterCompiler {
                 self.check_prohibited_names(argument)?;
             }
 
+            if function.arguments.len() > MAX_ARGUMENT_SIZE {
+                return Err(KaramelErrorType::ArgumentLimitExceeded(MAX_ARGUMENT_SIZE));
+            }
+
             self.check_prohibited_names(&function.name)?;
+
+            /* Errors at the function entry are shown at the first statement */
+            let body = function.opcode_body.as_ref().unwrap();
+            let first_statement = match &**body {
+                KaramelAstType::Block(asts) => asts.first().map(|ast| &**ast),
+                ast => Some(ast)
+            };
+
+            if let Some(location) = first_statement.and_then(|ast| context.source_map.get_statement(ast)) {
+                context.opcode_generator.add_source_location(location);
+            }
+
             context.opcode_generator.create_function_definition(function.clone());
-            self.generate_opcode(module.clone(), &function.opcode_body.as_ref().unwrap(), &function.opcode_body.as_ref().unwrap(), context, function.storage_index as usize)?;
+
+            match &**function.opcode_body.as_ref().unwrap() {
+                KaramelAstType::ClassDefination { name: _, fields, methods: _ } => self.generate_class_constructor(module.clone(), function.clone(), fields, context)?,
+                _ => {
+                    self.generate_default_arguments(module.clone(), function.clone(), context)?;
+                    self.generate_opcode(module.clone(), &function.opcode_body.as_ref().unwrap(), &function.opcode_body.as_ref().unwrap(), context, function.storage_index as usize)?
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    fn generate_default_arguments(&self, module: Rc<OpcodeModule>, function: Rc<FunctionReference>, context: &mut KaramelCompilerContext) -> CompilerResult {
+        /* Default values are calculated only if the caller did not pass the argument */
+        for (index, parameter) in function.parameters.iter().enumerate() {
+            if let Some(default) = &parameter.default {
+                context.opcode_generator.create_default_argument(index as u8);
+                let passed_location = self.create_compare(context);
+
+                self.generate_opcode(module.clone(), default, &KaramelAstType::None, context, function.storage_index)?;
+                context.opcode_generator.create_store(index as u16);
+                context.opcode_generator.subtract_location(passed_location.clone(), context.opcode_generator.build_current_location(), passed_location.clone());
+            }
+        }
+        Ok(())
+    }
+
+    fn generate_class_constructor(&self, module: Rc<OpcodeModule>, function: Rc<FunctionReference>, fields: &Vec<Rc<KaramelDictItem>>, context: &mut KaramelCompilerContext) -> CompilerResult {
+        /*
+        ╔════════════════════╗
+        ║   FIELD VALUES     ║
+        ╠════════════════════╣
+        ║   CREATE OBJECT    ║
+        ╠════════════════════╣
+        ║   CALL 'başlat'    ║
+        ╠════════════════════╣
+        ║   RETURN OBJECT    ║
+        ╚════════════════════╝
+        */
+        let storage_index = function.storage_index;
+
+        for field in fields.iter().rev() {
+            self.generate_primative(field.key.clone(), &KaramelAstType::None, context, storage_index)?;
+            self.generate_opcode(module.clone(), &field.value, &KaramelAstType::None, context, storage_index)?;
+        }
+
+        let class_location = match context.storages[storage_index].get_class_constant(function.name.to_string(), module.clone()) {
+            Some(location) => location,
+            None => return Err(KaramelErrorType::ValueNotFoundInStorage)
+        };
+
+        let instance_location = match context.storages[storage_index].get_variable_location(CLASS_INSTANCE_NAME) {
+            Some(location) => location,
+            None => return Err(KaramelErrorType::ValueNotFoundInStorage)
+        };
+
+        self.check_item_size(fields.len())?;
+        context.opcode_generator.create_constant(class_location);
+        context.opcode_generator.create_init_class(fields.len());
+        context.opcode_generator.create_store(instance_location);
+
+        let has_constructor = match module.classes.borrow().get(&function.name) {
+            Some(class) => class.has_element(None, Rc::new(CLASS_CONSTRUCTOR_NAME.to_string())),
+            None => false
+        };
+
+        if has_constructor {
+            for index in 0..function.arguments.len() {
+                context.opcode_generator.create_load(index as u16);
+            }
+
+            context.opcode_generator.create_load(instance_location);
+            self.generate_primative(Rc::new(KaramelPrimative::Text(Rc::new(CLASS_CONSTRUCTOR_NAME.to_string()))), &KaramelAstType::None, context, storage_index)?;
+            context.opcode_generator.add_opcode(VmOpCode::GetItem);
+            context.opcode_generator.create_call_stack(function.arguments.len() as u8, CallFlag::FORWARD_ARGUMENTS);
         }
 
+        context.opcode_generator.create_load(instance_location);
+        context.opcode_generator.add_opcode(VmOpCode::Return);
         Ok(())
     }
 
+    /* Operations are located with one of their operands, runtime errors of the operation are shown at the operator */
+    fn add_operation_location(&self, operand: &KaramelAstType, context: &mut KaramelCompilerContext) {
+        if let Some(location) = context.source_map.get_statement(operand) {
+            context.opcode_generator.add_source_location(location);
+        }
+    }
+
     fn generate_opcode(&self, module: Rc<OpcodeModule>, ast: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        /* Statement positions are used for the runtime errors */
+        if let Some(location) = context.source_map.get_statement(ast) {
+            context.opcode_generator.add_source_location(location);
+        }
+
         match ast {
             KaramelAstType::Assignment { variable, operator, expression } => self.generate_assignment(module.clone(), variable, operator, expression, context, storage_index),
             KaramelAstType::Symbol(variable) => self.generate_symbol(module.clone(), variable, upper_ast, context, storage_index),
@@ -166,11 +382,21 @@ impl InterpreterCompiler {
             KaramelAstType::Loop { loop_type, body } => self.generate_loop(module.clone(), loop_type, body, upper_ast, context, storage_index),
             KaramelAstType::Break => self.generate_break(upper_ast, context, storage_index),
             KaramelAstType::Continue => self.generate_continue(upper_ast, context, storage_index),
+            KaramelAstType::TryCatch { body, variable, catch_body } => self.generate_try_catch(module.clone(), body, variable, catch_body, upper_ast, context, storage_index),
+            KaramelAstType::Interpolation(items) => self.generate_interpolation(module.clone(), items, upper_ast, context, storage_index),
+            KaramelAstType::Conditional { condition, true_expression, false_expression } => self.generate_conditional(module.clone(), condition, true_expression, false_expression, upper_ast, context, storage_index),
+            KaramelAstType::Match { expression, cases } => self.generate_match(module.clone(), expression, cases, upper_ast, context, storage_index),
+            /* Named arguments are collected by the function call */
+            KaramelAstType::NamedArgument { name: _, expression } => self.generate_opcode(module.clone(), expression, upper_ast, context, storage_index),
+            KaramelAstType::Throw(expression) => self.generate_throw(module.clone(), expression, upper_ast, context, storage_index),
             KaramelAstType::Return(expression) => self.generate_return(module.clone(), expression, upper_ast, context, storage_index),
             KaramelAstType::IfStatement {condition, body, else_body, else_if} => self.generate_if_condition(module.clone(),condition, body, else_body, else_if, upper_ast, context, storage_index),
             KaramelAstType::Indexer {body, indexer} => self.generate_indexer(module.clone(), body, indexer, upper_ast, context, storage_index),
+            KaramelAstType::Slice {body, start, end, step} => self.generate_slice(module.clone(), body, start, end, step, upper_ast, context, storage_index),
             KaramelAstType::None => self.generate_none(context, storage_index),
             KaramelAstType::FunctionDefination{name: _, arguments: _, body: _} => Ok(()),
+            KaramelAstType::ClassDefination{name: _, fields: _, methods: _} => Ok(()),
+            KaramelAstType::Lambda{arguments, body: _, storage_index: lambda_storage_index} => self.generate_lambda(module.clone(), arguments, lambda_storage_index.get(), context, storage_index),
             KaramelAstType::ModulePath(name) => self.generate_function_map(name, context, storage_index),
             KaramelAstType::Load(names) => self.generate_load_module(names, context),
         }
@@ -255,7 +481,16 @@ impl InterpreterCompiler {
         }
     }
 
+    /* Item counts are written as two bytes */
+    fn check_item_size(&self, size: usize) -> CompilerResult {
+        match size > MAX_ITEM_SIZE {
+            true => Err(KaramelErrorType::ItemLimitExceeded(MAX_ITEM_SIZE)),
+            false => Ok(())
+        }
+    }
+
     fn generate_list(&self, module: Rc<OpcodeModule>, list: &Vec<Rc<KaramelAstType>>, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        self.check_item_size(list.len())?;
         for item in list.iter().rev() {
             self.generate_opcode(module.clone(), item, upper_ast, context, storage_index)?;
         }
@@ -264,6 +499,7 @@ impl InterpreterCompiler {
     }
 
     fn generate_dict(&self, module: Rc<OpcodeModule>, dict: &Vec<Rc<KaramelDictItem>>, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        self.check_item_size(dict.len())?;
         for item in dict.iter().rev() {
             self.generate_primative(item.key.clone(), upper_ast, context, storage_index)?;
             self.generate_opcode(module.clone(), &item.value, upper_ast, context, storage_index)?;
@@ -272,7 +508,7 @@ impl InterpreterCompiler {
         Ok(())
     }
 
-    fn generate_func_call_by_name(&self, name :&String, module_path: &Vec<String>, arguments: &Vec<Rc<KaramelAstType>>, assign_to_temp: bool, context: &mut KaramelCompilerContext, storage_index: usize) -> Result<bool, KaramelErrorType> {
+    fn generate_func_call_by_name(&self, name :&String, module_path: &Vec<String>, argument_size: u8, call_flags: CallFlag, context: &mut KaramelCompilerContext, storage_index: usize) -> Result<bool, KaramelErrorType> {
         let function_search = context.get_function(name.to_string(), module_path, storage_index);
 
         match function_search {
@@ -280,7 +516,7 @@ impl InterpreterCompiler {
                 let search_location = context.storages[storage_index].get_constant_location(Rc::new(KaramelPrimative::Function(function_ref.clone(), None)));
                 match search_location {
                     Some(location) => {
-                        context.opcode_generator.create_call(location, arguments.len() as u8, assign_to_temp);
+                        context.opcode_generator.create_call(location, argument_size, call_flags);
                         return Ok(true);
                     },
                     _ => return Err(KaramelErrorType::FunctionNotFound(function_ref.name.to_string()))
@@ -293,7 +529,7 @@ impl InterpreterCompiler {
             /* Variable found */
             Some(location) => {
                 context.opcode_generator.create_load(location);
-                context.opcode_generator.create_call_stack(arguments.len() as u8, assign_to_temp);
+                context.opcode_generator.create_call_stack(argument_size, call_flags);
                 return Ok(true);
             },
             /* Variable not found, lets check for function */
@@ -308,20 +544,19 @@ impl InterpreterCompiler {
         if let KaramelAstType::FuncCall { func_name_expression, arguments, assign_to_temp: _ } = indexer {
             match &**func_name_expression {
                 KaramelAstType::Symbol(function_name) => {
-                            /* Build arguments */
-                    for argument in arguments {
-                        self.generate_opcode(module.clone(), argument, upper_ast, context, storage_index)?;
-                    }
-                    
+                    /* Build arguments */
+                    let (argument_size, call_flags) = self.generate_arguments(module.clone(), arguments, assign_to_temp, upper_ast, context, storage_index)?;
+
                     self.generate_opcode(module.clone(), source, &KaramelAstType::None, context, storage_index)?;
                     //todo: Pass real object to function as a parameter.
                     
                     let search_location = context.storages[storage_index].get_constant_location(Rc::new(KaramelPrimative::Text(Rc::new(function_name.to_string()))));
                     match search_location {
                         Some(location) => {
+                            self.add_operation_location(func_name_expression, context);
                             context.opcode_generator.create_constant(location);
                             context.opcode_generator.add_opcode(VmOpCode::GetItem);
-                            context.opcode_generator.create_call_stack(arguments.len() as u8, assign_to_temp);
+                            context.opcode_generator.create_call_stack(argument_size, call_flags);
                             return Ok(());
                         },
                         _ => return Err(KaramelErrorType::FunctionNotFound(function_name.to_string()))
@@ -347,13 +582,12 @@ impl InterpreterCompiler {
 
     fn generate_func_call(&self, module: Rc<OpcodeModule>, func_name_expression: &KaramelAstType, arguments: &Vec<Rc<KaramelAstType>>, assign_to_temp: bool,  upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
         /* Build arguments */
-        for argument in arguments {
-            self.generate_opcode(module.clone(), argument, upper_ast, context, storage_index)?;
-        }
+        let (argument_size, call_flags) = self.generate_arguments(module.clone(), arguments, assign_to_temp, upper_ast, context, storage_index)?;
+        self.add_operation_location(func_name_expression, context);
 
         match &func_name_expression {
             KaramelAstType::Symbol(function_name) => {
-                let result = self.generate_func_call_by_name(&function_name, module.get_path(), &arguments, assign_to_temp, context, storage_index)?;
+                let result = self.generate_func_call_by_name(&function_name, module.get_path(), argument_size, call_flags, context, storage_index)?;
                 match result {
                     true => return Ok(()),
                     false => {
@@ -365,12 +599,12 @@ impl InterpreterCompiler {
 
             KaramelAstType::FuncCall {func_name_expression, arguments: inner_arguments, assign_to_temp: _} => {
                 self.generate_func_call(module.clone(), func_name_expression, inner_arguments, true, upper_ast, context, storage_index)?;
-                context.opcode_generator.create_call_stack(arguments.len() as u8, assign_to_temp);
+                context.opcode_generator.create_call_stack(argument_size, call_flags);
                 return Ok(());
             },
 
             KaramelAstType::ModulePath(names) => {
-                let result = self.generate_func_call_by_name(&names[names.len() - 1].to_string(), &names[0..(names.len()-1)].to_vec(), &arguments, assign_to_temp, context, storage_index)?;
+                let result = self.generate_func_call_by_name(&names[names.len() - 1].to_string(), &names[0..(names.len()-1)].to_vec(), argument_size, call_flags, context, storage_index)?;
                 match result {
                     true => return Ok(()),
                     false =>  return Err(KaramelErrorType::FunctionNotFound(names[names.len() - 1].to_string()))
@@ -378,13 +612,44 @@ impl InterpreterCompiler {
             },
             _ => {
                 self.generate_opcode(module.clone(), func_name_expression, upper_ast, context, storage_index)?;
-                context.opcode_generator.create_call_stack(arguments.len() as u8, assign_to_temp);
+                context.opcode_generator.create_call_stack(argument_size, call_flags);
                 return Ok(());
             }
         }
     }
 
+    /* Positional arguments are pushed in order and named arguments are passed as a dictionary after them */
+    fn generate_arguments(&self, module: Rc<OpcodeModule>, arguments: &Vec<Rc<KaramelAstType>>, assign_to_temp: bool, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> Result<(u8, CallFlag), KaramelErrorType> {
+        if arguments.len() > MAX_ARGUMENT_SIZE {
+            return Err(KaramelErrorType::ArgumentLimitExceeded(MAX_ARGUMENT_SIZE));
+        }
+
+        let mut call_flags = CallFlag::from(assign_to_temp);
+        let mut named_arguments = Vec::new();
+
+        for argument in arguments {
+            match &**argument {
+                KaramelAstType::NamedArgument { name, expression } => named_arguments.push((name, expression)),
+                _ => self.generate_opcode(module.clone(), argument, upper_ast, context, storage_index)?
+            };
+        }
+
+        if named_arguments.is_empty() {
+            return Ok((arguments.len() as u8, call_flags));
+        }
+
+        for (name, expression) in named_arguments.iter().rev() {
+            self.generate_primative(Rc::new(KaramelPrimative::Text(Rc::new(name.to_string()))), upper_ast, context, storage_index)?;
+            self.generate_opcode(module.clone(), expression, upper_ast, context, storage_index)?;
+        }
+
+        context.opcode_generator.create_init_dict(named_arguments.len());
+        call_flags |= CallFlag::NAMED_ARGUMENTS;
+        Ok(((arguments.len() - named_arguments.len() + 1) as u8, call_flags))
+    }
+
     fn generate_break(&self, _: &KaramelAstType, context: &mut KaramelCompilerContext, _: usize) -> CompilerResult {       
+        self.exit_try_blocks(context);
         let location = context.opcode_generator.current_location();
         context.opcode_generator.add_break_location(location.clone());
         context.opcode_generator.create_jump(location.clone());
@@ -392,24 +657,273 @@ impl InterpreterCompiler {
     }
 
     fn generate_continue(&self, _: &KaramelAstType, context: &mut KaramelCompilerContext, _: usize) -> CompilerResult {       
+        self.exit_try_blocks(context);
         let location = context.opcode_generator.current_location();
         context.opcode_generator.add_continue_location(location.clone());
         context.opcode_generator.create_jump(location.clone());
         Ok(())
     }
 
-    fn generate_return(&self, module: Rc<OpcodeModule>, expression: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+    /* Leaving loop from 'dene' block, exception handlers need to be removed */
+    fn exit_try_blocks(&self, context: &mut KaramelCompilerContext) {
+        for _ in 0..context.opcode_generator.loop_try_depth() {
+            context.opcode_generator.add_opcode(VmOpCode::TryEnd);
+        }
+    }
+
+    fn generate_match(&self, module: Rc<OpcodeModule>, expression: &KaramelAstType, cases: &Vec<Rc<KaramelMatchCase>>, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        /*
+        ╔════════════════════╗
+        ║   STORE VALUE      ║
+        ╠════════════════════╣
+        ║   PATTERN CHECKS   ║──┐
+        ╠════════════════════╣  │
+        ║   GUARD CHECK      ║──┤
+        ╠════════════════════╣  │
+        ║   CASE BODY        ║  │
+        ╠════════════════════╣  │
+        ║   JUMP TO END      ║  │
+        ╠════════════════════╣  │
+        ║   NEXT CASE ...    ║<─┘
+        ╚════════════════════╝
+        */
+        let mut exit_locations: Vec<Rc<OpcodeLocation>> = Vec::new();
+        let value_location = self.get_match_value_location(0, context, storage_index)?;
+
         self.generate_opcode(module.clone(), expression, upper_ast, context, storage_index)?;
+        context.opcode_generator.create_store(value_location);
+
+        for case in cases.iter() {
+            let mut failed_locations: Vec<Rc<OpcodeLocation>> = Vec::new();
+            self.generate_pattern(&case.pattern, value_location, 0, &mut failed_locations, context, storage_index)?;
+
+            if let Some(guard) = &case.guard {
+                self.generate_opcode(module.clone(), guard, upper_ast, context, storage_index)?;
+                failed_locations.push(self.create_compare(context));
+            }
+
+            self.generate_opcode(module.clone(), &case.body, upper_ast, context, storage_index)?;
+            self.create_exit_jump(context, &mut exit_locations);
+
+            for failed_location in failed_locations {
+                context.opcode_generator.subtract_location(failed_location.clone(), context.opcode_generator.build_current_location(), failed_location.clone());
+            }
+        }
+
+        for exit_location in exit_locations {
+            context.opcode_generator.set_current_location(exit_location);
+        }
+
+        Ok(())
+    }
+
+    fn get_match_value_location(&self, depth: usize, context: &mut KaramelCompilerContext, storage_index: usize) -> Result<u16, KaramelErrorType> {
+        match context.storages[storage_index].get_variable_location(&format!("{}{}", MATCH_VALUE_NAME, depth)) {
+            Some(location) => Ok(location),
+            None => Err(KaramelErrorType::ValueNotFoundInStorage)
+        }
+    }
+
+    fn get_variable_location(&self, name: &str, context: &mut KaramelCompilerContext, storage_index: usize) -> Result<u16, KaramelErrorType> {
+        match context.storages[storage_index].get_variable_location(name) {
+            Some(location) => Ok(location),
+            None => Err(KaramelErrorType::ValueNotFoundInStorage)
+        }
+    }
+
+    /* Check the value at the memory location. Failed checks jump to the next case */
+    fn generate_pattern(&self, pattern: &KaramelPattern, source: u16, depth: usize, failed_locations: &mut Vec<Rc<OpcodeLocation>>, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        match pattern {
+            KaramelPattern::Wildcard => (),
+            KaramelPattern::Variable(name) => {
+                let location = self.get_variable_location(name, context, storage_index)?;
+                context.opcode_generator.create_load(source);
+                context.opcode_generator.create_store(location);
+            },
+            KaramelPattern::Literal(literal) => {
+                context.opcode_generator.create_load(source);
+                if let KaramelAstType::Primative(primative) = &**literal {
+                    self.generate_primative(primative.clone(), &KaramelAstType::None, context, storage_index)?;
+                }
+                context.opcode_generator.add_opcode(VmOpCode::Equal);
+                failed_locations.push(self.create_compare(context));
+            },
+            KaramelPattern::List { items, rest } => {
+                self.check_item_size(items.len())?;
+                context.opcode_generator.create_load(source);
+                context.opcode_generator.create_pattern(PatternType::List { size: items.len(), has_rest: rest.is_some() });
+                failed_locations.push(self.create_compare(context));
+
+                for (index, item) in items.iter().enumerate() {
+                    if let KaramelPattern::Wildcard = &**item {
+                        continue;
+                    }
+
+                    context.opcode_generator.create_load(source);
+                    self.generate_primative(Rc::new(KaramelPrimative::Number(index as f64)), &KaramelAstType::None, context, storage_index)?;
+                    context.opcode_generator.add_opcode(VmOpCode::GetItem);
+                    self.generate_inner_pattern(item, depth, failed_locations, context, storage_index)?;
+                }
+
+                /* Rest of the items are copied with slice */
+                if let Some(rest) = rest {
+                    let location = self.get_variable_location(rest, context, storage_index)?;
+                    context.opcode_generator.create_load(source);
+                    self.generate_primative(Rc::new(KaramelPrimative::Number(items.len() as f64)), &KaramelAstType::None, context, storage_index)?;
+                    self.generate_primative(Rc::new(KaramelPrimative::Empty), &KaramelAstType::None, context, storage_index)?;
+                    self.generate_primative(Rc::new(KaramelPrimative::Empty), &KaramelAstType::None, context, storage_index)?;
+                    context.opcode_generator.add_opcode(VmOpCode::Slice);
+                    context.opcode_generator.create_store(location);
+                }
+            },
+            KaramelPattern::Dict(items) => {
+                self.check_item_size(items.len())?;
+                for (key, _) in items.iter() {
+                    self.generate_primative(key.clone(), &KaramelAstType::None, context, storage_index)?;
+                }
+
+                context.opcode_generator.create_load(source);
+                context.opcode_generator.create_pattern(PatternType::Dict { size: items.len() });
+                failed_locations.push(self.create_compare(context));
+
+                for (key, item) in items.iter() {
+                    if let KaramelPattern::Wildcard = &**item {
+                        continue;
+                    }
+
+                    context.opcode_generator.create_load(source);
+                    self.generate_primative(key.clone(), &KaramelAstType::None, context, storage_index)?;
+                    context.opcode_generator.add_opcode(VmOpCode::GetItem);
+                    self.generate_inner_pattern(item, depth, failed_locations, context, storage_index)?;
+                }
+            }
+        };
+        Ok(())
+    }
+
+    /* Inner value is at the stack */
+    fn generate_inner_pattern(&self, pattern: &KaramelPattern, depth: usize, failed_locations: &mut Vec<Rc<OpcodeLocation>>, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        match pattern {
+            KaramelPattern::Variable(name) => {
+                let location = self.get_variable_location(name, context, storage_index)?;
+                context.opcode_generator.create_store(location);
+                Ok(())
+            },
+            _ => {
+                let location = self.get_match_value_location(depth + 1, context, storage_index)?;
+                context.opcode_generator.create_store(location);
+                self.generate_pattern(pattern, location, depth + 1, failed_locations, context, storage_index)
+            }
+        }
+    }
+
+    fn generate_try_catch(&self, module: Rc<OpcodeModule>, body: &KaramelAstType, variable: &Option<String>, catch_body: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        /*
+        ╔════════════════════╗
+        ║   TRY START        ║
+        ╠════════════════════╣
+        ║   TRY BODY         ║
+        ╠════════════════════╣
+        ║   TRY END          ║
+        ╠════════════════════╣
+        ║   JUMP TO OUT OF   ║
+        ║   TRY CATCH        ║
+        ╠════════════════════╣
+        ║   STORE ERROR      ║
+        ╠════════════════════╣
+        ║   CATCH BODY       ║
+        ╚════════════════════╝
+        */
+        let catch_location = context.opcode_generator.create_location();
+        let exit_location = context.opcode_generator.create_location();
+
+        context.opcode_generator.create_try_start(variable.is_some(), catch_location.clone());
+        context.opcode_generator.try_started();
+        self.generate_opcode(module.clone(), body, upper_ast, context, storage_index)?;
+        context.opcode_generator.try_finished();
+
+        context.opcode_generator.add_opcode(VmOpCode::TryEnd);
+        context.opcode_generator.create_jump(exit_location.clone());
+
+        /* Error information is at the stack */
+        context.opcode_generator.set_current_location(catch_location);
+        if let Some(variable) = variable {
+            match context.storages[storage_index].get_variable_location(variable) {
+                Some(location) => context.opcode_generator.create_store(location),
+                None => return Err(KaramelErrorType::ValueNotFoundInStorage)
+            };
+        }
+
+        self.generate_opcode(module.clone(), catch_body, upper_ast, context, storage_index)?;
+        context.opcode_generator.set_current_location(exit_location);
+        Ok(())
+    }
+
+    fn generate_interpolation(&self, module: Rc<OpcodeModule>, items: &[Rc<KaramelAstType>], upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        self.check_item_size(items.len())?;
+        for item in items {
+            self.generate_opcode(module.clone(), item, upper_ast, context, storage_index)?;
+        }
+
+        context.opcode_generator.create_concat(items.len());
+        Ok(())
+    }
+
+    fn generate_throw(&self, module: Rc<OpcodeModule>, expression: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        self.generate_opcode(module.clone(), expression, upper_ast, context, storage_index)?;
+        context.opcode_generator.add_opcode(VmOpCode::Throw);
+        Ok(())
+    }
+
+    fn generate_return(&self, module: Rc<OpcodeModule>, expression: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        match self.is_tail_call(module.clone(), expression, context, storage_index) {
+            true => self.generate_tail_call(module.clone(), expression, upper_ast, context, storage_index)?,
+            false => self.generate_opcode(module.clone(), expression, upper_ast, context, storage_index)?
+        };
+
+        /* Return is not executed after the tail call, but it is kept for the calls that could not use the same memory */
         context.opcode_generator.add_opcode(VmOpCode::Return);
         Ok(())
     }
 
+    /* Only self recursive calls are using same memory, other functions could need a bigger memory */
+    fn is_tail_call(&self, module: Rc<OpcodeModule>, expression: &KaramelAstType, context: &KaramelCompilerContext, storage_index: usize) -> bool {
+        if context.opcode_generator.try_depth() > 0 {
+            return false;
+        }
+
+        match expression {
+            KaramelAstType::FuncCall { func_name_expression, arguments: _, assign_to_temp: _ } => match &**func_name_expression {
+                KaramelAstType::Symbol(name) => matches!(context.get_function(name.to_string(), module.get_path(), storage_index), Some(function) if function.storage_index == storage_index),
+                _ => false
+            },
+            _ => false
+        }
+    }
+
+    fn generate_tail_call(&self, module: Rc<OpcodeModule>, expression: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        if let KaramelAstType::FuncCall { func_name_expression, arguments, assign_to_temp } = expression {
+            if let KaramelAstType::Symbol(name) = &**func_name_expression {
+                let (argument_size, call_flags) = self.generate_arguments(module.clone(), arguments, assign_to_temp.get(), upper_ast, context, storage_index)?;
+                self.add_operation_location(func_name_expression, context);
+                self.generate_func_call_by_name(name, module.get_path(), argument_size, call_flags | CallFlag::TAIL_CALL, context, storage_index)?;
+            }
+        }
+        Ok(())
+    }
+
     fn generate_loop(&self, module: Rc<OpcodeModule>, loop_type: &LoopType, body: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
         /* Backup loop informations */
         context.opcode_generator.loop_started();
         let mut compare_location: Option<Rc<OpcodeLocation>> = None;
 
         let (variable, control, increment) = match loop_type {
+            LoopType::Iterate { variables, source } => {
+                self.generate_iterate_loop(module.clone(), variables, source, body, upper_ast, context, storage_index)?;
+                context.opcode_generator.loop_finished();
+                return Ok(());
+            },
+
             LoopType::Endless => {
                 (None, None, None)
             },
@@ -463,6 +977,80 @@ impl InterpreterCompiler {
         Ok(())
     }
 
+    fn generate_iterate_loop(&self, module: Rc<OpcodeModule>, variables: &Vec<String>, source: &KaramelAstType, body: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        /* Loop group already started for current loop */
+        let depth = context.opcode_generator.loop_depth() - 1;
+        let pair = variables.len() == 2;
+
+        let (source_location, index_location, zero_location) = {
+            let storage = &context.storages[storage_index];
+            (storage.get_variable_location(&format!("{}{}", ITERATOR_SOURCE_NAME, depth)),
+             storage.get_variable_location(&format!("{}{}", ITERATOR_INDEX_NAME, depth)),
+             storage.get_constant_location(Rc::new(KaramelPrimative::Number(0.0))))
+        };
+
+        let (source_location, index_location, zero_location) = match (source_location, index_location, zero_location) {
+            (Some(source_location), Some(index_location), Some(zero_location)) => (source_location, index_location, zero_location),
+            _ => return Err(KaramelErrorType::ValueNotFoundInStorage)
+        };
+
+        /* Prepare iterator list and reset iterator index */
+        self.generate_opcode(module.clone(), source, upper_ast, context, storage_index)?;
+        context.opcode_generator.create_init_iterator(pair);
+        context.opcode_generator.create_store(source_location);
+        context.opcode_generator.create_constant(zero_location);
+        context.opcode_generator.create_store(index_location);
+
+        let start_location = context.opcode_generator.current_location();
+        let iterate_location = context.opcode_generator.current_location();
+        context.opcode_generator.create_iterate(source_location, index_location, pair, iterate_location.clone());
+
+        /* Values are at stack with reverse order */
+        for variable in variables.iter().rev() {
+            match context.storages[storage_index].get_variable_location(variable) {
+                Some(location) => context.opcode_generator.create_store(location),
+                None => return Err(KaramelErrorType::ValueNotFoundInStorage)
+            };
+        }
+
+        self.generate_opcode(module.clone(), body, upper_ast, context, storage_index)?;
+        context.opcode_generator.create_jump(start_location.clone());
+
+        let end_location = context.opcode_generator.current_location();
+        context.opcode_generator.subtract_location(iterate_location.clone(), end_location.clone(), iterate_location.clone());
+
+        context.opcode_generator.set_breaks_locations(end_location.clone());
+        context.opcode_generator.set_continues_locations(start_location.clone());
+        Ok(())
+    }
+
+    fn generate_lambda(&self, module: Rc<OpcodeModule>, arguments: &Vec<KaramelFunctionArgument>, lambda_storage_index: usize, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        let name = format!("{}{}", LAMBDA_NAME_PREFIX, lambda_storage_index);
+        let function_location = match context.storages[storage_index].get_function_constant(name.to_string(), module.clone()) {
+            Some(location) => location,
+            None => return Err(KaramelErrorType::FunctionNotFound(name))
+        };
+
+        let captures = match &*context.storages[storage_index].constants[function_location as usize].deref() {
+            KaramelPrimative::Function(reference, _) => reference.arguments[arguments.len()..].to_vec(),
+            _ => return Err(KaramelErrorType::FunctionNotFound(name))
+        };
+
+        /* Captured values are copied when the function value created */
+        for capture in captures.iter() {
+            match context.storages[storage_index].get_variable_location(capture) {
+                Some(location) => context.opcode_generator.create_load(location),
+                None => return Err(KaramelErrorType::ValueNotFoundInStorage)
+            };
+        }
+
+        context.opcode_generator.create_constant(function_location);
+        if !captures.is_empty() {
+            context.opcode_generator.create_init_closure(captures.len());
+        }
+        Ok(())
+    }
+
     fn generate_symbol(&self, module: Rc<OpcodeModule>, variable: &String, _: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
         let storage = &context.storages[storage_index];                
         let result = storage.get_function_constant(variable.to_string(), module.clone());
@@ -508,6 +1096,7 @@ impl InterpreterCompiler {
             _ => return Err(KaramelErrorType::OperatorNotValid)
         };
 
+        self.add_operation_location(right_ast, context);
         context.opcode_generator.add_opcode(opcode);
         Ok(())
     }
@@ -529,7 +1118,7 @@ impl InterpreterCompiler {
                     *operator == KaramelOperatorType::Assign {
                         let result = storage.get_constant_location(primative.clone());
                         let primative_location = match result {
-                            Some(index) => index as u8,
+                            Some(index) => index as u16,
                             _ => return Err(KaramelErrorType::ValueNotFoundInStorage)
                         };
 
@@ -544,16 +1133,7 @@ impl InterpreterCompiler {
                     context.opcode_generator.create_load(location);
 
                     self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
-
-                    let opcode = match operator {
-                        KaramelOperatorType::AssignAddition       => VmOpCode::Addition,
-                        KaramelOperatorType::AssignDivision       => VmOpCode::Division,
-                        KaramelOperatorType::AssignMultiplication => VmOpCode::Multiply,
-                        KaramelOperatorType::AssignSubtraction    => VmOpCode::Subraction,
-                        _ => return Err(KaramelErrorType::OperatorNotValid)
-                    };
-
-                    context.opcode_generator.add_opcode(opcode);
+                    context.opcode_generator.add_opcode(self.get_assignment_opcode(operator)?);
                 } else {
                     self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
                 }
@@ -565,8 +1145,18 @@ impl InterpreterCompiler {
             KaramelAstType::Indexer {body, indexer} => {
                 self.generate_opcode(module.clone(), body, &KaramelAstType::None, context, storage_index)?;
                 self.generate_opcode(module.clone(), indexer, &KaramelAstType::None, context, storage_index)?;
-                self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
+
+                if *operator != KaramelOperatorType::Assign {
+
+                    /* Load current item to stack */
+                    self.generate_indexer(module.clone(), body, indexer, &KaramelAstType::None, context, storage_index)?;
+                    self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
+                    context.opcode_generator.add_opcode(self.get_assignment_opcode(operator)?);
+                } else {
+                    self.generate_opcode(module.clone(), expression_ast, &KaramelAstType::None, context, storage_index)?;
+                }
                 
+                self.add_operation_location(body, context);
                 context.opcode_generator.add_opcode(VmOpCode::SetItem);
                 Ok(())
             },
@@ -574,6 +1164,16 @@ impl InterpreterCompiler {
         }
     }
 
+    fn get_assignment_opcode(&self, operator: &KaramelOperatorType) -> Result<VmOpCode, KaramelErrorType> {
+        match operator {
+            KaramelOperatorType::AssignAddition       => Ok(VmOpCode::Addition),
+            KaramelOperatorType::AssignDivision       => Ok(VmOpCode::Division),
+            KaramelOperatorType::AssignMultiplication => Ok(VmOpCode::Multiply),
+            KaramelOperatorType::AssignSubtraction    => Ok(VmOpCode::Subraction),
+            _ => Err(KaramelErrorType::OperatorNotValid)
+        }
+    }
+
     fn generate_binary(&self, module: Rc<OpcodeModule>, left_ast: &KaramelAstType, operator: &KaramelOperatorType, right_ast: &KaramelAstType, _: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult { 
         self.generate_opcode(module.clone(), left_ast, &KaramelAstType::None, context, storage_index)?;
         self.generate_opcode(module.clone(), right_ast, &KaramelAstType::None, context, storage_index)?;
@@ -586,6 +1186,7 @@ impl InterpreterCompiler {
             _ => return Err(KaramelErrorType::OperatorNotValid)
         };
 
+        self.add_operation_location(right_ast, context);
         context.opcode_generator.add_opcode(opcode);
         Ok(())
     }
@@ -643,6 +1244,24 @@ impl InterpreterCompiler {
         location.clone()
     }
 
+    fn generate_conditional(&self, module: Rc<OpcodeModule>, condition: &KaramelAstType, true_expression: &KaramelAstType, false_expression: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        let mut exit_locations: Vec<Rc<OpcodeLocation>> = Vec::new();
+
+        self.generate_opcode(module.clone(), condition, upper_ast, context, storage_index)?;
+        let false_location = self.create_compare(context);
+        self.generate_opcode(module.clone(), true_expression, upper_ast, context, storage_index)?;
+        self.create_exit_jump(context, &mut exit_locations);
+
+        context.opcode_generator.subtract_location(false_location.clone(), context.opcode_generator.build_current_location(), false_location.clone());
+        self.generate_opcode(module.clone(), false_expression, upper_ast, context, storage_index)?;
+
+        for exit_location in exit_locations {
+            context.opcode_generator.set_current_location(exit_location);
+        }
+
+        Ok(())
+    }
+
     fn generate_if_condition(&self, module: Rc<OpcodeModule>, condition: &KaramelAstType, body: &KaramelAstType, else_body: &Option<Rc<KaramelAstType>>, else_if: &Vec<Rc<KaramelIfStatementElseItem>>, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
         /*
         ╔════════════════════╗
@@ -704,11 +1323,23 @@ impl InterpreterCompiler {
     fn generate_indexer(&self, module: Rc<OpcodeModule>, body: &KaramelAstType, indexer: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
         self.generate_opcode(module.clone(), body, upper_ast, context, storage_index)?;
         self.generate_opcode(module.clone(), indexer, upper_ast, context, storage_index)?;
+        self.add_operation_location(body, context);
         context.opcode_generator.add_opcode(VmOpCode::GetItem);
 
         Ok(())
     }
 
+    fn generate_slice(&self, module: Rc<OpcodeModule>, body: &KaramelAstType, start: &KaramelAstType, end: &KaramelAstType, step: &KaramelAstType, upper_ast: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult {
+        /* Empty parts are loaded as 'boş' */
+        for ast in [body, start, end, step] {
+            self.generate_opcode(module.clone(), ast, upper_ast, context, storage_index)?;
+        }
+
+        self.add_operation_location(body, context);
+        context.opcode_generator.add_opcode(VmOpCode::Slice);
+        Ok(())
+    }
+
     fn generate_suffix_unary(&self, operator: &KaramelOperatorType, expression: &KaramelAstType, _: &KaramelAstType, context: &mut KaramelCompilerContext, storage_index: usize) -> CompilerResult { 
         if let KaramelAstType::Symbol(variable) = expression {
             let location = match context.storages.get_mut(storage_index).unwrap().get_variable_location(variable) {
@@ -749,7 +1380,7 @@ mod tests {
     use crate::compiler::*;
     use std::vec::Vec;
     use std::rc::Rc;
-    use crate::compiler::ast::{KaramelAstType};
+    use crate::compiler::ast::{KaramelAstType, KaramelFunctionArgument};
     use crate::compiler::storage_builder::StorageBuilder;
     use crate::compiler::function::{FunctionReference};
     use super::module::{OpcodeModule};
@@ -843,7 +1474,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("test".to_string(), vec!["test".to_string()], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("test".to_string(), vec![KaramelFunctionArgument::new("test")], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
 
         let mut functions = Vec::new();
         functions.push(function_define);
@@ -860,7 +1491,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("test".to_string(), vec!["sayı".to_string()], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("test".to_string(), vec![KaramelFunctionArgument::new("sayı")], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
 
         let mut functions = Vec::new();
         functions.push(function_define);
@@ -881,7 +1512,7 @@ mod tests {
         let compiler = InterpreterCompiler {};
         let storage_builder: StorageBuilder = StorageBuilder::new();
 
-        let function_define = FunctionReference::opcode_function("döndür".to_string(), vec!["sayı".to_string()], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
+        let function_define = FunctionReference::opcode_function("döndür".to_string(), vec![KaramelFunctionArgument::new("sayı")], Rc::new(KaramelAstType::None), Rc::new(DummyModule::new()), 0, 0, true);
 
         let mut functions = Vec::new();
         functions.push(function_define);
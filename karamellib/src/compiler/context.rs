@@ -1,12 +1,19 @@
 use std::borrow::Borrow;
-use std::{cell::RefCell, ptr, rc::Rc};
+use std::{mem, ptr, rc::Rc};
 use crate::buildin::num::{NumModule};
 
+use crate::constants::{DEFAULT_MAX_CALL_DEPTH, DEFAULT_STACK_SIZE, MAIN_FUNCTION_NAME};
+use crate::error::KaramelErrorType;
 use crate::types::VmObject;
+use crate::vm::heap::Heap;
+use crate::vm::io::{KaramelIo, StandardIo};
+use crate::vm::limits::{ExecutionLimiter, InterruptHandle};
 use crate::{buildin::{Class, Module, ModuleCollection, base_functions, class::{dict, get_empty_class, list, number, proxy, text}, debug, io}, compiler::scope::Scope};
 
 use super::generator::OpcodeGenerator;
-use super::{KaramelPrimative, StaticStorage, function::{FunctionReference, FunctionType, FunctionFlag}, module::OpcodeModule};
+use super::generator::call::CallFlag;
+use super::source_map::{SourceLocation, SourceMap, StackFrame};
+use super::{KaramelPrimative, StaticStorage, VmOpCode, function::{FunctionReference, FunctionType, FunctionFlag}, module::OpcodeModule};
 
 #[derive(Default)]
 pub struct ExecutionPathInfo {
@@ -14,7 +21,14 @@ pub struct ExecutionPathInfo {
     pub script: Option<String>
 }
 
-const MAX_STACK: usize = 64 * 1024 + 1;
+/* Active 'dene' block information */
+#[derive(Clone, Copy)]
+pub struct ExceptionHandler {
+    pub scope_index: usize,
+    pub stack_ptr: *mut VmObject,
+    pub location: usize,
+    pub has_variable: bool
+}
 
 pub struct KaramelCompilerContext {
     pub execution_path: ExecutionPathInfo,
@@ -29,16 +43,34 @@ pub struct KaramelCompilerContext {
     pub scope_index: usize,
     pub functions : Vec<Rc<FunctionReference>>,
     pub classes : Vec<Rc<dyn Class >>,
-    pub stdout: Option<RefCell<String>>,
-    pub stderr: Option<RefCell<String>>,
+    pub io: Rc<dyn KaramelIo>,
     pub memory_dump: Option<String>,
     pub opcode_dump: Option<String>,
     pub opcodes_ptr: *mut u8,
     pub opcodes_top_ptr: *mut u8,
     pub primative_classes: Vec<Rc<dyn Class>>,
     pub opcode_generator: OpcodeGenerator,
-    pub stack: [VmObject; MAX_STACK],
-    pub stack_ptr: *mut VmObject
+    pub optimize: bool,
+
+    /* Operators raise an error instead of 'boş' for unsupported operand types */
+    pub strict_types: bool,
+    pub stack: Vec<VmObject>,
+    pub stack_ptr: *mut VmObject,
+    pub max_call_depth: usize,
+    pub exception_handlers: Vec<ExceptionHandler>,
+    pub heap: Heap,
+    pub limiter: ExecutionLimiter,
+    pub interrupt: InterruptHandle,
+    pub source_map: SourceMap,
+
+    /* Functions that called by the native functions return to the trampoline, native function call locations are kept
+    for the stack trace */
+    pub callback_trampoline: [u8; 4],
+    pub callback_locations: Vec<*mut u8>,
+
+    /* Location and active function calls of the last error */
+    pub error_location: Option<SourceLocation>,
+    pub stack_trace: Vec<StackFrame>
 }
 
 impl  KaramelCompilerContext {
@@ -55,17 +87,28 @@ impl  KaramelCompilerContext {
             scope_index: 0,
             functions: Vec::new(),
             classes: Vec::new(),
-            stdout: None,
-            stderr: None,
+            io: Rc::new(StandardIo),
             opcodes_ptr: ptr::null_mut(),
             opcodes_top_ptr: ptr::null_mut(),
             primative_classes: Vec::new(),
             main_module: ptr::null_mut(),
             opcode_generator: OpcodeGenerator::new(),
-            stack: [VmObject(0); MAX_STACK],
+            optimize: false,
+            strict_types: false,
+            stack: Vec::new(),
             stack_ptr: ptr::null_mut(),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
             memory_dump: None,
-            opcode_dump: None
+            opcode_dump: None,
+            exception_handlers: Vec::new(),
+            heap: Heap::new(),
+            limiter: ExecutionLimiter::default(),
+            interrupt: InterruptHandle::new(),
+            source_map: SourceMap::new(),
+            callback_trampoline: [VmOpCode::CallStack.into(), 0, CallFlag::ASSIGN_TO_TEMP.bits(), VmOpCode::Halt.into()],
+            callback_locations: Vec::new(),
+            error_location: None,
+            stack_trace: Vec::new()
         };
         
         compiler.primative_classes.push(number::get_primative_class());
@@ -75,14 +118,15 @@ impl  KaramelCompilerContext {
         compiler.primative_classes.push(get_empty_class());
         compiler.primative_classes.push(get_empty_class());
         compiler.primative_classes.push(get_empty_class());
-        compiler.primative_classes.push(get_empty_class());
         compiler.primative_classes.push(proxy::get_primative_class());
         compiler.primative_classes.push(get_empty_class());
+        compiler.primative_classes.push(get_empty_class());
 
         compiler.add_module(base_functions::BaseFunctionsModule::new());
         compiler.add_module(io::IoModule::new());
         compiler.add_module(NumModule::new());
         compiler.add_module(debug::DebugModule::new());
+        compiler.set_stack_size(DEFAULT_STACK_SIZE);
 
         for _ in 0..32 {
             compiler.scopes.push(Scope::empty());
@@ -121,7 +165,7 @@ impl  KaramelCompilerContext {
             for (_, module) in self.modules.iter() {
                 for function_reference in module.get_methods().iter() {
                     let result = match &function_reference.callback {
-                        FunctionType::Native(_) =>
+                        FunctionType::Native(_) | FunctionType::NativeClosure(_) =>
                             function_reference.module.get_path() == module_path && 
                             &function_reference.name == name.borrow(),
                         FunctionType::Opcode => 
@@ -160,6 +204,77 @@ impl  KaramelCompilerContext {
         }
     }
 
+    /// Change the total value count that stack could hold. Must be called before the execution.
+    pub fn set_stack_size(&mut self, size: usize) {
+        /* Last slot is used as a temporary write location before the stack check */
+        self.stack = vec![VmObject(0); size + 1];
+    }
+
+    /// Stack and scopes can not grow more than the limits. Error contains the active function calls.
+    #[inline]
+    pub fn check_stack(&self, count: usize) -> Result<(), KaramelErrorType> {
+        let used = (self.stack_ptr as usize - self.stack.as_ptr() as usize) / mem::size_of::<VmObject>();
+        match used + count >= self.stack.len() || self.scope_index >= self.max_call_depth {
+            true => Err(KaramelErrorType::StackOverflow(self.call_chain())),
+            false => Ok(())
+        }
+    }
+
+    /// Function names from the main code to the current function. Recursive calls are written once with the call count.
+    pub fn call_chain(&self) -> Vec<String> {
+        let mut chain: Vec<(String, usize)> = vec![(MAIN_FUNCTION_NAME.to_string(), 1)];
+        for scope in self.scopes.iter().take(self.scope_index + 1).skip(1) {
+            if scope.function.is_null() {
+                continue;
+            }
+
+            let name = unsafe { (*scope.function).name.to_string() };
+            match chain.last_mut() {
+                Some((last, count)) if *last == name => *count += 1,
+                _ => chain.push((name, 1))
+            };
+        }
+
+        chain.into_iter().map(|(name, count)| match count {
+            1 => name,
+            _ => format!("{} ({} kez)", name, count)
+        }).collect()
+    }
+
+    /// Opcode pointer is at the trampoline while a function called by a native function is starting or returning
+    pub fn is_callback_location(&self, location: *const u8) -> bool {
+        self.callback_trampoline.as_ptr_range().contains(&location)
+    }
+
+    /// Source locations of the active function calls, the current function is the first one.
+    pub fn build_stack_trace(&self) -> Vec<StackFrame> {
+        let mut trace = Vec::new();
+        let mut callback_locations = self.callback_locations.iter().rev();
+        let mut opcode_location = self.opcodes_ptr as usize - self.opcodes_top_ptr as usize;
+
+        for scope_index in (0..self.scope_index + 1).rev() {
+            let scope = &self.scopes[scope_index];
+            let function = match scope.function.is_null() {
+                true => MAIN_FUNCTION_NAME.to_string(),
+                false => unsafe { (*scope.function).name.to_string() }
+            };
+
+            trace.push(self.source_map.create_frame(function, self.source_map.find(opcode_location)));
+
+            /* Caller continues from the call opcode */
+            if !scope.location.is_null() {
+                /* Function called by a native function, caller continues from the native function call */
+                let location = match self.is_callback_location(scope.location) {
+                    true => callback_locations.next().copied().unwrap_or(self.opcodes_top_ptr),
+                    false => scope.location
+                };
+                opcode_location = location as usize - self.opcodes_top_ptr as usize;
+            }
+        }
+
+        trace
+    }
+
     pub fn reset(&mut self) {
         self.opcodes = Vec::new();
     }
@@ -1,36 +1,42 @@
 use std::borrow::Borrow;
-use std::{iter::Skip, rc::Rc, vec::Vec};
+use std::{iter::Skip, ptr, rc::Rc, vec::Vec};
 use std::cell::RefCell;
 use std::cell::Cell;
+use std::collections::HashMap;
 use std::slice::Iter;
 use std::iter::Take;
 use bitflags::bitflags;
 
-use crate::buildin::{DummyModule, Module};
+use crate::buildin::{Class, ClassConfig, ClassProperty, DummyModule, Module};
+use crate::buildin::class::baseclass::BasicInnerClass;
+use crate::constants::{CLASS_CONSTRUCTOR_NAME, CLASS_INSTANCE_NAME};
 use crate::compiler::scope::Scope;
 use crate::error::KaramelErrorType;
 use crate::{inc_memory_index, dec_memory_index, get_memory_index};
 use crate::types::*;
 use crate::compiler::context::KaramelCompilerContext;
+use crate::vm::interpreter::call_function;
+use crate::vm::io::KaramelIo;
 
 use super::module::OpcodeModule;
-use super::{KaramelPrimative, StaticStorage};
-use super::ast::KaramelAstType;
+use super::{KaramelPrimative, StaticStorage, EMPTY_OBJECT};
+use super::ast::{KaramelAstType, KaramelFunctionArgument};
+use super::generator::call::CallFlag;
 use super::storage_builder::StorageBuilder;
 
 pub type NativeCallResult = Result<VmObject, KaramelErrorType>;
 pub type NativeCall       = fn(FunctionParameter) -> NativeCallResult;
+pub type NativeClosure    = Rc<dyn Fn(FunctionParameter) -> NativeCallResult>;
 pub type IndexerGetCall   = fn (VmObject, f64) -> NativeCallResult ;
 pub type IndexerSetCall   = fn (VmObject, f64, VmObject) -> NativeCallResult ;
 
-#[derive(Debug)]
 pub struct FunctionParameter<'a> {
     stack: &'a [VmObject], 
     source: Option<VmObject>, 
     last_position: usize, 
     arg_size: u8,
-    stdout: &'a Option<RefCell<String>>,
-    stderr: &'a Option<RefCell<String>>
+    io: &'a dyn KaramelIo,
+    context: *mut KaramelCompilerContext
 }
 
 pub struct FunctionParameterIterator<'a> {
@@ -38,8 +44,45 @@ pub struct FunctionParameterIterator<'a> {
 }
 
 impl<'a> FunctionParameter<'a> {
-    pub fn new(stack: &'a [VmObject], source: Option<VmObject>, last_position: usize, arg_size: u8, stdout: &'a Option<RefCell<String>>, stderr: &'a Option<RefCell<String>>) -> Self {
-        FunctionParameter { stack, source, last_position, arg_size, stdout, stderr }
+    pub fn new(stack: &'a [VmObject], source: Option<VmObject>, last_position: usize, arg_size: u8, io: &'a dyn KaramelIo) -> Self {
+        FunctionParameter { stack, source, last_position, arg_size, io, context: ptr::null_mut() }
+    }
+
+    /// Running virtual machine that used by the 'call' function
+    pub fn with_context(mut self, context: *mut KaramelCompilerContext) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Call the function with the arguments and return its result. Karamel functions are executed before the native
+    /// function continues, errors that are not handled inside the called function are returned.
+    /// Arguments and the result are kept alive until the native function returns.
+    pub fn call(&self, function: VmObject, arguments: &[VmObject]) -> NativeCallResult {
+        if self.context.is_null() {
+            return Err(KaramelErrorType::FunctionCallNotAvailable);
+        }
+
+        unsafe {
+            let context = &mut *self.context;
+            context.heap.add_root(function);
+            for argument in arguments.iter() {
+                context.heap.add_root(*argument);
+            }
+
+            let result = call_function(context, function, arguments)?;
+            context.heap.add_root(result);
+            Ok(result)
+        }
+    }
+
+    /// Values that copied by the native function are kept alive until the native function returns
+    pub fn keep_alive(&self, objects: &[VmObject]) {
+        if !self.context.is_null() {
+            let heap = unsafe { &mut (*self.context).heap };
+            for object in objects.iter() {
+                heap.add_root(*object);
+            }
+        }
     }
 
     pub fn source(&self) -> Option<VmObject> {
@@ -54,13 +97,16 @@ impl<'a> FunctionParameter<'a> {
     }
 
     pub fn write_to_stdout<'b>(&self, data: &'b str) {
-        match self.stdout {
-            Some(out) => match out.try_borrow_mut() {
-                Ok(mut out_mut) => out_mut.push_str(data),
-                _ => println!("{}", data)
-            },
-            _ => println!("{}", data)
-        };
+        self.io.write(data);
+    }
+
+    pub fn write_to_stderr<'b>(&self, data: &'b str) {
+        self.io.write_err(data);
+    }
+
+    /// Next line of the program's input, 'None' if there is no input
+    pub fn read_line(&self) -> Option<String> {
+        self.io.read_line()
     }
 
     pub fn iter(&self) -> FunctionParameterIterator {
@@ -86,6 +132,7 @@ bitflags! {
         const STATIC       = 0b00000001;
         const IN_CLASS     = 0b00000010;
         const MODULE_LEVEL = 0b00000100;
+        const CLOSURE      = 0b00001000;
     }
 }
 
@@ -95,6 +142,7 @@ pub struct FunctionReference {
     pub flags: FunctionFlag,
     pub name: String,
     pub arguments: Vec<String>,
+    pub parameters: Vec<KaramelFunctionArgument>,
     pub defined_storage_index: usize,
     pub storage_index: usize,
     pub opcode_location: Cell<usize>,
@@ -109,6 +157,7 @@ unsafe impl Sync for FunctionReference {}
 #[derive(Clone)]
 pub enum FunctionType {
     Native(NativeCall),
+    NativeClosure(NativeClosure),
     Opcode
 }
 
@@ -119,9 +168,10 @@ impl Default for FunctionType {
 impl FunctionReference {
     pub fn execute(&self, compiler: &mut KaramelCompilerContext, base: Option<VmObject>) -> Result<(), KaramelErrorType>{
         unsafe {
-            match self.callback {
+            match &self.callback {
                 FunctionType::Native(func) => FunctionReference::native_function_call(&self, func, compiler, base),
-                FunctionType::Opcode => FunctionReference::opcode_function_call(&self,  compiler)
+                FunctionType::NativeClosure(func) => FunctionReference::native_function_call(&self, func.as_ref(), compiler, base),
+                FunctionType::Opcode => FunctionReference::opcode_function_call(&self, compiler, base)
             }
         }
     }
@@ -132,6 +182,7 @@ impl FunctionReference {
             flags: flags,
             name,
             arguments: Vec::new(),
+            parameters: Vec::new(),
             storage_index: 0,
             opcode_location: Cell::new(0),
             used_locations: RefCell::new(Vec::new()),
@@ -148,6 +199,25 @@ impl FunctionReference {
             flags: FunctionFlag::STATIC,
             name,
             arguments: Vec::new(),
+            parameters: Vec::new(),
+            storage_index: 0,
+            opcode_location: Cell::new(0),
+            used_locations: RefCell::new(Vec::new()),
+            defined_storage_index: 0,
+            opcode_body: None,
+            module
+        };
+        Rc::new(reference)
+    }
+
+    /// Native function that keeps its own state, e.g. a function registered by the embedding application
+    pub fn native_closure(func: NativeClosure, name: String, module: Rc<dyn Module>) -> Rc<FunctionReference> {
+        let reference = FunctionReference {
+            callback: FunctionType::NativeClosure(func),
+            flags: FunctionFlag::STATIC,
+            name,
+            arguments: Vec::new(),
+            parameters: Vec::new(),
             storage_index: 0,
             opcode_location: Cell::new(0),
             used_locations: RefCell::new(Vec::new()),
@@ -158,13 +228,14 @@ impl FunctionReference {
         Rc::new(reference)
     }
 
-    pub fn opcode_function(name: String, arguments: Vec<String>, body: Rc<KaramelAstType>, module: Rc<dyn Module>, storage_index: usize, defined_storage_index: usize, module_level: bool) -> Rc<FunctionReference> {
+    pub fn opcode_function(name: String, parameters: Vec<KaramelFunctionArgument>, body: Rc<KaramelAstType>, module: Rc<dyn Module>, storage_index: usize, defined_storage_index: usize, module_level: bool) -> Rc<FunctionReference> {
         let mut reference = FunctionReference {
             callback: FunctionType::Opcode,
             flags: FunctionFlag::STATIC,
             module,
             name,
-            arguments,
+            arguments: parameters.iter().map(|parameter| parameter.name.to_string()).collect(),
+            parameters,
             storage_index,
             defined_storage_index,
             opcode_location: Cell::new(0),
@@ -179,15 +250,82 @@ impl FunctionReference {
         Rc::new(reference)
     }
 
-    unsafe fn native_function_call(reference: &FunctionReference, func: NativeCall, compiler: &mut KaramelCompilerContext, source: Option<VmObject>) -> Result<(), KaramelErrorType> {            
+    pub fn opcode_method(name: String, parameters: Vec<KaramelFunctionArgument>, body: Rc<KaramelAstType>, module: Rc<dyn Module>, storage_index: usize, defined_storage_index: usize) -> Rc<FunctionReference> {
+        /* Object reference is the last argument of the method */
+        let mut arguments: Vec<String> = parameters.iter().map(|parameter| parameter.name.to_string()).collect();
+        arguments.push(CLASS_INSTANCE_NAME.to_string());
+
+        let reference = FunctionReference {
+            callback: FunctionType::Opcode,
+            flags: FunctionFlag::IN_CLASS,
+            module,
+            name,
+            arguments,
+            parameters,
+            storage_index,
+            defined_storage_index,
+            opcode_location: Cell::new(0),
+            used_locations: RefCell::new(Vec::new()),
+            opcode_body: Some(body.clone())
+        };
+
+        Rc::new(reference)
+    }
+
+    pub fn opcode_lambda(name: String, parameters: Vec<KaramelFunctionArgument>, captures: Vec<String>, body: Rc<KaramelAstType>, module: Rc<dyn Module>, storage_index: usize, defined_storage_index: usize) -> Rc<FunctionReference> {
+        /* Captured values are passed after the arguments */
+        let arguments = parameters.iter().map(|parameter| parameter.name.to_string()).chain(captures.into_iter()).collect();
+
+        let reference = FunctionReference {
+            callback: FunctionType::Opcode,
+            flags: FunctionFlag::CLOSURE,
+            module,
+            name,
+            arguments,
+            parameters,
+            storage_index,
+            defined_storage_index,
+            opcode_location: Cell::new(0),
+            used_locations: RefCell::new(Vec::new()),
+            opcode_body: Some(body.clone())
+        };
+
+        Rc::new(reference)
+    }
+
+    unsafe fn native_function_call(reference: &FunctionReference, func: &dyn Fn(FunctionParameter) -> NativeCallResult, compiler: &mut KaramelCompilerContext, source: Option<VmObject>) -> Result<(), KaramelErrorType> {            
         let total_args                 = *compiler.opcodes_ptr.offset(1);
-        let call_return_assign_to_temp = *compiler.opcodes_ptr.offset(2) != 0;
-        let parameter = FunctionParameter::new(&compiler.stack, source, get_memory_index!(compiler) as usize, karamel_dbg!(total_args), &compiler.stdout, &compiler.stderr);
+        let call_flags                 = CallFlag::from_bits_truncate(*compiler.opcodes_ptr.offset(2));
+        let call_return_assign_to_temp = call_flags.contains(CallFlag::ASSIGN_TO_TEMP);
+
+        if call_flags.contains(CallFlag::NAMED_ARGUMENTS) {
+            dec_memory_index!(compiler, total_args as usize);
+            return Err(KaramelErrorType::NamedArgumentNotSupported(reference.name.to_string()));
+        }
+
+        /* Source object is not at the stack anymore, called functions could start a collection */
+        let root_count = compiler.heap.root_count();
+        if let Some(source) = source {
+            compiler.heap.add_root(source);
+        }
+
+        let context = compiler as *mut KaramelCompilerContext;
+        let io = compiler.io.clone();
+        let parameter = FunctionParameter::new(&compiler.stack, source, get_memory_index!(compiler) as usize, karamel_dbg!(total_args), &*io).with_context(context);
 
         dump_data!(compiler, "native_function_call");
-        
-        match func(parameter) {
+
+        let result = func(parameter);
+        compiler.heap.release_roots(root_count);
+
+        match result {
             Ok(result) => {
+                /* Native function could handle the error of the function that it called */
+                if !compiler.stack_trace.is_empty() {
+                    compiler.error_location = None;
+                    compiler.stack_trace.clear();
+                }
+
                 dec_memory_index!(compiler, total_args as usize);
 
                 if call_return_assign_to_temp {
@@ -200,32 +338,159 @@ impl FunctionReference {
             },
             Err(error) => {
                 dec_memory_index!(compiler, total_args as usize);
-                println!("{:?}", error);
                 Err(error)
             }
         }
     }
 
-    fn opcode_function_call(reference: &FunctionReference, options: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    /* Move the arguments at the stack to the parameter order. Extra arguments are collected for the variadic parameter and
+    missing ones are marked to be calculated by the default value expressions. */
+    unsafe fn bind_arguments(&self, options: &mut KaramelCompilerContext, argument_size: u8, call_flags: CallFlag) -> Result<(u8, u64), KaramelErrorType> {
+        if call_flags.contains(CallFlag::FORWARD_ARGUMENTS) {
+            return Ok((argument_size, (*options.current_scope).missing_arguments));
+        }
+
+        let has_named = call_flags.contains(CallFlag::NAMED_ARGUMENTS);
+        let has_variadic = self.parameters.last().map_or(false, |parameter| parameter.variadic);
+        let has_default = self.parameters.iter().any(|parameter| parameter.default.is_some());
+
+        /* Argument count validation is done by the caller */
+        if !has_named && !has_variadic && (!has_default || argument_size as usize == self.parameters.len()) {
+            return Ok((argument_size, 0));
+        }
+
+        let mut named_arguments = HashMap::new();
+        let mut argument_size = argument_size as usize;
+
+        if has_named {
+            dec_memory_index!(options, 1);
+            if let KaramelPrimative::Dict(dict) = &*(*options.stack_ptr).deref() {
+                named_arguments = dict.borrow().clone();
+            }
+            argument_size -= 1;
+        }
+
+        let fixed_size = self.parameters.len() - has_variadic as usize;
+        if argument_size > fixed_size && !has_variadic {
+            return Err(KaramelErrorType::FunctionArgumentNotMatching {
+                function: self.name.to_string(),
+                expected: fixed_size as u8,
+                found: argument_size as u8
+            });
+        }
+
+        /* Extra arguments are collected into a list */
+        let mut variadic_arguments = Vec::new();
+        if argument_size > fixed_size {
+            dec_memory_index!(options, argument_size - fixed_size);
+            for index in 0..(argument_size - fixed_size) {
+                variadic_arguments.push(*options.stack_ptr.add(index));
+            }
+            argument_size = fixed_size;
+        }
+
+        let mut missing_arguments: u64 = 0;
+        for (index, parameter) in self.parameters.iter().enumerate().take(fixed_size) {
+            let named_argument = named_arguments.remove(&parameter.name);
+            if index < argument_size {
+                if named_argument.is_some() {
+                    return Err(KaramelErrorType::ArgumentValueAlreadyGiven { function: self.name.to_string(), argument: parameter.name.to_string() });
+                }
+                continue;
+            }
+
+            *options.stack_ptr = match (named_argument, &parameter.default) {
+                (Some(value), _) => value,
+                (None, Some(_)) => {
+                    missing_arguments |= 1 << index;
+                    EMPTY_OBJECT
+                },
+                (None, None) => {
+                    return Err(KaramelErrorType::ArgumentValueNotFound { function: self.name.to_string(), argument: parameter.name.to_string() });
+                }
+            };
+            inc_memory_index!(options, 1);
+        }
+
+        if has_variadic {
+            *options.stack_ptr = VmObject::from(variadic_arguments);
+            inc_memory_index!(options, 1);
+        }
+
+        if let Some(argument) = named_arguments.keys().next() {
+            return Err(KaramelErrorType::ArgumentNotDefined { function: self.name.to_string(), argument: argument.to_string() });
+        }
+
+        Ok((self.parameters.len() as u8, missing_arguments))
+    }
+
+    fn opcode_function_call(reference: &FunctionReference, options: &mut KaramelCompilerContext, base: Option<VmObject>) -> Result<(), KaramelErrorType> {
         unsafe {
             let argument_size              = *options.opcodes_ptr.offset(1);
-            let call_return_assign_to_temp = *options.opcodes_ptr.offset(2) != 0;
+            let call_flags                 = CallFlag::from_bits_truncate(*options.opcodes_ptr.offset(2));
+            let call_return_assign_to_temp = call_flags.contains(CallFlag::ASSIGN_TO_TEMP);
             let old_index                  = options.opcodes_ptr.offset(2);
+            let (argument_size, missing_arguments) = reference.bind_arguments(options, argument_size, call_flags)?;
             let location = reference.opcode_location.get() as isize;
-            options.opcodes_ptr            = options.opcodes_top_ptr.offset(location);
-            options.scope_index           += 1;
-
-            if argument_size != *options.opcodes_ptr {
+            let function_location          = options.opcodes_top_ptr.offset(location);
+
+            /* Class methods receive the object and closures receive captured values as a last arguments */
+            let base = match reference.flags.intersects(FunctionFlag::IN_CLASS | FunctionFlag::CLOSURE) {
+                true => base,
+                false => None
+            };
+
+            let base_size = match (base, reference.flags.contains(FunctionFlag::CLOSURE)) {
+                (Some(captures), true) => match &*captures.deref() {
+                    KaramelPrimative::List(items) => items.borrow().len() as u8,
+                    _ => 0
+                },
+                (Some(_), false) => 1,
+                (None, _) => 0
+            };
+
+            /* Error is shown at the call location */
+            if argument_size + base_size != *function_location {
                 return Err(KaramelErrorType::FunctionArgumentNotMatching {
                     function: reference.name.to_string(),
                     expected: argument_size, 
-                    found: *options.opcodes_ptr
+                    found: (*function_location).saturating_sub(base_size)
                 });
             }
+            options.opcodes_ptr = function_location;
+
+            if let Some(object) = base {
+                match reference.flags.contains(FunctionFlag::CLOSURE) {
+                    true => if let KaramelPrimative::List(items) = &*object.deref() {
+                        for item in items.borrow().iter() {
+                            *options.stack_ptr = *item;
+                            inc_memory_index!(options, 1);
+                        }
+                    },
+                    false => {
+                        *options.stack_ptr = object;
+                        inc_memory_index!(options, 1);
+                    }
+                };
+            }
+
+            let argument_size = argument_size + base_size;
 
             dec_memory_index!(options, argument_size.into());
             dump_data!(options, "Current");
 
+            /* Arguments are moved to the current function memory and the caller of the current function gets the result */
+            if call_flags.contains(CallFlag::TAIL_CALL) && ptr::eq((*options.current_scope).function, reference) {
+                let scope = options.current_scope;
+                ptr::copy(options.stack_ptr, (*scope).top_stack, argument_size as usize);
+                options.stack_ptr = (*scope).top_stack;
+                (*scope).missing_arguments = missing_arguments;
+
+                inc_memory_index!(options, (*options.storages_ptr.add(reference.storage_index)).variables.len());
+                return Ok(());
+            }
+
+            options.scope_index += 1;
             if options.scopes.len() <= options.scope_index {
                 options.scopes.resize(options.scopes.len() * 2, Scope::empty());
                 options.scopes_ptr = options.scopes.as_mut_ptr();
@@ -239,9 +504,13 @@ impl FunctionReference {
 
             (*scope).location                   = old_index;
             (*scope).call_return_assign_to_temp = call_return_assign_to_temp;
+            (*scope).missing_arguments          = missing_arguments;
+            (*scope).function                   = reference;
 
             options.current_scope = scope;
-            inc_memory_index!(options, argument_size.into());
+
+            /* Arguments and local variables are placed at the beginning of the function memory, stack and call depth are checked */
+            inc_memory_index!(options, (*storage).variables.len());
         }
         Ok(())
     }
@@ -261,6 +530,11 @@ pub fn find_function_definition_type(module: Rc<OpcodeModule>, ast: Rc<KaramelAs
             if let Some(_) = old_function {
                 return Err(KaramelErrorType::FunctionAlreadyDefined(name.to_string()));
             }
+
+            /* Arguments are placed at the beginning of the function memory */
+            for argument in arguments {
+                options.storages[new_storage_index].add_variable(&argument.name);
+            }
             
             find_function_definition_type(module.clone(), body.clone(), options, new_storage_index, false)?;
 
@@ -268,10 +542,70 @@ pub fn find_function_definition_type(module: Rc<OpcodeModule>, ast: Rc<KaramelAs
             storage_builder.prepare(module.clone(), ast.borrow(), new_storage_index, options)?;
 
             options.storages[current_storage_index].add_constant(Rc::new(KaramelPrimative::Function(function.clone(), None)));
+        },
+        KaramelAstType::ClassDefination { name, fields, methods } => {
+            let storage_builder = StorageBuilder::new();
+            let mut config = ClassConfig::default();
+            let mut constructor_arguments = Vec::new();
+            config.name = name.to_string();
+            config.storage_index = current_storage_index;
+
+            for method in methods {
+                if let KaramelAstType::FunctionDefination { name: method_name, arguments, body } = &**method {
+                    let new_storage_index = options.storages.len();
+                    options.storages.push(StaticStorage::new(new_storage_index));
+                    options.storages[new_storage_index].set_parent_location(current_storage_index);
+
+                    let function = FunctionReference::opcode_method(method_name.to_string(), arguments.to_vec(), body.clone(), module.clone(), new_storage_index, current_storage_index);
+                    for argument in function.arguments.iter() {
+                        options.storages[new_storage_index].add_variable(argument);
+                    }
+
+                    if let Some(_) = config.properties.insert(method_name.to_string(), ClassProperty::Function(function)) {
+                        return Err(KaramelErrorType::FunctionAlreadyDefined(method_name.to_string()));
+                    }
+
+                    if method_name == CLASS_CONSTRUCTOR_NAME {
+                        constructor_arguments = arguments.to_vec();
+                    }
+
+                    find_function_definition_type(module.clone(), body.clone(), options, new_storage_index, false)?;
+                    storage_builder.prepare(module.clone(), method, new_storage_index, options)?;
+                }
+            }
 
-            for argument in arguments {
-                options.storages[new_storage_index].add_variable(argument);
+            let mut class = BasicInnerClass::default();
+            class.set_class_config(config);
+            let class: Rc<dyn Class> = Rc::new(class);
+
+            if let Some(_) = module.classes.borrow_mut().insert(name.to_string(), class.clone()) {
+                return Err(KaramelErrorType::ClassAlreadyDefined(name.to_string()));
+            }
+
+            /* Class name is a function that create new object */
+            let new_storage_index = options.storages.len();
+            options.storages.push(StaticStorage::new(new_storage_index));
+            options.storages[new_storage_index].set_parent_location(current_storage_index);
+
+            let function = FunctionReference::opcode_function(name.to_string(), constructor_arguments.to_vec(), ast.clone(), module.clone(), new_storage_index, current_storage_index, module_level);
+            if let Some(_) = module.functions.borrow_mut().insert(name.to_string(), function.clone()) {
+                return Err(KaramelErrorType::FunctionAlreadyDefined(name.to_string()));
+            }
+
+            for argument in constructor_arguments.iter() {
+                options.storages[new_storage_index].add_variable(&argument.name);
+            }
+
+            options.storages[new_storage_index].add_variable(CLASS_INSTANCE_NAME);
+
+            for field in fields {
+                options.storages[new_storage_index].add_constant(field.key.clone());
+                storage_builder.prepare(module.clone(), &field.value, new_storage_index, options)?;
             }
+
+            options.storages[new_storage_index].add_constant(Rc::new(KaramelPrimative::Class(class)));
+            options.storages[new_storage_index].add_constant(Rc::new(KaramelPrimative::Text(Rc::new(CLASS_CONSTRUCTOR_NAME.to_string()))));
+            options.storages[current_storage_index].add_constant(Rc::new(KaramelPrimative::Function(function.clone(), None)));
         },
         KaramelAstType::Block(blocks) => {
             for block in blocks {
@@ -1,8 +1,38 @@
 use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+use bitflags::bitflags;
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait};
+use super::{DumpBuilder, OpcodeGeneratorTrait, add_operand, add_wide_prefix, dump_wide_prefix, is_wide};
+
+bitflags! {
+    #[derive(Default)]
+    /// Function call options that passed with the call opcode.
+    pub struct CallFlag: u8 {
+        const NONE              = 0b00000000;
+
+        /// Function return value needs to be assigned to stack location
+        const ASSIGN_TO_TEMP    = 0b00000001;
+
+        /// Last argument is a dictionary that keeps the named arguments
+        const NAMED_ARGUMENTS   = 0b00000010;
+
+        /// Arguments are already bound by the caller function, e.g. class constructor calls 'başlat'
+        const FORWARD_ARGUMENTS = 0b00000100;
+
+        /// Function calls itself at the return position, memory of the current call is reused
+        const TAIL_CALL         = 0b00001000;
+    }
+}
+
+impl From<bool> for CallFlag {
+    fn from(assign_to_temp: bool) -> Self {
+        match assign_to_temp {
+            true => CallFlag::ASSIGN_TO_TEMP,
+            false => CallFlag::NONE
+        }
+    }
+}
 
 #[derive(Clone)]
 
@@ -10,7 +40,7 @@ use super::{DumpBuilder, OpcodeGeneratorTrait};
 pub enum CallType {
 
     /// Call function from memory location
-    Call { constant_location: u8 },
+    Call { constant_location: u16 },
 
     /// Call function from last stack value
     CallStack
@@ -26,8 +56,8 @@ pub struct CallGenerator {
     /// How many arguments are passed to function
     pub argument_size: u8,
 
-    /// Function return value needs to be assigned to stack location or discarded
-    pub assign_to_temp: bool
+    /// Return value and argument options
+    pub flags: CallFlag
 }
 
 /// Generate function call opcodes based on givin parameters
@@ -35,25 +65,27 @@ impl OpcodeGeneratorTrait for CallGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
         match self.call_type {
             CallType::Call { constant_location } => {
+                let wide = is_wide(&[constant_location.into()]);
+                add_wide_prefix(opcodes, wide);
                 opcodes.push(VmOpCode::Call.into());
-                opcodes.push(constant_location);
+                add_operand(opcodes, constant_location.into(), wide);
             },
             CallType::CallStack => opcodes.push(VmOpCode::CallStack.into())
         };
         opcodes.push(self.argument_size);
-        opcodes.push(self.assign_to_temp.into());
+        opcodes.push(self.flags.bits());
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
-        let opcode_index = index.fetch_add(3, Ordering::SeqCst);
-
         match self.call_type {
             CallType::Call { constant_location } => {
-                index.fetch_add(1, Ordering::SeqCst);
-                builder.add(opcode_index, VmOpCode::Call, constant_location.to_string(), self.argument_size.to_string(), (self.assign_to_temp as u8).to_string());
+                let operand_size = dump_wide_prefix(builder, index.clone(), is_wide(&[constant_location.into()]));
+                let opcode_index = index.fetch_add(3 + operand_size, Ordering::SeqCst);
+                builder.add(opcode_index, VmOpCode::Call, constant_location.to_string(), self.argument_size.to_string(), self.flags.bits().to_string());
             },
             CallType::CallStack => {
-                builder.add(opcode_index, VmOpCode::CallStack, self.argument_size.to_string(), (self.assign_to_temp as u8).to_string(), "".to_string());
+                let opcode_index = index.fetch_add(3, Ordering::SeqCst);
+                builder.add(opcode_index, VmOpCode::CallStack, self.argument_size.to_string(), self.flags.bits().to_string(), "".to_string());
             }
         };
     }
@@ -71,7 +103,7 @@ mod tests {
         let generator = CallGenerator {
             call_type: CallType::Call { constant_location: 100 },
             argument_size: 1,
-            assign_to_temp: false
+            flags: CallFlag::NONE
         };
 
         generator.generate(&mut opcodes);
@@ -89,7 +121,7 @@ mod tests {
         let generator = CallGenerator {
             call_type: CallType::CallStack,
             argument_size: 5,
-            assign_to_temp: true
+            flags: CallFlag::ASSIGN_TO_TEMP
         };
 
         generator.generate(&mut opcodes);
@@ -106,7 +138,7 @@ mod tests {
         let generator = CallGenerator {
             call_type: CallType::Call { constant_location: 100 },
             argument_size: 5,
-            assign_to_temp: true
+            flags: CallFlag::ASSIGN_TO_TEMP
         };
 
         generator.generate(&mut opcodes);
@@ -117,4 +149,17 @@ mod tests {
         assert_eq!(opcodes[2], 5);
         assert_eq!(opcodes[3], 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_4() {
+        let mut opcodes = Vec::new();
+        let generator = CallGenerator {
+            call_type: CallType::Call { constant_location: 1000 },
+            argument_size: 2,
+            flags: CallFlag::ASSIGN_TO_TEMP
+        };
+
+        generator.generate(&mut opcodes);
+        assert_eq!(opcodes, vec![VmOpCode::Wide.into(), VmOpCode::Call.into(), 232, 3, 2, 1]);
+    }
+}
@@ -41,10 +41,12 @@ mod tests {
 
         generator.generate(&mut opcodes);
 
-        assert_eq!(opcodes.len(), 3);
+        assert_eq!(opcodes.len(), 5);
         assert_eq!(opcodes[0], VmOpCode::Compare.into());
         assert_eq!(opcodes[1], 123);
         assert_eq!(opcodes[2], 0);
+        assert_eq!(opcodes[3], 0);
+        assert_eq!(opcodes[4], 0);
     }
 
     #[test]
@@ -57,9 +59,11 @@ mod tests {
 
         generator.generate(&mut opcodes);
 
-        assert_eq!(opcodes.len(), 3);
+        assert_eq!(opcodes.len(), 5);
         assert_eq!(opcodes[0], VmOpCode::Compare.into());
         assert_eq!(opcodes[1], 21);
         assert_eq!(opcodes[2], 205);
+        assert_eq!(opcodes[3], 91);
+        assert_eq!(opcodes[4], 7);
     }
 }
\ No newline at end of file
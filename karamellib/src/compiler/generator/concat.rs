@@ -0,0 +1,27 @@
+use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait, add_operand, add_wide_prefix, dump_wide_prefix, is_wide};
+
+#[derive(Debug)]
+#[derive(Clone)]
+/// Generate concat opcodes. Stack values are joined as a text.
+pub struct ConcatGenerator {
+    pub total_item: usize
+}
+
+impl OpcodeGeneratorTrait for ConcatGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        let wide = is_wide(&[self.total_item]);
+        add_wide_prefix(opcodes, wide);
+        opcodes.push(VmOpCode::Concat.into());
+        add_operand(opcodes, self.total_item, wide);
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
+        let operand_size = dump_wide_prefix(builder, index.clone(), is_wide(&[self.total_item]));
+        let opcode_index = index.fetch_add(1 + operand_size, Ordering::SeqCst);
+        builder.add(opcode_index, VmOpCode::Concat, self.total_item.to_string(), "".to_string(), "".to_string());
+    }
+}
@@ -2,19 +2,22 @@ use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait};
+use super::{DumpBuilder, OpcodeGeneratorTrait, add_operand, add_wide_prefix, dump_wide_prefix, is_wide};
 
 
 #[derive(Clone)]
-pub struct ConstantGenerator { pub location: u8 }
+pub struct ConstantGenerator { pub location: u16 }
 impl OpcodeGeneratorTrait for ConstantGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
+        let wide = is_wide(&[self.location.into()]);
+        add_wide_prefix(opcodes, wide);
         opcodes.push(VmOpCode::Constant.into());
-        opcodes.push(self.location);
+        add_operand(opcodes, self.location.into(), wide);
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
-        let opcode_index = index.fetch_add(2, Ordering::SeqCst);
+        let operand_size = dump_wide_prefix(builder, index.clone(), is_wide(&[self.location.into()]));
+        let opcode_index = index.fetch_add(1 + operand_size, Ordering::SeqCst);
         builder.add(opcode_index, VmOpCode::Constant, self.location.to_string(), "".to_string(), "".to_string());
     }
-}
\ No newline at end of file
+}
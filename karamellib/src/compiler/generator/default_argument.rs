@@ -0,0 +1,24 @@
+use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait};
+
+#[derive(Debug)]
+#[derive(Clone)]
+/// Generate default argument check opcodes. Result is true if the argument value is not passed by the caller.
+pub struct DefaultArgumentGenerator {
+    pub location: u8
+}
+
+impl OpcodeGeneratorTrait for DefaultArgumentGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        opcodes.push(VmOpCode::DefaultArgument.into());
+        opcodes.push(self.location);
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
+        let opcode_index = index.fetch_add(2, Ordering::SeqCst);
+        builder.add(opcode_index, VmOpCode::DefaultArgument, self.location.to_string(), "".to_string(), "".to_string());
+    }
+}
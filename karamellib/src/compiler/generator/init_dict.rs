@@ -2,7 +2,7 @@ use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait};
+use super::{DumpBuilder, OpcodeGeneratorTrait, add_operand, add_wide_prefix, dump_wide_prefix, is_wide};
 
 #[derive(Debug)]
 #[derive(Clone)]
@@ -12,13 +12,16 @@ pub struct InitDictGenerator {
 
 impl OpcodeGeneratorTrait for InitDictGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
+        let wide = is_wide(&[self.argument_size]);
+        add_wide_prefix(opcodes, wide);
         opcodes.push(VmOpCode::Init.into());
         opcodes.push(0);
-        opcodes.push(self.argument_size as u8);
+        add_operand(opcodes, self.argument_size, wide);
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
-        let opcode_index = index.fetch_add(3, Ordering::SeqCst);
+        let operand_size = dump_wide_prefix(builder, index.clone(), is_wide(&[self.argument_size]));
+        let opcode_index = index.fetch_add(2 + operand_size, Ordering::SeqCst);
         builder.add(opcode_index, VmOpCode::Init, "0".to_string(), self.argument_size.to_string(), "".to_string());
     }
 }
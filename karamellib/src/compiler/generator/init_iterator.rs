@@ -0,0 +1,25 @@
+use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait};
+
+#[derive(Debug)]
+#[derive(Clone)]
+/// Convert the value at the top of the stack into list that could be used by iterate opcode
+pub struct InitIteratorGenerator {
+    pub pair: bool
+}
+
+impl OpcodeGeneratorTrait for InitIteratorGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        opcodes.push(VmOpCode::Init.into());
+        opcodes.push(3);
+        opcodes.push(self.pair as u8);
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
+        let opcode_index = index.fetch_add(3, Ordering::SeqCst);
+        builder.add(opcode_index, VmOpCode::Init, "3".to_string(), (self.pair as u8).to_string(), "".to_string());
+    }
+}
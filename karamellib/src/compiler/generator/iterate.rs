@@ -0,0 +1,62 @@
+use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait, OpcodeLocation, opcode_to_location, add_operand, add_wide_prefix, dump_wide_prefix, is_wide};
+
+#[derive(Clone)]
+/// Generate iterate opcodes.
+/// Move next item from source list to stack. If there is no item left, jump to end of the loop.
+pub struct IterateGenerator {
+    pub source: u16,
+    pub index: u16,
+    pub pair: bool,
+    pub location: Rc<OpcodeLocation>
+}
+
+impl OpcodeGeneratorTrait for IterateGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        let wide = is_wide(&[self.source.into(), self.index.into()]);
+        add_wide_prefix(opcodes, wide);
+        opcodes.push(VmOpCode::Iterate.into());
+        add_operand(opcodes, self.source.into(), wide);
+        add_operand(opcodes, self.index.into(), wide);
+        opcodes.push(self.pair as u8);
+        self.location.apply(opcodes);
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
+        let operand_size = dump_wide_prefix(builder, index.clone(), is_wide(&[self.source.into(), self.index.into()]));
+        let opcode_index = index.fetch_add(2 + operand_size * 2, Ordering::SeqCst);
+        let location = opcode_to_location(index, opcodes);
+        builder.add(opcode_index, VmOpCode::Iterate, self.source.to_string(), self.index.to_string(), location.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let mut opcodes = Vec::new();
+        let generator = IterateGenerator {
+            source: 1,
+            index: 2,
+            pair: true,
+            location: Rc::new(OpcodeLocation::new(300))
+        };
+
+        generator.generate(&mut opcodes);
+
+        assert_eq!(opcodes.len(), 8);
+        assert_eq!(opcodes[0], VmOpCode::Iterate.into());
+        assert_eq!(opcodes[1], 1);
+        assert_eq!(opcodes[2], 2);
+        assert_eq!(opcodes[3], 1);
+        assert_eq!(opcodes[4], 44);
+        assert_eq!(opcodes[5], 1);
+        assert_eq!(opcodes[6], 0);
+        assert_eq!(opcodes[7], 0);
+    }
+}
@@ -2,19 +2,43 @@ use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait};
+use super::{DumpBuilder, OpcodeGeneratorTrait, add_operand, add_wide_prefix, dump_wide_prefix, is_wide};
 
 
 #[derive(Clone)]
-pub struct LoadGenerator { pub location: u8 }
+pub struct LoadGenerator { pub location: u16 }
 impl OpcodeGeneratorTrait for LoadGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
+        let wide = is_wide(&[self.location.into()]);
+        add_wide_prefix(opcodes, wide);
         opcodes.push(VmOpCode::Load.into());
-        opcodes.push(self.location);
+        add_operand(opcodes, self.location.into(), wide);
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
-        let opcode_index = index.fetch_add(2, Ordering::SeqCst);
+        let operand_size = dump_wide_prefix(builder, index.clone(), is_wide(&[self.location.into()]));
+        let opcode_index = index.fetch_add(1 + operand_size, Ordering::SeqCst);
         builder.add(opcode_index, VmOpCode::Load, self.location.to_string(), "".to_string(), "".to_string());
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let mut opcodes = Vec::new();
+        let generator = LoadGenerator { location: 10 };
+        generator.generate(&mut opcodes);
+        assert_eq!(opcodes, vec![VmOpCode::Load.into(), 10]);
+    }
+
+    #[test]
+    fn test_2() {
+        let mut opcodes = Vec::new();
+        let generator = LoadGenerator { location: 300 };
+        generator.generate(&mut opcodes);
+        assert_eq!(opcodes, vec![VmOpCode::Wide.into(), VmOpCode::Load.into(), 44, 1]);
+    }
+}
@@ -1,6 +1,8 @@
 use std::{cell::RefCell, rc::Rc};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use crate::constants::OPCODE_LOCATION_SIZE;
+
 use super::{DumpBuilder, OpcodeGeneratorTrait};
 
 #[cfg(debug_assertions)]
@@ -61,8 +63,7 @@ impl OpcodeLocation {
         *self.location.borrow_mut() = LocationType::Fixed(location);
 
         for used_location in self.used_location.borrow().iter() {
-            opcodes[*used_location] = location as u8;
-            opcodes[*used_location + 1] = (location >> 8) as u8;
+            opcodes[*used_location..*used_location + OPCODE_LOCATION_SIZE].copy_from_slice(&(location as u32).to_le_bytes());
         }
     }
 
@@ -79,8 +80,7 @@ impl OpcodeLocation {
     pub fn apply(&self, opcodes: &mut Vec<u8>) {
         // Save position
         self.used_location.borrow_mut().push(opcodes.len());
-        opcodes.push(self.get() as u8);
-        opcodes.push((self.get() >> 8) as u8);
+        opcodes.extend_from_slice(&(self.get() as u32).to_le_bytes());
     }
 }
 
@@ -1,10 +1,11 @@
-use std::{borrow::Borrow, cell::{Cell, RefCell}, cmp, collections::VecDeque, rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+use std::{any::Any, borrow::Borrow, cell::{Cell, RefCell}, cmp, collections::VecDeque, rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
-use crate::{compiler::generator::location::DynamicLocationUpdateGenerator, constants::{DUMP_INDEX_WIDTH, DUMP_OPCODE_COLUMN_1, DUMP_OPCODE_COLUMN_2, DUMP_OPCODE_COLUMN_3, DUMP_OPCODE_TITLE, DUMP_OPCODE_WIDTH}};
+use crate::{compiler::generator::location::DynamicLocationUpdateGenerator, constants::{OPCODE_LOCATION_SIZE, DUMP_INDEX_WIDTH, DUMP_OPCODE_COLUMN_1, DUMP_OPCODE_COLUMN_2, DUMP_OPCODE_COLUMN_3, DUMP_OPCODE_TITLE, DUMP_OPCODE_WIDTH}};
 
-use self::{call::{CallGenerator, CallType}, compare::CompareGenerator, constant::ConstantGenerator, function::FunctionGenerator, init_dict::InitDictGenerator, init_list::InitListGenerator, jump::JumpGenerator, load::LoadGenerator, location::{CurrentLocationUpdateGenerator, OpcodeLocation, SubtractionGenerator}, location_group::OpcodeLocationGroup, opcode_item::OpcodeItem, store::{StoreGenerator, StoreType}};
+use self::{call::{CallFlag, CallGenerator, CallType}, concat::ConcatGenerator, compare::CompareGenerator, default_argument::DefaultArgumentGenerator, pattern::{PatternGenerator, PatternType}, constant::ConstantGenerator, function::FunctionGenerator, init_class::InitClassGenerator, init_closure::InitClosureGenerator, init_iterator::InitIteratorGenerator, iterate::IterateGenerator, init_dict::InitDictGenerator, init_list::InitListGenerator, jump::JumpGenerator, load::LoadGenerator, location::{CurrentLocationUpdateGenerator, OpcodeLocation, SubtractionGenerator}, location_group::OpcodeLocationGroup, opcode_item::OpcodeItem, store::{StoreGenerator, StoreType}, try_start::TryStartGenerator};
 
-use super::{VmOpCode, function::FunctionReference};
+use super::{VmOpCode, function::FunctionReference, source_map::SourceLocation, static_storage::StaticStorage};
+use self::optimizer::StorageChangeGenerator;
 
 pub mod opcode_item;
 pub mod location;
@@ -18,8 +19,28 @@ pub mod call;
 pub mod location_group;
 pub mod init_list;
 pub mod init_dict;
+pub mod init_class;
+pub mod init_closure;
+pub mod init_iterator;
+pub mod iterate;
+pub mod try_start;
+pub mod concat;
+pub mod default_argument;
+pub mod pattern;
+pub mod optimizer;
+
+/* Optimizer needs the concrete type of the items */
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
 
-pub trait OpcodeGeneratorTrait {
+pub trait OpcodeGeneratorTrait: AsAny {
     fn generate(&self, opcodes: &mut Vec<u8>);
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>);
 }
@@ -33,22 +54,52 @@ pub fn dump_default<'a, T: Borrow<String>, S1: Borrow<String>, S2: Borrow<String
 }
 
 pub fn opcode_to_location(index: Rc<AtomicUsize>, opcodes: &Vec<u8>) -> usize {
-    let location_2 = opcodes[index.fetch_add(1, Ordering::SeqCst)];
-    let location_1 = opcodes[index.fetch_add(1, Ordering::SeqCst)];
+    let location = index.fetch_add(OPCODE_LOCATION_SIZE, Ordering::SeqCst);
+    u32::from_le_bytes([opcodes[location], opcodes[location + 1], opcodes[location + 2], opcodes[location + 3]]) as usize
+}
+
+/* Operands that bigger than one byte are written as two bytes after 'Wide' opcode */
+pub fn is_wide(operands: &[usize]) -> bool {
+    operands.iter().any(|operand| *operand > u8::MAX as usize)
+}
 
-    ((location_1 as u16 * 256) + location_2 as u16) as usize
+pub fn add_wide_prefix(opcodes: &mut Vec<u8>, wide: bool) {
+    if wide {
+        opcodes.push(VmOpCode::Wide.into());
+    }
+}
+
+pub fn add_operand(opcodes: &mut Vec<u8>, operand: usize, wide: bool) {
+    debug_assert!(operand <= if wide { u16::MAX as usize } else { u8::MAX as usize }, "Operand does not fit: {}", operand);
+    match wide {
+        true => opcodes.extend_from_slice(&(operand as u16).to_le_bytes()),
+        false => opcodes.push(operand as u8)
+    };
+}
+
+/* Return operand size of the opcode */
+pub fn dump_wide_prefix<'a>(builder: &'a DumpBuilder, index: Rc<AtomicUsize>, wide: bool) -> usize {
+    match wide {
+        true => {
+            builder.add(index.fetch_add(1, Ordering::SeqCst), VmOpCode::Wide, "".to_string(), "".to_string(), "".to_string());
+            2
+        },
+        false => 1
+    }
 }
 
 pub struct LoopItem {
     pub loop_breaks: OpcodeLocationGroup,
     pub loop_continues:  OpcodeLocationGroup,
+    pub try_depth: Cell<usize>
 }
 
 impl LoopItem {
     pub fn new() -> Self {
         LoopItem {
             loop_breaks: OpcodeLocationGroup::new(),
-            loop_continues: OpcodeLocationGroup::new()
+            loop_continues: OpcodeLocationGroup::new(),
+            try_depth: Cell::new(0)
         }
     }
 }
@@ -121,28 +172,48 @@ impl DumpBuilder {
 
 pub struct OpcodeGenerator {
     generators: RefCell<Vec<Rc<dyn OpcodeGeneratorTrait>>>,
-    loop_groups: RefCell<VecDeque<LoopItem>>
+    loop_groups: RefCell<VecDeque<LoopItem>>,
+    try_depth: Cell<usize>,
+    source_locations: RefCell<Vec<(Rc<OpcodeLocation>, SourceLocation)>>
 }
 
 impl OpcodeGenerator {
     pub fn new() -> Self {
         OpcodeGenerator {
             generators: RefCell::new(Vec::new()),
-            loop_groups: RefCell::new(VecDeque::new())
+            loop_groups: RefCell::new(VecDeque::new()),
+            try_depth: Cell::new(0),
+            source_locations: RefCell::new(Vec::new())
         }
     }
 
+    /// Next opcodes are generated from the given source location
+    pub fn add_source_location(&self, location: SourceLocation) {
+        let opcode_location = self.current_location();
+        self.source_locations.borrow_mut().push((opcode_location, location));
+    }
+
+    /// Opcode locations of the source locations, valid after the generation
+    pub fn source_locations(&self) -> Vec<(usize, SourceLocation)> {
+        self.source_locations.borrow().iter().map(|(opcode_location, location)| (opcode_location.get(), *location)).collect()
+    }
+
+    /// Next opcodes use the constants of the given storage. Function definitions are changing the storage too.
+    pub fn set_storage(&self, storage_index: usize) {
+        self.generators.borrow_mut().push(Rc::new(StorageChangeGenerator { storage_index }));
+    }
+
     pub fn add_opcode<T: Borrow<VmOpCode>>(&self, opcode: T) {
         self.generators.borrow_mut().push(Rc::new(OpcodeItem { opcode: opcode.borrow().clone() }));
     }
 
-    pub fn create_load(&self, location: u8) -> Rc<LoadGenerator> {
+    pub fn create_load(&self, location: u16) -> Rc<LoadGenerator> {
         let generator = Rc::new(LoadGenerator { location: location });
         self.generators.borrow_mut().push(generator.clone());
         generator
     }
 
-    pub fn create_constant(&self, location: u8) -> Rc<ConstantGenerator> {
+    pub fn create_constant(&self, location: u16) -> Rc<ConstantGenerator> {
         let generator = Rc::new(ConstantGenerator { location: location });
         self.generators.borrow_mut().push(generator.clone());
         generator
@@ -198,6 +269,37 @@ impl OpcodeGenerator {
         self.loop_groups.borrow_mut().pop_back();
     }
 
+    pub fn loop_depth(&self) -> usize {
+        self.loop_groups.borrow().len()
+    }
+
+    /// 'dene' blocks inside current loop. Break and continue should remove their exception handlers.
+    pub fn loop_try_depth(&self) -> usize {
+        match self.loop_groups.borrow().back() {
+            Some(group) => group.try_depth.get(),
+            None => 0
+        }
+    }
+
+    /// Active 'dene' blocks. Errors at the tail calls should be catched by them, so the function memory could not be reused.
+    pub fn try_depth(&self) -> usize {
+        self.try_depth.get()
+    }
+
+    pub fn try_started(&self) {
+        self.try_depth.set(self.try_depth.get() + 1);
+        if let Some(group) = self.loop_groups.borrow().back() {
+            group.try_depth.set(group.try_depth.get() + 1);
+        }
+    }
+
+    pub fn try_finished(&self) {
+        self.try_depth.set(self.try_depth.get() - 1);
+        if let Some(group) = self.loop_groups.borrow().back() {
+            group.try_depth.set(group.try_depth.get() - 1);
+        }
+    }
+
     /// Create empty location point. It is used for jump and compare location positions.
     pub fn create_location(&self) -> Rc<OpcodeLocation> {
         Rc::new(OpcodeLocation::empty())
@@ -240,13 +342,37 @@ impl OpcodeGenerator {
         generator
     }
 
+    pub fn create_try_start(&self, has_variable: bool, location: Rc<OpcodeLocation>) -> Rc<TryStartGenerator> {
+        let generator = Rc::new(TryStartGenerator { has_variable, location: location.clone() });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_concat(&self, total_item: usize) -> Rc<ConcatGenerator> {
+        let generator = Rc::new(ConcatGenerator { total_item });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_default_argument(&self, location: u8) -> Rc<DefaultArgumentGenerator> {
+        let generator = Rc::new(DefaultArgumentGenerator { location });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_pattern(&self, pattern_type: PatternType) -> Rc<PatternGenerator> {
+        let generator = Rc::new(PatternGenerator { pattern_type });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
     pub fn create_compare(&self, location: Rc<OpcodeLocation>) -> Rc<CompareGenerator> {
         let generator = Rc::new(CompareGenerator { location: location.clone() });
         self.generators.borrow_mut().push(generator.clone());
         generator
     }
 
-    pub fn create_store(&self, destination: u8) -> Rc<StoreGenerator> {
+    pub fn create_store(&self, destination: u16) -> Rc<StoreGenerator> {
         let generator = Rc::new(StoreGenerator { 
             store_type: StoreType::Store(destination)
          });
@@ -254,7 +380,7 @@ impl OpcodeGenerator {
         generator
     }
 
-    pub fn create_copy_to_store(&self, destination: u8) -> Rc<StoreGenerator> {
+    pub fn create_copy_to_store(&self, destination: u16) -> Rc<StoreGenerator> {
         let generator = Rc::new(StoreGenerator { 
             store_type: StoreType::CopyToStore(destination)
          });
@@ -262,7 +388,7 @@ impl OpcodeGenerator {
         generator
     }
 
-    pub fn create_fast_store(&self, source: u8, destination: u8) -> Rc<StoreGenerator> {
+    pub fn create_fast_store(&self, source: u16, destination: u16) -> Rc<StoreGenerator> {
         let generator = Rc::new(StoreGenerator { 
             store_type: StoreType::FastStore {
                 destination: destination,
@@ -273,21 +399,21 @@ impl OpcodeGenerator {
         generator
     }
 
-    pub fn create_call(&self, function_location: u8, argument_size: u8, assign_to_temp: bool) -> Rc<CallGenerator> {
+    pub fn create_call(&self, function_location: u16, argument_size: u8, flags: CallFlag) -> Rc<CallGenerator> {
         let generator = Rc::new(CallGenerator { 
                 call_type: CallType::Call { constant_location: function_location },
                 argument_size,
-                assign_to_temp
+                flags
              });
         self.generators.borrow_mut().push(generator.clone());
         generator
     }
 
-    pub fn create_call_stack(&self, argument_size: u8, assign_to_temp: bool) -> Rc<CallGenerator> {
+    pub fn create_call_stack(&self, argument_size: u8, flags: CallFlag) -> Rc<CallGenerator> {
         let generator = Rc::new(CallGenerator { 
                 call_type: CallType::CallStack,
                 argument_size,
-                assign_to_temp
+                flags
              });
         self.generators.borrow_mut().push(generator.clone());
         generator
@@ -310,9 +436,39 @@ impl OpcodeGenerator {
         self.generators.borrow_mut().push(generator.clone());
         generator
     }
+
+    pub fn create_init_class(&self, argument_size: usize) -> Rc<InitClassGenerator> {
+        let generator = Rc::new(InitClassGenerator { argument_size });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_init_closure(&self, argument_size: usize) -> Rc<InitClosureGenerator> {
+        let generator = Rc::new(InitClosureGenerator { argument_size });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_init_iterator(&self, pair: bool) -> Rc<InitIteratorGenerator> {
+        let generator = Rc::new(InitIteratorGenerator { pair });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
+
+    pub fn create_iterate(&self, source: u16, index: u16, pair: bool, location: Rc<OpcodeLocation>) -> Rc<IterateGenerator> {
+        let generator = Rc::new(IterateGenerator { source, index, pair, location });
+        self.generators.borrow_mut().push(generator.clone());
+        generator
+    }
 }
 
 impl OpcodeGenerator {
+    /// Rewrite the items before the generation. Folded constants are added to the storages.
+    pub fn optimize(&self, storages: &mut [StaticStorage]) {
+        let source_locations: Vec<Rc<OpcodeLocation>> = self.source_locations.borrow().iter().map(|(location, _)| location.clone()).collect();
+        optimizer::optimize(&mut self.generators.borrow_mut(), &source_locations, storages);
+    }
+
     pub fn generate(&self, opcodes: &mut Vec<u8>) {
         for generator in self.generators.borrow().iter() {
             generator.generate(opcodes);
@@ -522,7 +678,7 @@ mod tests {
         let generator = OpcodeGenerator::new();
 
         generator.add_opcode(VmOpCode::Halt);
-        generator.create_call(20, 1, true);
+        generator.create_call(20, 1, CallFlag::ASSIGN_TO_TEMP);
 
         generator.generate(&mut opcodes);
         let generated = generator.dump(&opcodes);
@@ -546,7 +702,7 @@ mod tests {
         let generator = OpcodeGenerator::new();
 
         generator.add_opcode(VmOpCode::Halt);
-        generator.create_call(10, 0, false);
+        generator.create_call(10, 0, CallFlag::NONE);
 
         generator.generate(&mut opcodes);
         let generated = generator.dump(&opcodes);
@@ -570,7 +726,7 @@ mod tests {
         let generator = OpcodeGenerator::new();
 
         generator.add_opcode(VmOpCode::Halt);
-        generator.create_call_stack(0, false);
+        generator.create_call_stack(0, CallFlag::NONE);
 
         generator.generate(&mut opcodes);
         let generated = generator.dump(&opcodes);
@@ -594,7 +750,7 @@ mod tests {
         let generator = OpcodeGenerator::new();
 
         generator.add_opcode(VmOpCode::Halt);
-        generator.create_call_stack(5, true);
+        generator.create_call_stack(5, CallFlag::ASSIGN_TO_TEMP);
 
         generator.generate(&mut opcodes);
         let generated = generator.dump(&opcodes);
@@ -778,4 +934,54 @@ mod tests {
 
         assert_eq!(expected, generated);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_dump_17() {
+        let mut expected = String::with_capacity(1024);
+        let mut opcodes = Vec::new();
+        let generator = OpcodeGenerator::new();
+
+        generator.add_opcode(VmOpCode::Halt);
+        generator.create_init_class(3);
+
+        generator.generate(&mut opcodes);
+        let generated = generator.dump(&opcodes);
+        println!("{}", generated);
+
+        expected.push_str(r#"
+╔═════════════════════════════════════════════╗
+║                 OPCODE DUMP                 ║
+╠═══╦═════════════════╦═══════╦═══════╦═══════╣
+║ 0 ║ Halt            ║       ║       ║       ║
+║ 1 ║ Init            ║   2   ║   3   ║       ║
+╚═══╩═════════════════╩═══════╩═══════╩═══════╝"#);
+
+        assert_eq!(expected, generated);
+    }
+
+    #[test]
+    fn test_dump_18() {
+        let mut expected = String::with_capacity(1024);
+        let mut opcodes = Vec::new();
+        let generator = OpcodeGenerator::new();
+
+        generator.add_opcode(VmOpCode::Halt);
+        generator.create_init_iterator(true);
+        generator.create_iterate(1, 2, false, generator.create_location_with_data(7));
+
+        generator.generate(&mut opcodes);
+        let generated = generator.dump(&opcodes);
+        println!("{}", generated);
+
+        expected.push_str(r#"
+╔═════════════════════════════════════════════╗
+║                 OPCODE DUMP                 ║
+╠═══╦═════════════════╦═══════╦═══════╦═══════╣
+║ 0 ║ Halt            ║       ║       ║       ║
+║ 1 ║ Init            ║   3   ║   1   ║       ║
+║ 4 ║ Iterate         ║   1   ║   2   ║   7   ║
+╚═══╩═════════════════╩═══════╩═══════╩═══════╝"#);
+
+        assert_eq!(expected, generated);
+    }
+}
@@ -0,0 +1,425 @@
+use std::{collections::{HashMap, HashSet}, rc::Rc, sync::atomic::AtomicUsize};
+
+use crate::compiler::{KaramelPrimative, VmOpCode, static_storage::StaticStorage};
+use crate::constants::MAX_STORAGE_SIZE;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait, constant::ConstantGenerator, function::FunctionGenerator, jump::JumpGenerator, load::LoadGenerator, location::{CurrentLocationUpdateGenerator, DynamicLocationUpdateGenerator, OpcodeLocation, SubtractionGenerator}, opcode_item::OpcodeItem, store::{StoreGenerator, StoreType}};
+
+type Generators = Vec<Rc<dyn OpcodeGeneratorTrait>>;
+
+#[derive(Clone)]
+/// Marks the storage of the next items, constant folding needs to know where the constants are.
+pub struct StorageChangeGenerator { pub storage_index: usize }
+impl OpcodeGeneratorTrait for StorageChangeGenerator {
+    fn generate(&self, _: &mut Vec<u8>) { }
+    fn dump<'a>(&self, _: &'a DumpBuilder, _: Rc<AtomicUsize>, _: &Vec<u8>) { }
+}
+
+/// Constant folding, unreachable code removal, jump threading and load/store peepholes.
+/// Locations are only changed by the items, so any item could be removed or replaced except the location items.
+pub fn optimize(generators: &mut Generators, source_locations: &[Rc<OpcodeLocation>], storages: &mut [StaticStorage]) {
+    /* Labels that only used by the source map are not a jump target */
+    let source_locations: HashSet<*const OpcodeLocation> = source_locations.iter().map(Rc::as_ptr).collect();
+
+    /* Every pass could give a new chance to the others */
+    loop {
+        let mut changed = fold_constants(generators, storages);
+        changed |= remove_unreachable_codes(generators, &source_locations);
+        changed |= thread_jumps(generators, &source_locations);
+        changed |= rewrite_load_store(generators);
+
+        if !changed {
+            break;
+        }
+    }
+}
+
+fn downcast<T: 'static>(item: &Rc<dyn OpcodeGeneratorTrait>) -> Option<&T> {
+    (**item).as_any().downcast_ref::<T>()
+}
+
+fn get_opcode(item: &Rc<dyn OpcodeGeneratorTrait>) -> Option<VmOpCode> {
+    downcast::<OpcodeItem>(item).map(|item| item.opcode)
+}
+
+/* Location items are not generating any opcode */
+fn is_location(item: &Rc<dyn OpcodeGeneratorTrait>) -> bool {
+    downcast::<CurrentLocationUpdateGenerator>(item).is_some() ||
+    downcast::<DynamicLocationUpdateGenerator>(item).is_some() ||
+    downcast::<SubtractionGenerator>(item).is_some() ||
+    downcast::<StorageChangeGenerator>(item).is_some()
+}
+
+fn is_label(item: &Rc<dyn OpcodeGeneratorTrait>, source_locations: &HashSet<*const OpcodeLocation>) -> bool {
+    match downcast::<CurrentLocationUpdateGenerator>(item) {
+        Some(generator) => !source_locations.contains(&Rc::as_ptr(&generator.location)),
+        None => false
+    }
+}
+
+/* Execution never continues with the next item */
+fn is_exit(item: &Rc<dyn OpcodeGeneratorTrait>) -> bool {
+    downcast::<JumpGenerator>(item).is_some() || matches!(get_opcode(item), Some(VmOpCode::Return) | Some(VmOpCode::Halt) | Some(VmOpCode::Throw))
+}
+
+fn get_constant(item: &Rc<dyn OpcodeGeneratorTrait>, storage: &StaticStorage) -> Option<Rc<KaramelPrimative>> {
+    let constant = storage.constants.get(downcast::<ConstantGenerator>(item)?.location as usize)?.deref();
+    match &*constant {
+        KaramelPrimative::Number(_) | KaramelPrimative::Bool(_) | KaramelPrimative::Text(_) => Some(constant),
+        _ => None
+    }
+}
+
+/* Same calculations with the interpreter, other types are left to the runtime */
+fn calculate(opcode: VmOpCode, left: &KaramelPrimative, right: &KaramelPrimative) -> Option<KaramelPrimative> {
+    let value = match (opcode, left, right) {
+        (VmOpCode::Addition, KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => KaramelPrimative::Number(left + right),
+        (VmOpCode::Addition, KaramelPrimative::Text(left), KaramelPrimative::Text(right)) => KaramelPrimative::Text(Rc::new(format!("{}{}", left, right))),
+        (VmOpCode::Subraction, KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => KaramelPrimative::Number(left - right),
+        (VmOpCode::Multiply, KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => KaramelPrimative::Number(left * right),
+        (VmOpCode::Division, KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => KaramelPrimative::Number(left / right),
+        (VmOpCode::Module, KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => KaramelPrimative::Number(left % right),
+        (VmOpCode::Equal, KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => KaramelPrimative::Bool(left == right),
+        (VmOpCode::NotEqual, KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => KaramelPrimative::Bool(left != right),
+        (VmOpCode::GreaterThan, KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => KaramelPrimative::Bool(left > right),
+        (VmOpCode::GreaterEqualThan, KaramelPrimative::Number(left), KaramelPrimative::Number(right)) => KaramelPrimative::Bool(left >= right),
+        (VmOpCode::And, KaramelPrimative::Bool(left), KaramelPrimative::Bool(right)) => KaramelPrimative::Bool(*left && *right),
+        (VmOpCode::Or, KaramelPrimative::Bool(left), KaramelPrimative::Bool(right)) => KaramelPrimative::Bool(*left || *right),
+        _ => return None
+    };
+
+    /* Division by zero is kept for the runtime */
+    match value {
+        KaramelPrimative::Number(number) if !number.is_finite() => None,
+        value => Some(value)
+    }
+}
+
+/* Returns the folded value and the item count that replaced with it */
+fn fold(generators: &[Rc<dyn OpcodeGeneratorTrait>], index: usize, storage: &StaticStorage) -> Option<(KaramelPrimative, usize)> {
+    let left = get_constant(generators.get(index)?, storage)?;
+    if get_opcode(generators.get(index + 1)?) == Some(VmOpCode::Not) {
+        return Some((KaramelPrimative::Bool(!left.is_true()), 2));
+    }
+
+    let right = get_constant(generators.get(index + 1)?, storage)?;
+    let opcode = get_opcode(generators.get(index + 2)?)?;
+    calculate(opcode, &left, &right).map(|value| (value, 3))
+}
+
+fn fold_constants(generators: &mut Generators, storages: &mut [StaticStorage]) -> bool {
+    let mut changed = false;
+    let mut storage_index = 0;
+    let mut index = 0;
+
+    while index < generators.len() {
+        if let Some(generator) = downcast::<FunctionGenerator>(&generators[index]) {
+            storage_index = generator.function.storage_index;
+        }
+        else if let Some(generator) = downcast::<StorageChangeGenerator>(&generators[index]) {
+            storage_index = generator.storage_index;
+        }
+
+        let storage = &mut storages[storage_index];
+        match fold(generators, index, storage) {
+            Some((value, size)) if storage.constants.len() < MAX_STORAGE_SIZE => {
+                let location = storage.add_constant(Rc::new(value));
+                let generator: Rc<dyn OpcodeGeneratorTrait> = Rc::new(ConstantGenerator { location: location as u16 });
+                generators.splice(index..index + size, std::iter::once(generator));
+                changed = true;
+
+                /* New constant could be the right side of the previous operation */
+                index = index.saturating_sub(1);
+            },
+            _ => index += 1
+        };
+    }
+
+    changed
+}
+
+/* Items after an exit are removed until the next label or function */
+fn remove_unreachable_codes(generators: &mut Generators, source_locations: &HashSet<*const OpcodeLocation>) -> bool {
+    let size = generators.len();
+    let mut unreachable = false;
+
+    generators.retain(|item| {
+        if is_label(item, source_locations) || downcast::<FunctionGenerator>(item).is_some() {
+            unreachable = false;
+            return true;
+        }
+
+        if is_location(item) {
+            return true;
+        }
+
+        if unreachable {
+            return false;
+        }
+
+        unreachable = is_exit(item);
+        true
+    });
+
+    size != generators.len()
+}
+
+struct JumpTargets {
+    labels: HashMap<*const OpcodeLocation, usize>,
+
+    /* Break and continue locations are copied from the loop labels */
+    aliases: HashMap<*const OpcodeLocation, Rc<OpcodeLocation>>
+}
+
+impl JumpTargets {
+    fn new(generators: &Generators, source_locations: &HashSet<*const OpcodeLocation>) -> Self {
+        let mut labels = HashMap::new();
+        let mut aliases = HashMap::new();
+
+        for (index, item) in generators.iter().enumerate() {
+            if let Some(generator) = downcast::<CurrentLocationUpdateGenerator>(item) {
+                if !source_locations.contains(&Rc::as_ptr(&generator.location)) {
+                    labels.insert(Rc::as_ptr(&generator.location), index);
+                }
+            }
+            else if let Some(generator) = downcast::<DynamicLocationUpdateGenerator>(item) {
+                aliases.insert(Rc::as_ptr(&generator.target), generator.source.clone());
+            }
+        }
+
+        JumpTargets { labels, aliases }
+    }
+
+    /* Item index of the label */
+    fn find(&self, location: &Rc<OpcodeLocation>) -> Option<usize> {
+        let mut location = location;
+        for _ in 0..self.aliases.len() + 1 {
+            match self.labels.get(&Rc::as_ptr(location)) {
+                Some(index) => return Some(*index),
+                None => location = self.aliases.get(&Rc::as_ptr(location))?
+            };
+        }
+        None
+    }
+}
+
+/* First item that generates opcode after the index */
+fn next_opcode(generators: &Generators, index: usize) -> Option<usize> {
+    (index + 1..generators.len()).find(|index| !is_location(&generators[*index]))
+}
+
+/* Jumps to a jump are redirected to the last target, jumps to the next opcode are removed */
+fn thread_jumps(generators: &mut Generators, source_locations: &HashSet<*const OpcodeLocation>) -> bool {
+    let targets = JumpTargets::new(generators, source_locations);
+    let mut changed = false;
+    let mut removed = Vec::new();
+
+    for index in 0..generators.len() {
+        let location = match downcast::<JumpGenerator>(&generators[index]) {
+            Some(generator) => generator.location.clone(),
+            None => continue
+        };
+
+        let mut target = location.clone();
+        let mut visited = HashSet::new();
+        let mut infinite_loop = false;
+
+        while let Some(next) = targets.find(&target).and_then(|label| next_opcode(generators, label)) {
+            let next_location = match downcast::<JumpGenerator>(&generators[next]) {
+                Some(generator) => generator.location.clone(),
+                None => break
+            };
+
+            if !visited.insert(next) {
+                infinite_loop = true;
+                break;
+            }
+            target = next_location;
+        }
+
+        if infinite_loop {
+            continue;
+        }
+
+        if targets.find(&target).is_some_and(|label| label > index && next_opcode(generators, index).is_none_or(|next| next > label)) {
+            removed.push(index);
+        }
+        else if !Rc::ptr_eq(&target, &location) {
+            generators[index] = Rc::new(JumpGenerator { location: target });
+            changed = true;
+        }
+    }
+
+    for index in removed.iter().rev() {
+        generators.remove(*index);
+    }
+
+    changed || !removed.is_empty()
+}
+
+fn get_load(item: &Rc<dyn OpcodeGeneratorTrait>) -> Option<u16> {
+    downcast::<LoadGenerator>(item).map(|generator| generator.location)
+}
+
+fn get_store(item: &Rc<dyn OpcodeGeneratorTrait>) -> Option<u16> {
+    match downcast::<StoreGenerator>(item)?.store_type {
+        StoreType::Store(destination) => Some(destination),
+        _ => None
+    }
+}
+
+/* Stack is not used if the value moved to the memory directly */
+fn rewrite_load_store(generators: &mut Generators) -> bool {
+    let mut changed = false;
+    let mut index = 0;
+
+    while index + 1 < generators.len() {
+        let (first, second) = (&generators[index], &generators[index + 1]);
+        let store_type = match (get_load(first), downcast::<ConstantGenerator>(first), get_store(first), get_store(second), get_load(second)) {
+            (Some(source), _, _, Some(destination), _) if source == destination => None,
+            (Some(source), _, _, Some(destination), _) => Some(StoreType::Move { destination, source }),
+            (_, Some(constant), _, Some(destination), _) => Some(StoreType::FastStore { destination, source: constant.location }),
+            (_, _, Some(destination), _, Some(source)) if source == destination => Some(StoreType::CopyToStore(destination)),
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+
+        let replacement: Vec<Rc<dyn OpcodeGeneratorTrait>> = match store_type {
+            Some(store_type) => vec![Rc::new(StoreGenerator { store_type })],
+            None => Vec::new()
+        };
+
+        generators.splice(index..index + 2, replacement);
+        changed = true;
+    }
+
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::generator::OpcodeGenerator;
+
+    fn optimize_and_generate(generator: &OpcodeGenerator, storages: &mut Vec<StaticStorage>) -> Vec<u8> {
+        let mut opcodes = Vec::new();
+        generator.optimize(storages);
+        generator.generate(&mut opcodes);
+        opcodes
+    }
+
+    fn create_storage(constants: &[KaramelPrimative]) -> Vec<StaticStorage> {
+        let mut storage = StaticStorage::new(0);
+        for constant in constants {
+            storage.add_constant(Rc::new(constant.clone()));
+        }
+        vec![storage]
+    }
+
+    #[test]
+    fn constant_folding() {
+        let mut storages = create_storage(&[KaramelPrimative::Number(2.0), KaramelPrimative::Number(3.0), KaramelPrimative::Number(4.0)]);
+        let generator = OpcodeGenerator::new();
+        generator.create_constant(0);
+        generator.create_constant(1);
+        generator.add_opcode(VmOpCode::Multiply);
+        generator.create_constant(2);
+        generator.add_opcode(VmOpCode::Addition);
+        generator.add_opcode(VmOpCode::Halt);
+
+        let opcodes = optimize_and_generate(&generator, &mut storages);
+        assert_eq!(opcodes, vec![VmOpCode::Constant.into(), 4, VmOpCode::Halt.into()]);
+        assert_eq!(*storages[0].constants[4].deref(), KaramelPrimative::Number(10.0));
+    }
+
+    #[test]
+    fn division_by_zero() {
+        let mut storages = create_storage(&[KaramelPrimative::Number(1.0), KaramelPrimative::Number(0.0)]);
+        let generator = OpcodeGenerator::new();
+        generator.create_constant(0);
+        generator.create_constant(1);
+        generator.add_opcode(VmOpCode::Division);
+
+        let opcodes = optimize_and_generate(&generator, &mut storages);
+        assert_eq!(opcodes, vec![VmOpCode::Constant.into(), 0, VmOpCode::Constant.into(), 1, VmOpCode::Division.into()]);
+    }
+
+    #[test]
+    fn unreachable_code() {
+        let mut storages = create_storage(&[]);
+        let generator = OpcodeGenerator::new();
+        let location = generator.create_location();
+        generator.add_opcode(VmOpCode::Return);
+        generator.create_load(1);
+        generator.add_opcode(VmOpCode::Return);
+        generator.set_current_location(location.clone());
+        generator.create_load(2);
+        generator.create_jump(location);
+
+        let opcodes = optimize_and_generate(&generator, &mut storages);
+        assert_eq!(opcodes, vec![VmOpCode::Return.into(), VmOpCode::Load.into(), 2, VmOpCode::Jump.into(), 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn jump_threading() {
+        let mut storages = create_storage(&[]);
+        let generator = OpcodeGenerator::new();
+        let first = generator.create_location();
+        let second = generator.create_location();
+        let entry = generator.create_location();
+        generator.set_current_location(second.clone());
+        generator.create_load(2);
+        generator.add_opcode(VmOpCode::Halt);
+        generator.set_current_location(first.clone());
+        generator.create_jump(second);
+        generator.set_current_location(entry);
+        generator.create_jump(first);
+
+        let opcodes = optimize_and_generate(&generator, &mut storages);
+        assert_eq!(opcodes, vec![VmOpCode::Load.into(), 2, VmOpCode::Halt.into(), VmOpCode::Jump.into(), 0, 0, 0, 0, VmOpCode::Jump.into(), 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn jump_to_next() {
+        let mut storages = create_storage(&[]);
+        let generator = OpcodeGenerator::new();
+        let location = generator.create_location();
+        generator.create_jump(location.clone());
+        generator.set_current_location(location);
+        generator.add_opcode(VmOpCode::Halt);
+
+        let opcodes = optimize_and_generate(&generator, &mut storages);
+        assert_eq!(opcodes, vec![VmOpCode::Halt.into()]);
+    }
+
+    #[test]
+    fn infinite_jump() {
+        let mut storages = create_storage(&[]);
+        let generator = OpcodeGenerator::new();
+        let location = generator.current_location();
+        generator.create_jump(location);
+
+        let opcodes = optimize_and_generate(&generator, &mut storages);
+        assert_eq!(opcodes, vec![VmOpCode::Jump.into(), 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn load_store() {
+        let mut storages = create_storage(&[]);
+        let generator = OpcodeGenerator::new();
+        generator.create_load(1);
+        generator.create_store(2);
+        generator.create_load(3);
+        generator.create_store(3);
+        generator.create_constant(4);
+        generator.create_store(5);
+        generator.add_opcode(VmOpCode::Addition);
+        generator.create_store(6);
+        generator.create_load(6);
+
+        let opcodes = optimize_and_generate(&generator, &mut storages);
+        assert_eq!(opcodes, vec![VmOpCode::Move.into(), 2, 1, VmOpCode::FastStore.into(), 5, 4, VmOpCode::Addition.into(), VmOpCode::CopyToStore.into(), 6]);
+    }
+}
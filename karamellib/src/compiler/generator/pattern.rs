@@ -0,0 +1,64 @@
+use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait, add_operand, add_wide_prefix, dump_wide_prefix, is_wide};
+
+#[derive(Debug)]
+#[derive(Clone)]
+pub enum PatternType {
+    /// List with given item count. Rest flag allows more items.
+    List { size: usize, has_rest: bool },
+
+    /// Dictionary with given keys at stack.
+    Dict { size: usize }
+}
+
+#[derive(Debug)]
+#[derive(Clone)]
+/// Generate structure check opcodes for 'eşleştir' patterns.
+pub struct PatternGenerator {
+    pub pattern_type: PatternType
+}
+
+impl PatternGenerator {
+    fn is_wide(&self) -> bool {
+        match self.pattern_type {
+            PatternType::List { size, .. } => is_wide(&[size]),
+            PatternType::Dict { size } => is_wide(&[size])
+        }
+    }
+}
+
+impl OpcodeGeneratorTrait for PatternGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        let wide = self.is_wide();
+        add_wide_prefix(opcodes, wide);
+
+        match self.pattern_type {
+            PatternType::List { size, has_rest } => {
+                opcodes.push(VmOpCode::MatchList.into());
+                add_operand(opcodes, size, wide);
+                opcodes.push(has_rest as u8);
+            },
+            PatternType::Dict { size } => {
+                opcodes.push(VmOpCode::MatchDict.into());
+                add_operand(opcodes, size, wide);
+            }
+        };
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
+        let operand_size = dump_wide_prefix(builder, index.clone(), self.is_wide());
+        match self.pattern_type {
+            PatternType::List { size, has_rest } => {
+                let opcode_index = index.fetch_add(2 + operand_size, Ordering::SeqCst);
+                builder.add(opcode_index, VmOpCode::MatchList, size.to_string(), (has_rest as u8).to_string(), "".to_string());
+            },
+            PatternType::Dict { size } => {
+                let opcode_index = index.fetch_add(1 + operand_size, Ordering::SeqCst);
+                builder.add(opcode_index, VmOpCode::MatchDict, size.to_string(), "".to_string(), "".to_string());
+            }
+        };
+    }
+}
@@ -2,17 +2,21 @@ use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
 
 use crate::compiler::VmOpCode;
 
-use super::{DumpBuilder, OpcodeGeneratorTrait};
+use super::{DumpBuilder, OpcodeGeneratorTrait, add_operand, add_wide_prefix, dump_wide_prefix, is_wide};
 
 #[derive(Debug)]
 #[derive(Clone)]
 pub enum StoreType {
-    Store(u8),
+    Store(u16),
     FastStore {
-        destination: u8,
-        source: u8
+        destination: u16,
+        source: u16
     },
-    CopyToStore(u8)
+    CopyToStore(u16),
+    Move {
+        destination: u16,
+        source: u16
+    }
 }
 
 #[derive(Debug)]
@@ -21,27 +25,47 @@ pub struct StoreGenerator {
     pub store_type: StoreType 
 }
 
+impl StoreGenerator {
+    fn is_wide(&self) -> bool {
+        match self.store_type {
+            StoreType::Store(destination) => is_wide(&[destination.into()]),
+            StoreType::CopyToStore(destination) => is_wide(&[destination.into()]),
+            StoreType::FastStore { destination, source } => is_wide(&[destination.into(), source.into()]),
+            StoreType::Move { destination, source } => is_wide(&[destination.into(), source.into()])
+        }
+    }
+}
+
 impl OpcodeGeneratorTrait for StoreGenerator {
     fn generate(&self, opcodes: &mut Vec<u8>) {
+        let wide = self.is_wide();
+        add_wide_prefix(opcodes, wide);
+
         match self.store_type {
             StoreType::Store(destination) => {
                 opcodes.push(VmOpCode::Store.into());
-                opcodes.push(destination);
+                add_operand(opcodes, destination.into(), wide);
             },
             StoreType::CopyToStore(destination) => {
                 opcodes.push(VmOpCode::CopyToStore.into());
-                opcodes.push(destination);
+                add_operand(opcodes, destination.into(), wide);
             },
             StoreType::FastStore { destination, source} => {
                 opcodes.push(VmOpCode::FastStore.into());
-                opcodes.push(destination);
-                opcodes.push(source);
+                add_operand(opcodes, destination.into(), wide);
+                add_operand(opcodes, source.into(), wide);
+            },
+            StoreType::Move { destination, source} => {
+                opcodes.push(VmOpCode::Move.into());
+                add_operand(opcodes, destination.into(), wide);
+                add_operand(opcodes, source.into(), wide);
             }
         };
     }
 
     fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, _: &Vec<u8>) {
-        let opcode_index = index.fetch_add(2, Ordering::SeqCst);
+        let operand_size = dump_wide_prefix(builder, index.clone(), self.is_wide());
+        let opcode_index = index.fetch_add(1 + operand_size, Ordering::SeqCst);
         
         match self.store_type {
             StoreType::Store(destination) => {
@@ -52,8 +76,33 @@ impl OpcodeGeneratorTrait for StoreGenerator {
             },
             StoreType::FastStore { destination, source} => {
                 builder.add(opcode_index, VmOpCode::FastStore, destination.to_string(), source.to_string(), "".to_string());
-                index.fetch_add(1, Ordering::SeqCst);
+                index.fetch_add(operand_size, Ordering::SeqCst);
+            },
+            StoreType::Move { destination, source} => {
+                builder.add(opcode_index, VmOpCode::Move, destination.to_string(), source.to_string(), "".to_string());
+                index.fetch_add(operand_size, Ordering::SeqCst);
             }
         };
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let mut opcodes = Vec::new();
+        let generator = StoreGenerator { store_type: StoreType::FastStore { destination: 1, source: 2 } };
+        generator.generate(&mut opcodes);
+        assert_eq!(opcodes, vec![VmOpCode::FastStore.into(), 1, 2]);
+    }
+
+    #[test]
+    fn test_2() {
+        let mut opcodes = Vec::new();
+        let generator = StoreGenerator { store_type: StoreType::FastStore { destination: 1, source: 256 } };
+        generator.generate(&mut opcodes);
+        assert_eq!(opcodes, vec![VmOpCode::Wide.into(), VmOpCode::FastStore.into(), 1, 0, 0, 1]);
+    }
+}
@@ -0,0 +1,51 @@
+use std::{rc::Rc, sync::atomic::{AtomicUsize, Ordering}};
+
+use crate::compiler::VmOpCode;
+
+use super::{DumpBuilder, OpcodeGeneratorTrait, OpcodeLocation, opcode_to_location};
+
+#[derive(Clone)]
+/// Generate try opcodes.
+/// Register exception handler. If an error raised, continue execution from catch location.
+pub struct TryStartGenerator {
+    pub has_variable: bool,
+    pub location: Rc<OpcodeLocation>
+}
+
+impl OpcodeGeneratorTrait for TryStartGenerator {
+    fn generate(&self, opcodes: &mut Vec<u8>) {
+        opcodes.push(VmOpCode::TryStart.into());
+        opcodes.push(self.has_variable as u8);
+        self.location.apply(opcodes);
+    }
+
+    fn dump<'a>(&self, builder: &'a DumpBuilder, index: Rc<AtomicUsize>, opcodes: &Vec<u8>) {
+        let opcode_index = index.fetch_add(2, Ordering::SeqCst);
+        let location = opcode_to_location(index, opcodes);
+        builder.add(opcode_index, VmOpCode::TryStart, (self.has_variable as u8).to_string(), location.to_string(), "".to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_1() {
+        let mut opcodes = Vec::new();
+        let generator = TryStartGenerator {
+            has_variable: true,
+            location: Rc::new(OpcodeLocation::new(300))
+        };
+
+        generator.generate(&mut opcodes);
+
+        assert_eq!(opcodes.len(), 6);
+        assert_eq!(opcodes[0], VmOpCode::TryStart.into());
+        assert_eq!(opcodes[1], 1);
+        assert_eq!(opcodes[2], 44);
+        assert_eq!(opcodes[3], 1);
+        assert_eq!(opcodes[4], 0);
+        assert_eq!(opcodes[5], 0);
+    }
+}
@@ -9,6 +9,8 @@ pub mod module;
 pub mod scope;
 pub mod context;
 pub mod generator;
+pub mod source_map;
+pub mod bytecode;
 
 pub use self::compiler::*;
 pub use self::static_storage::*;
@@ -84,12 +86,16 @@ pub enum VmOpCode {
 
     /// Compare previous two opcode.
     /// If true, jump over 2 opcode and continue to execution.
-    /// If false, read next 2 opcode than calculate false jump location via high and low byte.
+    /// If false, read next 4 opcode than calculate false jump location as little endian value.
     Compare = 22,
     Jump = 23,
 
     Init = 24,
 
+    /// Move next item of the iterator list to stack and increase iterator index.
+    /// If there is no item left, read next 4 opcode than calculate end of loop location as little endian value.
+    Iterate = 25,
+
     /// Copy value from memory to stack.
     Load = 26,
     
@@ -105,7 +111,37 @@ pub enum VmOpCode {
     GetItem = 31,
     SetItem = 32,
     Constant = 33,
-    Halt = 34
+    Halt = 34,
+
+    /// Register exception handler with catch location. Read next opcode for error variable flag, than read next 4 opcode for catch location.
+    TryStart = 35,
+
+    /// Remove last registered exception handler.
+    TryEnd = 36,
+
+    /// Raise user defined error with last stack value.
+    Throw = 37,
+
+    /// Join stack values as a text. Read next opcode for total item.
+    Concat = 38,
+
+    /// Create new list or text from the object with start, end and step values at stack.
+    Slice = 39,
+
+    /// Push true if the argument at given memory location did not passed by the caller and default value needs to be calculated.
+    DefaultArgument = 40,
+
+    /// Push true if the last stack value is a list that matches with the pattern. Read next opcode for item count, than read next opcode for rest flag.
+    MatchList = 41,
+
+    /// Push true if the last stack value is a dictionary that has all keys at stack. Read next opcode for key count.
+    MatchDict = 42,
+
+    /// Prefix for the next opcode. Memory location and item count operands of the next opcode are two bytes.
+    Wide = 43,
+
+    /// Copy value from memory to an another memory location. Stack not involved at this operation.
+    Move = 44
 }
 
 impl From<VmOpCode> for u8 {
@@ -120,6 +156,16 @@ impl From<&VmOpCode> for u8 {
     }
 }
 
+impl VmOpCode {
+    /// Opcode of the byte, 'None' if there is no opcode with the value
+    pub fn from_byte(value: u8) -> Option<VmOpCode> {
+        match value {
+            1..=10 | 12 | 16..=44 => Some(unsafe { mem::transmute::<u8, VmOpCode>(value) }),
+            _ => None
+        }
+    }
+}
+
 
 impl fmt::Display for VmOpCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -16,6 +16,7 @@ use crate::types::CompilerResult;
 use super::context::KaramelCompilerContext;
 use super::ast::KaramelAstType;
 use super::function::FunctionReference;
+use super::source_map::{SourceLocation, SourcePosition, StatementPositions};
 
 use crate::error::*;
 
@@ -25,6 +26,8 @@ pub struct OpcodeModule {
     pub file_path: String,
     pub main_ast: Rc<KaramelAstType>,
     pub functions: RefCell<HashMap<String, Rc<FunctionReference>>>,
+    pub classes: RefCell<HashMap<String, Rc<dyn Class>>>,
+    pub lambdas: RefCell<Vec<Rc<FunctionReference>>>,
     pub modules: RefCell<HashMap<String, Rc<dyn Module>>>,
     pub path: Vec<String>
 }
@@ -36,6 +39,8 @@ impl OpcodeModule {
             file_path, 
             main_ast,
             functions: RefCell::new(HashMap::new()),
+            classes: RefCell::new(HashMap::new()),
+            lambdas: RefCell::new(Vec::new()),
             modules: RefCell::new(HashMap::new()),
             storage_index: 0,
             path: Vec::new()
@@ -71,7 +76,7 @@ impl Module for OpcodeModule {
     }
 
     fn get_classes(&self) -> Vec<Rc<dyn Class>> {
-        Vec::new()
+        self.classes.borrow().iter().map(|(_, class)| class.clone()).collect()
     }
 }
 
@@ -91,7 +96,8 @@ fn get_module_path(options: &KaramelCompilerContext, module_path: &PathBuf) -> V
     path
 }
 
-pub fn load_module(params: &[String], modules: &mut Vec<Rc<OpcodeModule>>, options: &mut KaramelCompilerContext, upper_storage_index: usize) -> Result<Rc<OpcodeModule>, KaramelError> {
+/// Load and compile the module. Errors that are not inside the module are shown at the given location of the load statement.
+pub fn load_module(params: &[String], modules: &mut Vec<Rc<OpcodeModule>>, options: &mut KaramelCompilerContext, upper_storage_index: usize, location: Option<SourceLocation>) -> Result<Rc<OpcodeModule>, KaramelError> {
     let mut path = PathBuf::from(&options.execution_path.path[..]);
     let module = params[(params.len() - 1)].to_string();
 
@@ -103,15 +109,19 @@ pub fn load_module(params: &[String], modules: &mut Vec<Rc<OpcodeModule>>, optio
 
     let content = match read_module_or_script(path.to_str().unwrap(), options) {
         Ok(content) => content,
-        Err(error) => return Err(KaramelError::new(0, 0, error))
+        Err(error) => return Err(load_error(options, location, error))
     };
 
     let mut parser = Parser::new(&content);
-    parser.parse()?;
+    if let Err(error) = parser.parse() {
+        return Err(set_module_error(options, &path, content, error));
+    }
 
     let syntax = SyntaxParser::new(parser.tokens().to_vec());
     return match syntax.parse() {
         Ok(ast) => {
+            options.source_map.add_file(path.to_str().unwrap().to_string(), content, syntax.positions());
+
             let module_storage = options.storages.len();
             options.storages.push(StaticStorage::new(module_storage));
             options.storages[module_storage].set_parent_location(upper_storage_index);
@@ -121,19 +131,40 @@ pub fn load_module(params: &[String], modules: &mut Vec<Rc<OpcodeModule>>, optio
             module.storage_index = module_storage;
 
             let module = Rc::new(module);
-            find_load_type(module.main_ast.clone(), options, modules, module.storage_index)?;
-            find_function_definition_type(module.clone(), ast.clone(), options, module_storage, true).map_err(KaramelErrorType::from)?;
+            find_load_type(module.main_ast.clone(), options, modules, module.storage_index).map_err(|error| load_error(options, location, error))?;
+            find_function_definition_type(module.clone(), ast.clone(), options, module_storage, true).map_err(|error| load_error(options, location, error))?;
             Ok(module.clone())
         },
-        Err(error) => return Err(error)
+        Err(error) => Err(set_module_error(options, &path, content, error))
     };
 }
 
+/* Error is shown with the module source code */
+fn set_module_error(options: &mut KaramelCompilerContext, path: &PathBuf, content: String, error: KaramelError) -> KaramelError {
+    let file = options.source_map.add_file(path.to_str().unwrap().to_string(), content, StatementPositions::new());
+    options.error_location = Some(SourceLocation {
+        file,
+        position: SourcePosition { line: error.line, column: error.column }
+    });
+    error
+}
+
+/* Location that found inside the module is kept */
+fn load_error(options: &mut KaramelCompilerContext, location: Option<SourceLocation>, error: KaramelErrorType) -> KaramelError {
+    if options.error_location.is_none() {
+        options.error_location = location;
+    }
+
+    let position = options.error_location.map(|location| location.position).unwrap_or_default();
+    KaramelError::new(position.line, position.column, error)
+}
+
 fn find_load_type(ast: Rc<KaramelAstType>, options: &mut KaramelCompilerContext, modules: &mut Vec<Rc<OpcodeModule>>, upper_storage_index: usize) -> CompilerResult {
     match &*ast {
         KaramelAstType::Load(module_name) => {
             if !options.has_module(&module_name) {
-                let module = load_module(module_name, modules, options, upper_storage_index)?;
+                let location = options.source_map.get_statement(&ast);
+                let module = load_module(module_name, modules, options, upper_storage_index, location)?;
                 options.add_module(module.clone());
                 modules.push(module.clone());
             }
@@ -153,7 +184,10 @@ pub fn get_modules(main_ast: Rc<KaramelAstType>, options: &mut KaramelCompilerCo
     let mut modules: Vec<Rc<OpcodeModule>> = Vec::new();
     match find_load_type(main_ast, options, &mut modules, 0) {
         Ok(()) => Ok(modules),
-        Err(error) => Err(KaramelError::new(0, 0, error))
+        Err(error) => {
+            let position = options.error_location.map(|location| location.position).unwrap_or_default();
+            Err(KaramelError::new(position.line, position.column, error))
+        }
     }
 }
 
@@ -213,7 +247,7 @@ mod tests {
     fn generate_file_name<T: Borrow<str>>(file_name: T) -> String {
         match std::env::current_exe() {
             Ok(path) => match path.parent() {
-                Some(parent_path) => parent_path.clone().join(file_name.borrow()).to_str().unwrap().to_string(),
+                Some(parent_path) => parent_path.join(file_name.borrow()).to_str().unwrap().to_string(),
                 _ => Path::new(".").join(file_name.borrow()).to_str().unwrap().to_string()
             },
             _ => Path::new(".").join(file_name.borrow()).to_str().unwrap().to_string()
@@ -230,7 +264,7 @@ fonk topla(bir, iki): dondur bir + iki"#;
             let mut modules = Vec::new();
             let mut options = KaramelCompilerContext::new();
             options.execution_path = get_execution_path(ExecutionSource::Code("".to_string()));
-            load_module(&[String::from("topla")].to_vec(), &mut modules, &mut options, 0)?;
+            load_module(&[String::from("topla")].to_vec(), &mut modules, &mut options, 0, None)?;
             Ok(())
         }, [topla_path].to_vec())
     }
@@ -249,8 +283,8 @@ fonk topla2(bir, iki): dondur module_1::topla(bir, iki)"#;
             let mut modules = Vec::new();
             let mut options = KaramelCompilerContext::new();
             options.execution_path = get_execution_path(ExecutionSource::Code("".to_string()));
-            load_module(&[String::from("module_1")].to_vec(), &mut modules, &mut options, 1)?;
-            load_module(&[String::from("module_2")].to_vec(), &mut modules, &mut options, 0)?;
+            load_module(&[String::from("module_1")].to_vec(), &mut modules, &mut options, 1, None)?;
+            load_module(&[String::from("module_2")].to_vec(), &mut modules, &mut options, 0, None)?;
             Ok(())
         }, [module_1_path, module_2_path].to_vec())
     }
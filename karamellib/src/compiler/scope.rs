@@ -1,6 +1,7 @@
 use std::ptr;
 
 use crate::types::VmObject;
+use super::function::FunctionReference;
 
 
 #[derive(Clone)]
@@ -8,7 +9,13 @@ pub struct Scope {
     pub location: *mut u8,
     pub call_return_assign_to_temp: bool,
     pub top_stack: *mut VmObject,
-    pub constant_ptr: *const VmObject
+    pub constant_ptr: *const VmObject,
+
+    /* Bit mask of the arguments that use default value */
+    pub missing_arguments: u64,
+
+    /* Called function, null for the main code */
+    pub function: *const FunctionReference
 }
 
 impl Scope {
@@ -17,7 +24,9 @@ impl Scope {
             call_return_assign_to_temp: false, 
             location: ptr::null_mut(), 
             top_stack: ptr::null_mut(), 
-            constant_ptr: ptr::null()
+            constant_ptr: ptr::null(),
+            missing_arguments: 0,
+            function: ptr::null()
         }
     }
 }
\ No newline at end of file
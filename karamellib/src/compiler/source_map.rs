@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+
+use super::ast::KaramelAstType;
+
+/// Line and column of the statement at the source code
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SourcePosition {
+    pub line: u32,
+    pub column: u32
+}
+
+/// Statement and operation positions that found by the syntax parser. Statements are identified with their memory address,
+/// references are kept to prevent reusing the address by an another statement.
+pub type StatementPositions = Vec<(Rc<KaramelAstType>, SourcePosition)>;
+
+pub fn statement_key(ast: &KaramelAstType) -> usize {
+    ast as *const KaramelAstType as usize
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SourceLocation {
+    pub file: usize,
+    pub position: SourcePosition
+}
+
+pub struct SourceFile {
+    pub path: String,
+    pub code: String
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackFrame {
+    pub function: String,
+    pub path: String,
+    pub position: SourcePosition
+}
+
+impl fmt::Display for StackFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /* Location is not known for the programs that loaded from the bytecode */
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.function),
+            false => write!(f, "{} [{}:{}:{}]", self.function, self.path, self.position.line, self.position.column)
+        }
+    }
+}
+
+/// Source files of the program with the statement positions and the opcode location table
+#[derive(Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+    statements: HashMap<usize, (Rc<KaramelAstType>, SourceLocation)>,
+
+    /* Sorted by the opcode location, opcodes use the last location before them */
+    locations: Vec<(usize, SourceLocation)>
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        SourceMap::default()
+    }
+
+    pub fn add_file(&mut self, path: String, code: String, positions: StatementPositions) -> usize {
+        let file = self.files.len();
+        self.files.push(SourceFile { path, code });
+        self.statements.extend(positions.into_iter().map(|(ast, position)| (statement_key(&ast), (ast, SourceLocation { file, position }))));
+        file
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn location_count(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Remove the files and the locations that added after the given counts
+    pub fn truncate(&mut self, files: usize, locations: usize) {
+        self.files.truncate(files);
+        self.locations.truncate(locations);
+        self.statements.retain(|_, (_, location)| location.file < files);
+    }
+
+    pub fn get_file(&self, file: usize) -> Option<&SourceFile> {
+        self.files.get(file)
+    }
+
+    pub fn get_statement(&self, ast: &KaramelAstType) -> Option<SourceLocation> {
+        self.statements.get(&statement_key(ast)).map(|(_, location)| *location)
+    }
+
+    pub fn add_location(&mut self, opcode_location: usize, location: SourceLocation) {
+        /* Statement without any opcode is replaced with the next one */
+        match self.locations.last_mut() {
+            Some((last_location, last)) if *last_location == opcode_location => *last = location,
+            _ => self.locations.push((opcode_location, location))
+        };
+    }
+
+    pub fn find(&self, opcode_location: usize) -> Option<SourceLocation> {
+        match self.locations.partition_point(|(location, _)| *location <= opcode_location) {
+            0 => None,
+            index => Some(self.locations[index - 1].1)
+        }
+    }
+
+    pub fn create_frame(&self, function: String, location: Option<SourceLocation>) -> StackFrame {
+        match location.and_then(|location| self.get_file(location.file).map(|file| (file, location.position))) {
+            Some((file, position)) => StackFrame { function, path: file.path.to_string(), position },
+            None => StackFrame { function, path: String::new(), position: SourcePosition::default() }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_location() {
+        let mut map = SourceMap::new();
+        let file = map.add_file("test.k".to_string(), String::new(), StatementPositions::new());
+        let location = |line| SourceLocation { file, position: SourcePosition { line, column: 0 } };
+
+        map.add_location(5, location(0));
+        map.add_location(10, location(1));
+        map.add_location(10, location(2));
+        map.add_location(20, location(3));
+
+        assert_eq!(map.find(0), None);
+        assert_eq!(map.find(5), Some(location(0)));
+        assert_eq!(map.find(19), Some(location(2)));
+        assert_eq!(map.find(100), Some(location(3)));
+    }
+
+    #[test]
+    fn truncate() {
+        let mut map = SourceMap::new();
+        let first = Rc::new(KaramelAstType::None);
+        let second = Rc::new(KaramelAstType::None);
+        map.add_file("ilk.k".to_string(), String::new(), vec![(first.clone(), SourcePosition::default())]);
+        map.add_location(5, SourceLocation { file: 0, position: SourcePosition::default() });
+
+        let file = map.add_file("ikinci.k".to_string(), String::new(), vec![(second.clone(), SourcePosition::default())]);
+        map.add_location(10, SourceLocation { file, position: SourcePosition::default() });
+
+        map.truncate(1, 1);
+        assert_eq!(map.file_count(), 1);
+        assert_eq!(map.find(10).map(|location| location.file), Some(0));
+        assert!(map.get_statement(&first).is_some());
+        assert!(map.get_statement(&second).is_none());
+    }
+}
@@ -1,6 +1,8 @@
 use crate::buildin::Module;
 use crate::types::*;
 use crate::compiler::*;
+use crate::constants::MAX_STORAGE_SIZE;
+use crate::error::KaramelErrorType;
 use std::rc::Rc;
 
 #[cfg(not(feature = "unittest"))]
@@ -28,7 +30,7 @@ impl StaticStorage {
         storage.constants_ptr = storage.constants.as_ptr();
         storage
     }
-    pub fn get_variable_size(&self) -> u8 { self.variables.len() as u8 }
+    pub fn get_variable_size(&self) -> u16 { self.variables.len() as u16 }
     
     pub fn set_parent_location(&mut self, parent_location: usize) {
         self.parent_location = Some(parent_location);
@@ -36,6 +38,19 @@ impl StaticStorage {
     pub fn get_parent_location(&self) -> Option<usize> {
         self.parent_location
     }
+
+    /* Locations could not be bigger than two bytes */
+    pub fn validate_size(&self) -> CompilerResult {
+        if self.variables.len() > MAX_STORAGE_SIZE {
+            return Err(KaramelErrorType::VariableLimitExceeded(MAX_STORAGE_SIZE));
+        }
+
+        if self.constants.len() > MAX_STORAGE_SIZE {
+            return Err(KaramelErrorType::ConstantLimitExceeded(MAX_STORAGE_SIZE));
+        }
+
+        Ok(())
+    }
     pub fn add_constant(&mut self, value: Rc<KaramelPrimative>) -> usize {
         let constant_position = self.constants.iter().position(|x| {
             *x.deref() == *value
@@ -50,39 +65,39 @@ impl StaticStorage {
         }
     }
 
-    pub fn add_variable(&mut self, name: &str) -> u8 {
+    pub fn add_variable(&mut self, name: &str) -> u16 {
         let result = self.variables.iter().position(|key| key == name);
         match result {
-            Some(location) => location as u8,
+            Some(location) => location as u16,
             _ => {
                 self.variables.push(name.to_string());
-                (self.variables.len()-1) as u8
+                (self.variables.len()-1) as u16
             }
         }
     }
 
-    pub fn get_variable_location(&self, name: &str) -> Option<u8> {
+    pub fn get_variable_location(&self, name: &str) -> Option<u16> {
         let result = self.variables.iter().position(|key| key == name);
         match result {
-            Some(location) => Some(location as u8),
+            Some(location) => Some(location as u16),
             _ => None
         }
     }
 
-    pub fn get_constant_location(&self, value: Rc<KaramelPrimative>) -> Option<u8> {
+    pub fn get_constant_location(&self, value: Rc<KaramelPrimative>) -> Option<u16> {
         return match self.constants.iter().position(|x| { *x.deref() == *value }) {
-            Some(number) => Some(number as u8),
+            Some(number) => Some(number as u16),
             _ => None
         };
     }
 
-    pub fn get_function_constant(&self, name: String, module: Rc<dyn Module>) -> Option<u8> {
+    pub fn get_function_constant(&self, name: String, module: Rc<dyn Module>) -> Option<u16> {
         
         for (index, item) in self.constants.iter().enumerate() {
             if let KaramelPrimative::Function(reference, _) = &*item.deref() {
                 if reference.name        == name && 
                    reference.module.get_path() == module.get_path() {
-                    return Some(index as u8);
+                    return Some(index as u16);
                 }
             }
         }
@@ -90,12 +105,12 @@ impl StaticStorage {
         None
     }
 
-    pub fn get_class_constant(&self, name: String, _module_path: Rc<dyn Module>) -> Option<u8> {
+    pub fn get_class_constant(&self, name: String, _module_path: Rc<dyn Module>) -> Option<u16> {
         
         for (index, item) in self.constants.iter().enumerate() {
             if let KaramelPrimative::Class(reference) = &*item.deref() {
                 if reference.get_class_name() == name {
-                    return Some(index as u8);
+                    return Some(index as u16);
                 }
             }
         }
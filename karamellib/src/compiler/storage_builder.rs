@@ -1,24 +1,92 @@
+use std::cell::Cell;
 use std::rc::Rc;
 
+use crate::constants::{ITERATOR_INDEX_NAME, ITERATOR_SOURCE_NAME, LAMBDA_NAME_PREFIX, MATCH_VALUE_NAME};
 use crate::error::KaramelErrorType;
-use crate::compiler::ast::KaramelAstType;
+use crate::compiler::ast::{KaramelAstType, KaramelPattern};
 use crate::compiler::value::KaramelPrimative;
 use crate::compiler::context::KaramelCompilerContext;
 use crate::syntax::loops::LoopType;
 
+use super::StaticStorage;
+use super::function::FunctionReference;
 use super::module::OpcodeModule;
-pub struct StorageBuilder;
+pub struct StorageBuilder {
+    loop_depth: Cell<usize>,
+    in_lambda: bool
+}
 
 impl StorageBuilder {
     pub fn new() -> Self {
-        StorageBuilder { }
+        StorageBuilder {
+            loop_depth: Cell::new(0),
+            in_lambda: false
+        }
+    }
+
+    fn new_lambda() -> Self {
+        StorageBuilder {
+            loop_depth: Cell::new(0),
+            in_lambda: true
+        }
     }
 
     pub fn prepare(&self, module: Rc<OpcodeModule>, ast: &KaramelAstType, storage_index: usize, options: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
-        self.build(module.clone(),ast, &KaramelAstType::None, options, storage_index)?;
+        match ast {
+            /* Inner functions have their own storage, only prepared function's body belongs to the storage */
+            KaramelAstType::FunctionDefination { name: _, arguments, body } => {
+                /* Default values are calculated inside the function */
+                for default in arguments.iter().filter_map(|argument| argument.default.as_ref()) {
+                    self.build(module.clone(),default, ast, options, storage_index)?;
+                }
+                self.build(module.clone(),body, ast, options, storage_index)?
+            },
+            _ => self.build(module.clone(),ast, &KaramelAstType::None, options, storage_index)?
+        };
         Ok(())
     }
 
+    fn build_pattern(&self, pattern: &KaramelPattern, depth: usize, options: &mut KaramelCompilerContext, storage_index: usize) {
+        match pattern {
+            KaramelPattern::Wildcard => (),
+            KaramelPattern::Variable(name) => {
+                options.storages[storage_index].add_variable(name);
+            },
+            KaramelPattern::Literal(literal) => if let KaramelAstType::Primative(primative) = &**literal {
+                options.storages[storage_index].add_constant(primative.clone());
+            },
+            KaramelPattern::List { items, rest } => {
+                for index in 0..items.len() {
+                    options.storages[storage_index].add_constant(Rc::new(KaramelPrimative::Number(index as f64)));
+                }
+
+                if let Some(rest) = rest {
+                    options.storages[storage_index].add_variable(rest);
+                    options.storages[storage_index].add_constant(Rc::new(KaramelPrimative::Number(items.len() as f64)));
+                    options.storages[storage_index].add_constant(Rc::new(KaramelPrimative::Empty));
+                }
+
+                for item in items {
+                    self.build_inner_pattern(item, depth, options, storage_index);
+                }
+            },
+            KaramelPattern::Dict(items) => {
+                for (key, item) in items {
+                    options.storages[storage_index].add_constant(key.clone());
+                    self.build_inner_pattern(item, depth, options, storage_index);
+                }
+            }
+        };
+    }
+
+    /* Inner list and dictionary values are kept at depth based hidden variables */
+    fn build_inner_pattern(&self, pattern: &KaramelPattern, depth: usize, options: &mut KaramelCompilerContext, storage_index: usize) {
+        if let KaramelPattern::List { items: _, rest: _ } | KaramelPattern::Dict(_) | KaramelPattern::Literal(_) = pattern {
+            options.storages[storage_index].add_variable(&format!("{}{}", MATCH_VALUE_NAME, depth + 1));
+        }
+        self.build_pattern(pattern, depth + 1, options, storage_index);
+    }
+
     fn build(&self, module: Rc<OpcodeModule>, ast: &KaramelAstType, _: &KaramelAstType, options: &mut KaramelCompilerContext, storage_index: usize) -> Result<(), KaramelErrorType> {
         use crate::buildin::Module;
         
@@ -106,6 +174,13 @@ impl StorageBuilder {
             } => {
                 self.build(module.clone(),source, ast, options, storage_index)?;
                 self.build(module.clone(),indexer, ast, options, storage_index)?;
+
+                /* Method name should be accessible even if there is a function with the same name */
+                if let KaramelAstType::FuncCall { func_name_expression, arguments: _, assign_to_temp: _ } = &**indexer {
+                    if let KaramelAstType::Symbol(function_name) = &**func_name_expression {
+                        options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Text(Rc::new(function_name.to_string()))));
+                    }
+                }
             },
 
 /*
@@ -138,6 +213,11 @@ impl StorageBuilder {
                         }
                         else {
                             options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Text(Rc::new(function_name.to_string()))));
+
+                            /* Callable value could be captured from the defined storage */
+                            if self.in_lambda {
+                                options.storages.get_mut(storage_index).unwrap().add_variable(function_name);
+                            }
                         }
                     },
                     KaramelAstType::ModulePath(names) => {
@@ -157,6 +237,32 @@ impl StorageBuilder {
                 self.build(module.clone(),expression, ast, options, storage_index)?;
             },
 
+            KaramelAstType::Interpolation(items) => {
+                for item in items {
+                    self.build(module.clone(),item, ast, options, storage_index)?;
+                }
+            },
+
+            KaramelAstType::Conditional { condition, true_expression, false_expression } => {
+                self.build(module.clone(),condition, ast, options, storage_index)?;
+                self.build(module.clone(),true_expression, ast, options, storage_index)?;
+                self.build(module.clone(),false_expression, ast, options, storage_index)?;
+            },
+
+            KaramelAstType::Throw(expression) => {
+                self.build(module.clone(),expression, ast, options, storage_index)?;
+            },
+
+            KaramelAstType::TryCatch { body, variable, catch_body } => {
+                self.build(module.clone(),body, ast, options, storage_index)?;
+
+                if let Some(variable) = variable {
+                    options.storages.get_mut(storage_index).unwrap().add_variable(variable);
+                }
+
+                self.build(module.clone(),catch_body, ast, options, storage_index)?;
+            },
+
             KaramelAstType::Loop {
                 loop_type,
                 body
@@ -170,9 +276,27 @@ impl StorageBuilder {
                     LoopType::Simple(control) => {
                         self.build(module.clone(),&*control, ast, options, storage_index)?
                     },
+                    LoopType::Iterate { variables, source } => {
+                        self.build(module.clone(),&*source, ast, options, storage_index)?;
+
+                        let storage = options.storages.get_mut(storage_index).unwrap();
+                        for variable in variables {
+                            storage.add_variable(variable);
+                        }
+
+                        /* Hidden iterator variables are unique for each loop depth */
+                        let depth = self.loop_depth.get();
+                        storage.add_variable(&format!("{}{}", ITERATOR_SOURCE_NAME, depth));
+                        storage.add_variable(&format!("{}{}", ITERATOR_INDEX_NAME, depth));
+                        storage.add_constant(Rc::new(KaramelPrimative::Number(0.0)));
+                    },
                     LoopType::Endless => {}
                 };
-                self.build(module.clone(),&*body, ast, options, storage_index)?;
+
+                self.loop_depth.set(self.loop_depth.get() + 1);
+                let result = self.build(module.clone(),&*body, ast, options, storage_index);
+                self.loop_depth.set(self.loop_depth.get() - 1);
+                result?;
             },
 
             KaramelAstType::Primative(primative) => {
@@ -186,6 +310,24 @@ impl StorageBuilder {
                 return Ok(())
             },
 
+            KaramelAstType::Match { expression, cases } => {
+                self.build(module.clone(),expression, ast, options, storage_index)?;
+                options.storages[storage_index].add_variable(&format!("{}{}", MATCH_VALUE_NAME, 0));
+
+                for case in cases {
+                    self.build_pattern(&case.pattern, 0, options, storage_index);
+                    if let Some(guard) = &case.guard {
+                        self.build(module.clone(),guard, ast, options, storage_index)?;
+                    }
+                    self.build(module.clone(),&case.body, ast, options, storage_index)?;
+                }
+            },
+
+            KaramelAstType::NamedArgument { name, expression } => {
+                options.storages.get_mut(storage_index).unwrap().add_constant(Rc::new(KaramelPrimative::Text(Rc::new(name.to_string()))));
+                self.build(module.clone(),expression, ast, options, storage_index)?;
+            },
+
             KaramelAstType::Dict(dict) => {
                 for dict_item in dict {
                     options.storages.get_mut(storage_index).unwrap().add_constant(dict_item.key.clone());
@@ -194,13 +336,62 @@ impl StorageBuilder {
                 return Ok(())
             },
 
+            KaramelAstType::Slice { body, start, end, step } => {
+                self.build(module.clone(),body, ast, options, storage_index)?;
+                self.build(module.clone(),start, ast, options, storage_index)?;
+                self.build(module.clone(),end, ast, options, storage_index)?;
+                self.build(module.clone(),step, ast, options, storage_index)?;
+            },
+
             KaramelAstType::Indexer { body, indexer } => {
                 self.build(module.clone(),body, ast, options, storage_index)?;
                 self.build(module.clone(),indexer, ast, options, storage_index)?;
             },
 
-            KaramelAstType::FunctionDefination { name: _, arguments: _, body } => {
-                self.build(module.clone(),body, ast, options, storage_index)?;
+            KaramelAstType::Lambda { arguments, body, storage_index: lambda_storage_index } => {
+                let new_storage_index = options.storages.len();
+                options.storages.push(StaticStorage::new(new_storage_index));
+                options.storages[new_storage_index].set_parent_location(storage_index);
+
+                for argument in arguments {
+                    options.storages[new_storage_index].add_variable(&argument.name);
+                }
+
+                let lambda_builder = StorageBuilder::new_lambda();
+                for default in arguments.iter().filter_map(|argument| argument.default.as_ref()) {
+                    lambda_builder.build(module.clone(),default, ast, options, new_storage_index)?;
+                }
+                lambda_builder.build(module.clone(),body, ast, options, new_storage_index)?;
+
+                /* Variables that are not argument, function or class are captured from the defined storage */
+                let mut captures = Vec::new();
+                let mut locals = Vec::new();
+                {
+                    let storage = &options.storages[new_storage_index];
+                    for variable in storage.variables.iter().skip(arguments.len()) {
+                        let is_function = storage.get_function_constant(variable.to_string(), module.clone()).is_some();
+                        let is_class = storage.get_class_constant(variable.to_string(), module.clone()).is_some();
+
+                        match is_function || is_class {
+                            true => locals.push(variable.to_string()),
+                            false => captures.push(variable.to_string())
+                        };
+                    }
+                }
+
+                /* Captured values are passed after the arguments */
+                options.storages[new_storage_index].variables = arguments.iter().map(|argument| argument.name.to_string()).chain(captures.iter().cloned()).chain(locals.into_iter()).collect();
+
+                for capture in captures.iter() {
+                    options.storages[storage_index].add_variable(capture);
+                }
+
+                let name = format!("{}{}", LAMBDA_NAME_PREFIX, new_storage_index);
+                let function = FunctionReference::opcode_lambda(name, arguments.to_vec(), captures.to_vec(), body.clone(), module.clone(), new_storage_index, storage_index);
+                module.lambdas.borrow_mut().push(function.clone());
+                lambda_storage_index.set(new_storage_index);
+
+                options.storages[storage_index].add_constant(Rc::new(KaramelPrimative::Function(function, None)));
             },
 
             KaramelAstType::IfStatement {
@@ -1,4 +1,3 @@
-use std::borrow::Borrow;
 use std::vec::Vec;
 use std::rc::Rc;
 use std::cell::RefCell;
@@ -10,6 +9,7 @@ use std::collections::HashMap;
 use crate::{buildin::Class, types::*};
 use crate::compiler::function::FunctionReference;
 use crate::compiler::GetType;
+use crate::vm::heap::Heap;
 
 pub const EMPTY_OBJECT: VmObject = VmObject(QNAN | EMPTY_FLAG);
 pub const TRUE_OBJECT: VmObject  = VmObject(QNAN | TRUE_FLAG);
@@ -83,6 +83,14 @@ impl KaramelPrimative {
         }
     }
 
+    /* Text values are used without quotes */
+    pub fn to_display_text(&self) -> String {
+        match self {
+            KaramelPrimative::Text(value) => value.to_string(),
+            _ => format!("{}", self)
+        }
+    }
+
     pub fn discriminant(&self) -> usize {
         match self {
             KaramelPrimative::Number(_) => 0,
@@ -265,20 +273,24 @@ impl VmObject {
             KaramelPrimative::Bool(true)       => TRUE_OBJECT,
             KaramelPrimative::Bool(false)      => FALSE_OBJECT,
             _                                => {
-                VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & (Rc::into_raw(primative)) as u64))
+                let pointer = Rc::into_raw(primative);
+                Heap::track(pointer);
+                VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & pointer as u64))
             }
         }
     }
 
     #[inline]
-    pub fn native_convert<T: Borrow<KaramelPrimative>>(primative: T) -> VmObject {
-        match primative.borrow() {
+    pub fn native_convert(primative: KaramelPrimative) -> VmObject {
+        match primative {
             KaramelPrimative::Empty            => VmObject(QNAN | EMPTY_FLAG),
             KaramelPrimative::Number(number)   => VmObject(number.to_bits()),
             KaramelPrimative::Bool(true)       => TRUE_OBJECT,
             KaramelPrimative::Bool(false)      => FALSE_OBJECT,
             _                                => {
-                VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & (Rc::into_raw(Rc::new(primative))) as u64))
+                let pointer = Rc::into_raw(Rc::new(primative));
+                Heap::track(pointer);
+                VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & pointer as u64))
             }
         }
     }
@@ -291,7 +303,9 @@ impl VmObject {
             KaramelPrimative::Bool(true)       => TRUE_OBJECT,
             KaramelPrimative::Bool(false)      => FALSE_OBJECT,
             _                                => {
-                VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & (Rc::into_raw(primative)) as u64))
+                let pointer = Rc::into_raw(primative);
+                Heap::track(pointer);
+                VmObject(QNAN | POINTER_FLAG | (POINTER_MASK & pointer as u64))
             }
         }
     }
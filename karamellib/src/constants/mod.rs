@@ -9,10 +9,60 @@ https://github.com/erhanbaris/karamel"#;
 
 pub static STARTUP_MODULE_NAME: &'static str = "baz.k";
 
+pub static CLASS_INSTANCE_NAME: &'static str = "bu";
+pub static CLASS_CONSTRUCTOR_NAME: &'static str = "başlat";
+
+/* Hidden variables for iteration loops. Names are not valid symbols, so they could not be accessed from script */
+pub static ITERATOR_SOURCE_NAME: &'static str = "#döngü_kaynak";
+pub static ITERATOR_INDEX_NAME: &'static str = "#döngü_sıra";
+pub static MATCH_VALUE_NAME: &'static str = "#eşleştir_değer";
+
+/* Default argument usage is kept as a bit mask at the function scope */
+pub static MAX_DEFAULT_ARGUMENT_INDEX: usize = 64;
+
+/* Functions without name are registered with storage index based names */
+pub static LAMBDA_NAME_PREFIX: &'static str = "#isimsiz_";
+
 
 pub static DUMP_OPCODE_TITLE: &'static str = "OPCODE DUMP";
 pub static DUMP_INDEX_WIDTH: usize = 5;
 pub static DUMP_OPCODE_WIDTH: usize = 15;
 pub static DUMP_OPCODE_COLUMN_1: usize = 5;
 pub static DUMP_OPCODE_COLUMN_2: usize = 5;
-pub static DUMP_OPCODE_COLUMN_3: usize = 5;
\ No newline at end of file
+pub static DUMP_OPCODE_COLUMN_3: usize = 5;
+
+/* Error information keys for 'yakala' block variable */
+pub static ERROR_CODE_NAME: &'static str = "kod";
+pub static ERROR_MESSAGE_NAME: &'static str = "mesaj";
+
+/* Garbage collection starts after this many heap objects are created */
+pub static HEAP_INITIAL_THRESHOLD: usize = 10_000;
+
+/* Variable and constant locations bigger than one byte are written with 'Wide' opcode */
+pub static MAX_STORAGE_SIZE: usize = u16::MAX as usize + 1;
+pub static MAX_ARGUMENT_SIZE: usize = u8::MAX as usize;
+pub static MAX_ITEM_SIZE: usize = u16::MAX as usize;
+
+/* Jump locations are written as four bytes */
+pub static OPCODE_LOCATION_SIZE: usize = 4;
+
+/* Compiled program file header */
+pub static BYTECODE_MAGIC: &[u8] = b"KRML";
+pub static BYTECODE_VERSION: u16 = 1;
+
+/* Execution limits, could be changed before running the code */
+pub static DEFAULT_STACK_SIZE: usize = 64 * 1024;
+pub static DEFAULT_MAX_CALL_DEPTH: usize = 16 * 1024;
+pub static TIME_LIMIT_CHECK_INTERVAL: u32 = 1024;
+
+/* Name of the main code at the call chain */
+pub static MAIN_FUNCTION_NAME: &'static str = "ana";
+
+/* File name of the code that is not read from a file */
+pub static SOURCE_CODE_NAME: &'static str = "<kod>";
+
+/* Functions that registered by the embedding application are at this module */
+pub static EMBEDDED_MODULE_NAME: &'static str = "!gömülü";
+
+/* Value of the last expression that evaluated by the embedding application */
+pub static EMBEDDED_RESULT_VARIABLE: &'static str = "!sonuç";
@@ -254,16 +254,220 @@ pub enum KaramelErrorType {
 
     #[error("Öperatör geçerli değil")]
     #[strum(message = "153")]
-    OperatorNotValid
+    OperatorNotValid,
+
+    #[error("Sınıf adı tanımlanmamış")]
+    #[strum(message = "154")]
+    ClassNameNotDefined,
+
+    #[error("'{0}' sınıfı önceden tanımlanmış")]
+    #[strum(message = "155")]
+    ClassAlreadyDefined(String),
+
+    #[error("Sınıf içerisinde sadece alan ve fonksiyon tanımlanabilir")]
+    #[strum(message = "156")]
+    ClassBodyNotValid,
+
+    #[error("Döngü için en fazla iki değişken tanımlanabilir")]
+    #[strum(message = "157")]
+    LoopVariableNotValid,
+
+    #[error("'{0}' türü döngü ile gezilemez")]
+    #[strum(message = "158")]
+    ValueNotIterable(String),
+
+    #[error("Değer döngü değişkenlerine ayrıştırılamadı")]
+    #[strum(message = "159")]
+    LoopValueCouldNotUnpack,
+
+    #[error("Hata yakalama bloğu bulunamadı")]
+    #[strum(message = "160")]
+    CatchBlockNotFound,
+
+    #[error("Hata yakalama değişkeni geçersiz")]
+    #[strum(message = "161")]
+    CatchVariableNotValid,
+
+    #[error("Hata fırlatmak için değer bulunamadı")]
+    #[strum(message = "162")]
+    ThrowValueNotFound,
+
+    #[error("{0}")]
+    #[strum(message = "163")]
+    UserError(String),
+
+    #[error("Biçimlendirme şablonu geçersiz")]
+    #[strum(message = "164")]
+    FormatNotValid,
+
+    #[error("'{0}' biçimlendirme değeri bulunamadı")]
+    #[strum(message = "165")]
+    FormatArgumentNotFound(String),
+
+    #[error("Yazı içerisindeki ifade geçersiz")]
+    #[strum(message = "166")]
+    InterpolationNotValid,
+
+    #[error("Yazı içerisindeki ifade kapatılmamış")]
+    #[strum(message = "167")]
+    InterpolationNotClosed,
+
+    #[error("Geçersiz kaçış karakteri")]
+    #[strum(message = "168")]
+    InvalidEscapeSequence,
+
+    #[error("Dilimleme adımı sıfır olamaz")]
+    #[strum(message = "169")]
+    SliceStepCannotBeZero,
+
+    #[error("'{0}' türü dilimlenemez")]
+    #[strum(message = "170")]
+    SliceNotSupported(String),
+
+    #[error("Koşullu ifadenin ikinci değeri bulunamadı")]
+    #[strum(message = "171")]
+    ConditionalElseNotFound,
+
+    #[error("Varsayılan değeri olmayan argüman, varsayılan değeri olan argümandan sonra tanımlanamaz")]
+    #[strum(message = "172")]
+    DefaultArgumentOrderNotValid,
+
+    #[error("Değişken sayıda değer alan argüman en sonda tanımlanmalı ve varsayılan değer almamalı")]
+    #[strum(message = "173")]
+    VariadicArgumentMustBeLast,
+
+    #[error("'{0}' argümanı birden fazla tanımlandı")]
+    #[strum(message = "174")]
+    ArgumentAlreadyDefined(String),
+
+    #[error("İsimli argümanlardan sonra sıralı argüman kullanılamaz")]
+    #[strum(message = "175")]
+    PositionalArgumentAfterNamed,
+
+    #[error("'{function}' fonksiyonunda '{argument}' isimli argüman bulunmuyor")]
+    #[strum(message = "176")]
+    ArgumentNotDefined {
+        function: String,
+        argument: String
+    },
+
+    #[error("'{function}' fonksiyonunun '{argument}' argümanına değer verilmedi")]
+    #[strum(message = "177")]
+    ArgumentValueNotFound {
+        function: String,
+        argument: String
+    },
+
+    #[error("'{function}' fonksiyonunun '{argument}' argümanına birden fazla değer verildi")]
+    #[strum(message = "178")]
+    ArgumentValueAlreadyGiven {
+        function: String,
+        argument: String
+    },
+
+    #[error("'{0}' fonksiyonu isimli argüman desteklemiyor")]
+    #[strum(message = "179")]
+    NamedArgumentNotSupported(String),
+
+    #[error("Eşleştirme durumları yeni satırda tanımlanmalı")]
+    #[strum(message = "180")]
+    MatchCaseNotFound,
+
+    #[error("Eşleştirme deseni geçerli değil")]
+    #[strum(message = "181")]
+    PatternNotValid,
+
+    #[error("Değişken sayısı sınırı aşıldı, en fazla {0} değişken tanımlanabilir")]
+    #[strum(message = "182")]
+    VariableLimitExceeded(usize),
+
+    #[error("Sabit sayısı sınırı aşıldı, en fazla {0} sabit tanımlanabilir")]
+    #[strum(message = "183")]
+    ConstantLimitExceeded(usize),
+
+    #[error("Argüman sayısı sınırı aşıldı, en fazla {0} argüman kullanılabilir")]
+    #[strum(message = "184")]
+    ArgumentLimitExceeded(usize),
+
+    #[error("Program boyutu sınırı aşıldı")]
+    #[strum(message = "185")]
+    OpcodeLimitExceeded,
+
+    #[error("Yığın taşması, çağrı zinciri: {}", .0.join(" -> "))]
+    #[strum(message = "186")]
+    StackOverflow(Vec<String>),
+
+    #[error("İşlem sınırı aşıldı, en fazla {0} işlem çalıştırılabilir")]
+    #[strum(message = "187")]
+    OpcodeBudgetExceeded(u64),
+
+    #[error("Bellek sınırı aşıldı, en fazla {0} nesne kullanılabilir")]
+    #[strum(message = "188")]
+    HeapLimitExceeded(usize),
+
+    #[error("Çalışma süresi sınırı aşıldı, en fazla {0} milisaniye çalışabilir")]
+    #[strum(message = "189")]
+    TimeLimitExceeded(u128),
+
+    #[error("Program durduruldu")]
+    #[strum(message = "190")]
+    Interrupted,
+
+    #[error("Derlenmiş kod sürümü ({0}) desteklenmiyor")]
+    #[strum(message = "191")]
+    BytecodeVersionNotSupported(u16),
+
+    #[error("Derlenmiş kod geçersiz")]
+    #[strum(message = "192")]
+    InvalidBytecode,
+
+    #[error("'{0}' derlenmiş koda yazılamaz")]
+    #[strum(message = "193")]
+    BytecodeValueNotSupported(String),
+
+    #[error("Fonksiyon çağrısı sadece çalışan program içinden yapılabilir")]
+    #[strum(message = "194")]
+    FunctionCallNotAvailable,
+
+    #[error("'{operator}' işlemi '{left}' ve '{right}' türleri ile kullanılamaz")]
+    #[strum(message = "195")]
+    OperatorTypeMismatch {
+        operator: String,
+        left: String,
+        right: String
+    },
+
+    #[error("'{operator}' işlemi '{value}' türü ile kullanılamaz")]
+    #[strum(message = "196")]
+    UnaryOperatorTypeMismatch {
+        operator: String,
+        value: String
+    },
+
+    #[error("'{found}' türündeki değer '{expected}' türüne çevrilemez")]
+    #[strum(message = "197")]
+    ValueConversionFailed {
+        expected: String,
+        found: String
+    },
+
+    #[error("'{0}' değişkeni bulunamadı")]
+    #[strum(message = "198")]
+    VariableNotFound(String),
+
+    #[error("'{0}' fonksiyonu bir nesne üzerinden çağrılmalıdır")]
+    #[strum(message = "199")]
+    MethodSourceNotFound(String),
+
+    #[error("Eleman sayısı sınırı aşıldı, en fazla {0} eleman kullanılabilir")]
+    #[strum(message = "200")]
+    ItemLimitExceeded(usize)
 }
 
-impl From<KaramelErrorType> for KaramelError {
-    fn from(item: KaramelErrorType) -> Self {
-        KaramelError {
-            column: 0,
-            line: 0,
-            error_type: item
-        }
+impl KaramelErrorType {
+    /// Execution limit and interrupt errors could not be handled by 'dene' block
+    pub fn stops_execution(&self) -> bool {
+        matches!(self, KaramelErrorType::OpcodeBudgetExceeded(_) | KaramelErrorType::HeapLimitExceeded(_) | KaramelErrorType::TimeLimitExceeded(_) | KaramelErrorType::Interrupted)
     }
 }
 
@@ -290,9 +494,14 @@ impl KaramelError {
 
 pub fn generate_error_message<T: AsRef<str>, E: Borrow<KaramelError>>(data: T, error: E) -> String {
     let error_ref = error.borrow();
-    let lines = data.as_ref().split(|c| c == '\n').collect::<Vec<_>>();
-    let line = lines.iter().skip(error_ref.line as usize).next().unwrap();
-    return format!("...\r\n{}\r\n{:>width$} [{}:{}] (#{}) {}", line, "^", error_ref.line, error_ref.column, error_ref.error_type.get_message().unwrap(), error_ref.error_type,  width=error_ref.column as usize);
+
+    /* Location could be outside of the given source code */
+    let line = data.as_ref().split(|c| c == '\n').nth(error_ref.line as usize).unwrap_or_default();
+    let number = match error_ref.error_type.get_message() {
+        Some(number) => format!("(#{}) ", number),
+        None => String::new()
+    };
+    return format!("...\r\n{}\r\n{:>width$} [{}:{}] {}{}", line, "^", error_ref.line, error_ref.column, number, error_ref.error_type,  width=error_ref.column as usize);
 }
 
 #[cfg(test)]
@@ -305,7 +514,7 @@ mod test {
     fn test_all_error_has_number() {
         for error_enum in super::KaramelErrorType::iter() {
             let error_message = format!("{}", error_enum);
-            if error_enum != KaramelErrorType::GeneralError("".to_string()) && error_message.len() == 0 {
+            if error_enum != KaramelErrorType::GeneralError("".to_string()) && error_enum != KaramelErrorType::UserError("".to_string()) && error_message.len() == 0 {
                 assert!(false, "'{:?}' hata mesaji yok", error_enum)
             }
 
@@ -331,4 +540,11 @@ mod test {
         let error_message = format!("{}", error_info.error_type);
         assert!(error_message.contains(&error_message), "Mesaj icerisinde hata kodu mesaji");
     }
+
+    #[test]
+    fn test_error_message_outside_of_source() {
+        let error_info = super::KaramelError::new(5, 3, super::KaramelErrorType::SyntaxError);
+        let error_message = super::generate_error_message("merhaba dunya", &error_info);
+        assert!(error_message.contains("[5:3] (#100)"), "{}", error_message);
+    }
 }
\ No newline at end of file
@@ -33,22 +33,10 @@ impl Log for ConsoleLogger {
 }
 
 pub fn write_stdout(context: &KaramelCompilerContext, data: String) {
-    match &context.stdout {
-        Some(out) => match out.try_borrow_mut() {
-            Ok(mut out_mut) => { out_mut.push_str(&data[..]) },
-            _ => ()
-        },
-        _ => ()
-    };
+    context.io.write(&data);
 }
 
 
 pub fn write_stderr(context: &KaramelCompilerContext, data: String) {
-    match &context.stderr {
-        Some(out) => match out.try_borrow_mut() {
-            Ok(mut out_mut) => { out_mut.push_str(&data[..]) },
-            _ => ()
-        },
-        _ => ()
-    };
+    context.io.write_err(&data);
 }
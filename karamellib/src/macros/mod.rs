@@ -72,6 +72,7 @@ macro_rules! get_memory_index {
 #[macro_export] 
 macro_rules! inc_memory_index {
     ($context: expr, $count: expr) => {{
+        $context.check_stack($count)?;
         $context.stack_ptr = karamel_dbg!($context.stack_ptr.add($count));
     }}
 }
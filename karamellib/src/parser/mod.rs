@@ -11,7 +11,7 @@ use std::collections::HashMap;
 
 use crate::{error::KaramelError, types::*};
 use self::number::NumberParser;
-use self::text::TextParser;
+use self::text::{TextParser, InterpolatedTextParser};
 use self::operator::OperatorParser;
 use self::symbol::SymbolParser;
 use self::line::LineParser;
@@ -56,6 +56,8 @@ impl<'a> Parser<'a> {
         let number_parser       = NumberParser     {};
         let text_parser_single  = TextParser       { tag:'\'' };
         let text_parser_double  = TextParser       { tag:'"' };
+        let interpolated_single = InterpolatedTextParser { tag:'\'' };
+        let interpolated_double = InterpolatedTextParser { tag:'"' };
         let operator_parser     = OperatorParser   {};
         let mut symbol_parser   = SymbolParser     {
             keywords: HashMap::new()
@@ -75,8 +77,11 @@ impl<'a> Parser<'a> {
             else if comment_parser.check(&mut self.tokinizer) {
                 status = comment_parser.parse(&mut self.tokinizer);
             }
-            else if symbol_parser.check(&mut self.tokinizer) {
-                status = symbol_parser.parse(&mut self.tokinizer);
+            else if interpolated_single.check(&mut self.tokinizer) {
+                status = interpolated_single.parse(&mut self.tokinizer);
+            }
+            else if interpolated_double.check(&mut self.tokinizer) {
+                status = interpolated_double.parse(&mut self.tokinizer);
             }
             else if text_parser_single.check(&mut self.tokinizer) {
                 status = text_parser_single.parse(&mut self.tokinizer);
@@ -84,6 +89,9 @@ impl<'a> Parser<'a> {
             else if text_parser_double.check(&mut self.tokinizer) {
                 status = text_parser_double.parse(&mut self.tokinizer);
             }
+            else if symbol_parser.check(&mut self.tokinizer) {
+                status = symbol_parser.parse(&mut self.tokinizer);
+            }
             else if number_parser.check(&mut self.tokinizer) {
                 status = number_parser.parse(&mut self.tokinizer);
             }
@@ -2,54 +2,244 @@ use std::rc::Rc;
 use crate::types::*;
 use crate::error::KaramelErrorType;
 
+use super::Parser;
+
 pub struct TextParser {
     pub tag: char
 }
 
+/* Text with '$' prefix. Expressions in curly braces are evaluated and added to text */
+pub struct InterpolatedTextParser {
+    pub tag: char
+}
+
 impl TokenParser for TextParser {
     fn check(&self, tokinizer: &mut Tokinizer) -> bool {
         let ch = tokinizer.get_char();
-        return ch == self.tag;
+        return ch == self.tag || (ch == 'r' && tokinizer.get_next_char() == self.tag);
     }
 
     fn parse(&self, tokinizer: &mut Tokinizer) -> Result<(), KaramelErrorType> {
+        let start_line   = tokinizer.line;
+        let start_column = tokinizer.column;
+
+        /* Escape sequences are not decoded at raw text */
+        let raw = tokinizer.get_char() == 'r';
+        if raw {
+            tokinizer.increase_index();
+        }
+
+        /* Triple quoted text could be written into multiple lines */
+        let multiline = self.is_triple_tag(tokinizer);
+        let tag_size  = if multiline { 3 } else { 1 };
+        for _ in 0..tag_size {
+            tokinizer.increase_index();
+        }
+
+        let mut buffer = String::new();
+        loop {
+            if tokinizer.is_end() {
+                return Err(KaramelErrorType::MissingStringDeliminator);
+            }
+
+            let ch = tokinizer.get_char();
+            if ch == self.tag && (!multiline || self.is_triple_tag(tokinizer)) {
+                for _ in 0..tag_size {
+                    tokinizer.increase_index();
+                }
+                break;
+            }
+
+            match ch {
+                '\\' if raw && tokinizer.get_next_char() == self.tag => {
+                    buffer.push(ch);
+                    buffer.push(self.tag);
+                    tokinizer.increase_index();
+                    tokinizer.increase_index();
+                },
+                '\\' if !raw => buffer.push(read_escape(tokinizer)?),
+                '\r' | '\n' if !multiline => return Err(KaramelErrorType::MissingStringDeliminator),
+                '\r' if tokinizer.get_next_char() == '\n' => tokinizer.increase_index(),
+                '\n' => {
+                    buffer.push(ch);
+                    tokinizer.increase_index();
+                    tokinizer.increate_line();
+                },
+                _ => {
+                    buffer.push(ch);
+                    tokinizer.increase_index();
+                }
+            };
+        }
+
+        tokinizer.tokens.push(Token {
+            line: start_line,
+            start: start_column,
+            end: tokinizer.column,
+            token_type: KaramelTokenType::Text(Rc::new(buffer))
+        });
+        return Ok(());
+    }
+}
+
+impl TextParser {
+    fn is_triple_tag(&self, tokinizer: &mut Tokinizer) -> bool {
+        tokinizer.get_char() == self.tag && tokinizer.get_next_char() == self.tag && tokinizer.get_third_char() == self.tag
+    }
+}
+
+/* Decode escape sequence and move after it. Supported sequences are \n, \t, \r, \0, \\, \', \" and \u{...} */
+fn read_escape(tokinizer: &mut Tokinizer) -> Result<char, KaramelErrorType> {
+    tokinizer.increase_index();
+    if tokinizer.is_end() {
+        return Err(KaramelErrorType::MissingStringDeliminator);
+    }
+
+    let decoded = match tokinizer.get_char() {
+        'n'  => '\n',
+        't'  => '\t',
+        'r'  => '\r',
+        '0'  => '\0',
+        '\\' => '\\',
+        '\'' => '\'',
+        '"'  => '"',
+        'u'  => return read_unicode_escape(tokinizer),
+        _    => return Err(KaramelErrorType::InvalidEscapeSequence)
+    };
+
+    tokinizer.increase_index();
+    Ok(decoded)
+}
+
+fn read_unicode_escape(tokinizer: &mut Tokinizer) -> Result<char, KaramelErrorType> {
+    tokinizer.increase_index();
+    if tokinizer.get_char() != '{' {
+        return Err(KaramelErrorType::InvalidEscapeSequence);
+    }
+    tokinizer.increase_index();
+
+    let mut code = String::new();
+    while !tokinizer.is_end() && tokinizer.get_char() != '}' && code.len() <= 6 {
+        code.push(tokinizer.get_char());
         tokinizer.increase_index();
+    }
 
-        let mut ch: char      = '\0';
-        let mut ch_next: char;
-        let start             = tokinizer.index as usize;
+    if tokinizer.get_char() != '}' || code.is_empty() || code.len() > 6 {
+        return Err(KaramelErrorType::InvalidEscapeSequence);
+    }
+    tokinizer.increase_index();
+
+    match u32::from_str_radix(&code, 16).ok().and_then(char::from_u32) {
+        Some(ch) => Ok(ch),
+        None => Err(KaramelErrorType::InvalidEscapeSequence)
+    }
+}
+
+impl TokenParser for InterpolatedTextParser {
+    fn check(&self, tokinizer: &mut Tokinizer) -> bool {
+        tokinizer.get_char() == '$' && tokinizer.get_next_char() == self.tag
+    }
+
+    fn parse(&self, tokinizer: &mut Tokinizer) -> Result<(), KaramelErrorType> {
         let start_column = tokinizer.column;
-        let mut end           = start;
+        tokinizer.increase_index();
+        tokinizer.increase_index();
+
+        let mut parts  = Vec::new();
+        let mut buffer = String::new();
+        let mut closed = false;
 
         while !tokinizer.is_end() {
-            ch      = tokinizer.get_char();
-            ch_next = tokinizer.get_next_char();
+            let ch      = tokinizer.get_char();
+            let ch_next = tokinizer.get_next_char();
 
-            if ch == '\\' && ch_next == self.tag {
-                end += ch.len_utf8();
-                end += 1; // for tag char
+            if ch == '\\' {
+                buffer.push(read_escape(tokinizer)?);
+                continue;
+            }
+            else if (ch == '{' && ch_next == '{') || (ch == '}' && ch_next == '}') {
+                buffer.push(ch_next);
                 tokinizer.increase_index();
             }
             else if ch == self.tag {
                 tokinizer.increase_index();
+                closed = true;
                 break;
             }
+            else if ch == '}' {
+                return Err(KaramelErrorType::InterpolationNotValid);
+            }
+            else if ch == '{' {
+                if !buffer.is_empty() {
+                    parts.push(KaramelTextPart::Text(Rc::new(std::mem::take(&mut buffer))));
+                }
+
+                tokinizer.increase_index();
+                parts.push(KaramelTextPart::Expression(self.parse_expression(tokinizer)?));
+                continue;
+            }
             else {
-                end += ch.len_utf8();
+                buffer.push(ch);
             }
 
             tokinizer.increase_index();
         }
 
-        if ch != self.tag {
+        if !closed {
             return Err(KaramelErrorType::MissingStringDeliminator);
         }
 
-        tokinizer.add_token(start_column - 1, KaramelTokenType::Text(Rc::new(tokinizer.data[start..end].to_string())));
+        if !buffer.is_empty() {
+            parts.push(KaramelTextPart::Text(Rc::new(buffer)));
+        }
+
+        tokinizer.add_token(start_column, KaramelTokenType::InterpolatedText(parts));
         return Ok(());
     }
 }
 
+impl InterpolatedTextParser {
+    fn parse_expression(&self, tokinizer: &mut Tokinizer) -> Result<Vec<Token>, KaramelErrorType> {
+        let line   = tokinizer.line;
+        let column = tokinizer.column;
+        let mut expression = String::new();
+        let mut depth = 0;
+
+        loop {
+            let ch = tokinizer.get_char();
+            if tokinizer.is_end() || ch == self.tag || ch.is_new_line() {
+                return Err(KaramelErrorType::InterpolationNotClosed);
+            }
+
+            tokinizer.increase_index();
+            match ch {
+                '{' => depth += 1,
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                _ => ()
+            };
+            expression.push(ch);
+        }
+
+        let mut parser = Parser::new(&expression);
+        if let Err(error) = parser.parse() {
+            return Err(error.error_type);
+        }
+
+        /* Token positions should point to the original text */
+        let tokens = parser.tokens().into_iter().map(|mut token| {
+            token.line   = line;
+            token.start += column;
+            token.end   += column;
+            token
+        }).collect::<Vec<Token>>();
+
+        match tokens.iter().all(|token| matches!(token.token_type, KaramelTokenType::WhiteSpace(_))) {
+            true => Err(KaramelErrorType::InterpolationNotValid),
+            false => Ok(tokens)
+        }
+    }
+}
 
 #[cfg(test)]
 #[test]
@@ -67,6 +257,9 @@ fn text_parse_test_1() {
         data: data.to_string(),
         index: 0
     };
+    tokinizer.iter_second.next();
+    tokinizer.iter_third.next();
+    tokinizer.iter_third.next();
 
     let parser = TextParser { tag: '"' };
     let parse_result = parser.parse(&mut tokinizer);
@@ -99,6 +292,9 @@ fn text_parse_test_2() {
         data: data.to_string(),
         index: 0
     };
+    tokinizer.iter_second.next();
+    tokinizer.iter_third.next();
+    tokinizer.iter_third.next();
 
     let parser = TextParser { tag: '\'' };
     let parse_result = parser.parse(&mut tokinizer);
@@ -114,3 +310,98 @@ fn text_parse_test_2() {
         _ => assert_eq!(true, false)
     };
 }
+
+#[cfg(test)]
+#[test]
+fn interpolated_text_parse_test_1() {
+    let mut parser = Parser::new("$'merhaba {ad}, {{{1 + 2}}}'");
+    assert_eq!(parser.parse().is_ok(), true);
+
+    let tokens = parser.tokens();
+    assert_eq!(tokens.len(), 1);
+
+    match &tokens[0].token_type {
+        KaramelTokenType::InterpolatedText(parts) => {
+            assert_eq!(parts.len(), 5);
+            assert_eq!(parts[0], KaramelTextPart::Text(Rc::new("merhaba ".to_string())));
+            match &parts[1] {
+                KaramelTextPart::Expression(tokens) => {
+                    assert_eq!(tokens.len(), 1);
+                    assert_eq!(tokens[0].start, 11);
+                    assert_eq!(tokens[0].token_type, KaramelTokenType::Symbol(Rc::new("ad".to_string())));
+                },
+                _ => assert_eq!(true, false)
+            };
+            assert_eq!(parts[2], KaramelTextPart::Text(Rc::new(", {".to_string())));
+            match &parts[3] {
+                KaramelTextPart::Expression(tokens) => assert_eq!(tokens.len(), 5),
+                _ => assert_eq!(true, false)
+            };
+            assert_eq!(parts[4], KaramelTextPart::Text(Rc::new("}".to_string())));
+        },
+        _ => assert_eq!(true, false)
+    };
+}
+
+#[cfg(test)]
+#[test]
+fn interpolated_text_parse_test_2() {
+    assert_eq!(Parser::new("$'{}'").parse().err().unwrap().error_type, KaramelErrorType::InterpolationNotValid);
+    assert_eq!(Parser::new("$'{a'").parse().err().unwrap().error_type, KaramelErrorType::InterpolationNotClosed);
+    assert_eq!(Parser::new("$'a}'").parse().err().unwrap().error_type, KaramelErrorType::InterpolationNotValid);
+}
+
+#[cfg(test)]
+fn parse_single_text(data: &str) -> Result<Token, KaramelErrorType> {
+    let mut parser = Parser::new(data);
+    if let Err(error) = parser.parse() {
+        return Err(error.error_type);
+    }
+
+    Ok(parser.tokens().remove(0))
+}
+
+#[cfg(test)]
+#[test]
+fn escape_text_parse_test() {
+    let token = parse_single_text(r#"'a\nb\tc\\d\'e\"f\u{1F600}\u{131}'"#).unwrap();
+    assert_eq!(token.token_type, KaramelTokenType::Text(Rc::new("a\nb\tc\\d'e\"f😀ı".to_string())));
+
+    assert_eq!(parse_single_text(r#"'\q'"#).err(), Some(KaramelErrorType::InvalidEscapeSequence));
+    assert_eq!(parse_single_text(r#"'\u{}'"#).err(), Some(KaramelErrorType::InvalidEscapeSequence));
+    assert_eq!(parse_single_text(r#"'\u{110000}'"#).err(), Some(KaramelErrorType::InvalidEscapeSequence));
+    assert_eq!(parse_single_text(r#"'\u0041'"#).err(), Some(KaramelErrorType::InvalidEscapeSequence));
+    assert_eq!(parse_single_text("'merhaba\ndünya'").err(), Some(KaramelErrorType::MissingStringDeliminator));
+}
+
+#[cfg(test)]
+#[test]
+fn raw_text_parse_test() {
+    let token = parse_single_text(r#"r'c:\yol\n'"#).unwrap();
+    assert_eq!(token.token_type, KaramelTokenType::Text(Rc::new(r#"c:\yol\n"#.to_string())));
+    assert_eq!(token.start, 0);
+    assert_eq!(token.end, 11);
+
+    let token = parse_single_text(r#"r"a\"b""#).unwrap();
+    assert_eq!(token.token_type, KaramelTokenType::Text(Rc::new(r#"a\"b"#.to_string())));
+}
+
+#[cfg(test)]
+#[test]
+fn multiline_text_parse_test() {
+    let mut parser = Parser::new("'''birinci\n  'ikinci'\\t\n''' a");
+    assert_eq!(parser.parse().is_ok(), true);
+
+    let tokens = parser.tokens();
+    assert_eq!(tokens[0].token_type, KaramelTokenType::Text(Rc::new("birinci\n  'ikinci'\t\n".to_string())));
+    assert_eq!(tokens[0].line, 0);
+    assert_eq!(tokens[0].start, 0);
+
+    /* Tokens after the text have the correct position */
+    let symbol = tokens.iter().find(|token| token.token_type.is_symbol()).unwrap();
+    assert_eq!(symbol.line, 2);
+    assert_eq!(symbol.start, 4);
+
+    assert_eq!(parse_single_text("\"\"\"kapanmadı\n\"").err(), Some(KaramelErrorType::MissingStringDeliminator));
+    assert_eq!(parse_single_text("''").unwrap().token_type, KaramelTokenType::Text(Rc::new("".to_string())));
+}
@@ -42,6 +42,7 @@ pub fn parse_binary<T: SyntaxParserTrait>(parser: &SyntaxParser, operators: &[Ka
     loop {
         let index_backup = parser.get_index();
         parser.cleanup_whitespaces();
+        let position = parser.current_position();
         
         if let Some(operator) = parser.match_operator(operators) {
             if !functions_updated_for_temp {
@@ -58,10 +59,14 @@ pub fn parse_binary<T: SyntaxParserTrait>(parser: &SyntaxParser, operators: &[Ka
                 Err(_) => return right_expr
             };
 
+            /* Binary operation does not have a reference yet, operator position is kept with the right operand */
+            let right_expr = Rc::new(right_expr.unwrap());
+            parser.add_position(&right_expr, position);
+
             left_expr = KaramelAstType::Binary {
                 left: Rc::new(left_expr),
                 operator,
-                right: Rc::new(right_expr.unwrap())
+                right: right_expr
             };
         }
         else {
@@ -8,6 +8,7 @@ use crate::syntax::util::map_parser;
 use crate::compiler::ast::KaramelAstType;
 use crate::syntax::statement::StatementParser;
 use crate::syntax::function_defination::FunctionDefinationParser;
+use crate::syntax::class_defination::ClassDefinationParser;
 
 struct BlockParser;
 pub struct SingleLineBlockParser;
@@ -30,16 +31,23 @@ impl SyntaxParserTrait for MultiLineBlockParser {
 impl BlockParser {
     fn parse(parser: &SyntaxParser, multiline: bool) -> AstResult {
         let mut block_asts: Vec<Rc<KaramelAstType>> = Vec::new();
+        let mut first_position = None;
         let current_indentation = parser.get_indentation();
 
         loop {
             parser.indentation_check()?;
-            let ast = map_parser(parser, &[FunctionDefinationParser::parse, StatementParser::parse, ExpressionParser::parse, NewlineParser::parse])?;
+            let position = parser.current_position();
+            let ast = map_parser(parser, &[ClassDefinationParser::parse, FunctionDefinationParser::parse, StatementParser::parse, ExpressionParser::parse, NewlineParser::parse])?;
     
             match ast {
                 KaramelAstType::None =>  break,
                 KaramelAstType::NewLine =>  (),
-                _ => block_asts.push(Rc::new(ast))
+                _ => {
+                    let ast = Rc::new(ast);
+                    parser.add_position(&ast, position);
+                    first_position.get_or_insert(position);
+                    block_asts.push(ast)
+                }
             };
 
             if !multiline { break; }
@@ -50,6 +58,11 @@ impl BlockParser {
             }
         }
 
+        parser.single_statement_position.set(match block_asts.len() {
+            1 => first_position,
+            _ => None
+        });
+
         return match block_asts.len() {
             0 => Ok(KaramelAstType::None),
             1 => Ok((&*block_asts[0]).clone()),
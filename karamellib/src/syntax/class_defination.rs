@@ -0,0 +1,80 @@
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait};
+use crate::syntax::primative::PrimativeParser;
+use crate::compiler::ast::{KaramelAstType, KaramelDictItem};
+use crate::compiler::value::KaramelPrimative;
+use crate::syntax::block::MultiLineBlockParser;
+use crate::error::KaramelErrorType;
+use std::rc::Rc;
+
+pub struct ClassDefinationParser;
+
+impl SyntaxParserTrait for ClassDefinationParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.indentation_check()?;
+
+        if parser.match_keyword(KaramelKeywordType::Class) {
+            let indentation = parser.get_indentation();
+
+            parser.cleanup_whitespaces();
+
+            let name_expression = PrimativeParser::parse_symbol(parser)?;
+            let class_name = match name_expression {
+                KaramelAstType::Symbol(text) => text,
+                _ => {
+                    return Err(KaramelErrorType::ClassNameNotDefined);
+                }
+            };
+
+            parser.cleanup_whitespaces();
+            if let None = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
+                return Err(KaramelErrorType::ColonMarkMissing);
+            }
+
+            parser.cleanup_whitespaces();
+
+            /* Class body must be defined at new lines */
+            let body = match parser.get_newline() {
+                (true, _) => {
+                    parser.in_indication()?;
+                    MultiLineBlockParser::parse(parser)
+                },
+                (false, _) => return Err(KaramelErrorType::ClassBodyNotValid)
+            }?;
+
+            let items = match body {
+                KaramelAstType::Block(blocks) => blocks,
+                KaramelAstType::None => return Err(KaramelErrorType::ClassBodyNotValid),
+                _ => vec![Rc::new(body)]
+            };
+
+            let mut fields  = Vec::new();
+            let mut methods = Vec::new();
+
+            for item in items {
+                match &*item {
+                    KaramelAstType::FunctionDefination { name: _, arguments: _, body: _ } => methods.push(item.clone()),
+                    KaramelAstType::Assignment { variable, operator: KaramelOperatorType::Assign, expression } => match &**variable {
+                        KaramelAstType::Symbol(field_name) => fields.push(Rc::new(KaramelDictItem {
+                            key: Rc::new(KaramelPrimative::Text(Rc::new(field_name.to_string()))),
+                            value: expression.clone()
+                        })),
+                        _ => return Err(KaramelErrorType::ClassBodyNotValid)
+                    },
+                    _ => return Err(KaramelErrorType::ClassBodyNotValid)
+                };
+            }
+
+            parser.set_indentation(indentation);
+            return Ok(KaramelAstType::ClassDefination {
+                name: class_name,
+                fields,
+                methods
+            });
+        }
+
+        parser.set_index(index_backup);
+        return Ok(KaramelAstType::None);
+    }
+}
@@ -53,6 +53,7 @@ pub fn special_control(parser: &SyntaxParser) -> AstResult {
     loop {
         let index_backup = parser.get_index();
         parser.cleanup_whitespaces();
+        let position = parser.current_position();
         if let Some(operator) = parser.match_operator(&operators) {
             if !functions_updated_for_temp {
                 update_functions_for_temp_return(&left_expr);
@@ -67,23 +68,16 @@ pub fn special_control(parser: &SyntaxParser) -> AstResult {
                 Err(_) => return right_expr
             };
 
-            left_expr = match operator {
-                KaramelOperatorType::LessEqualThan => KaramelAstType::Control {
-                    left: Rc::new(right_expr.unwrap()),
-                    operator: KaramelOperatorType::GreaterEqualThan,
-                    right: Rc::new(left_expr)
-                },
-                KaramelOperatorType::LessThan => KaramelAstType::Control {
-                    left: Rc::new(right_expr.unwrap()),
-                    operator: KaramelOperatorType::GreaterThan,
-                    right: Rc::new(left_expr)
-                },
-                _ => KaramelAstType::Control {
-                    left: Rc::new(left_expr),
-                    operator,
-                    right: Rc::new(right_expr.unwrap())
-                }
+            /* Less than operators are written with the reversed operands */
+            let (left, operator, right) = match operator {
+                KaramelOperatorType::LessEqualThan => (Rc::new(right_expr.unwrap()), KaramelOperatorType::GreaterEqualThan, Rc::new(left_expr)),
+                KaramelOperatorType::LessThan => (Rc::new(right_expr.unwrap()), KaramelOperatorType::GreaterThan, Rc::new(left_expr)),
+                _ => (Rc::new(left_expr), operator, Rc::new(right_expr.unwrap()))
             };
+
+            /* Operator position is kept with the right operand */
+            parser.add_position(&right, position);
+            left_expr = KaramelAstType::Control { left, operator, right };
         }        
         else {
             parser.set_index(index_backup);
@@ -16,6 +16,13 @@ pub struct ExpressionParser;
 
 impl SyntaxParserTrait for ExpressionParser {
     fn parse(parser: &SyntaxParser) -> AstResult {
+        let ast = Self::parse_expression(parser)?;
+        Self::parse_conditional(ast, parser)
+    }
+}
+
+impl ExpressionParser {
+    fn parse_expression(parser: &SyntaxParser) -> AstResult {
         let mut ast = OrParser::parse(parser)?;
     
         loop {
@@ -56,4 +63,65 @@ impl SyntaxParserTrait for ExpressionParser {
 
         Ok(ast)
     }
+
+    /* parse for 'condition ? value : value' and 'condition ise value yoksa value' */
+    fn parse_conditional(condition: KaramelAstType, parser: &SyntaxParser) -> AstResult {
+        if condition == KaramelAstType::None {
+            return Ok(condition);
+        }
+
+        let index_backup = parser.get_index();
+        parser.cleanup_whitespaces();
+
+        let separator = match parser.match_operator(&[KaramelOperatorType::QuestionMark]) {
+            Some(_) => KaramelTokenType::Operator(KaramelOperatorType::ColonMark),
+            None => match parser.match_keyword(KaramelKeywordType::If) {
+                true => KaramelTokenType::Keyword(KaramelKeywordType::Else),
+                false => {
+                    parser.set_index(index_backup);
+                    return Ok(condition);
+                }
+            }
+        };
+
+        parser.cleanup_whitespaces();
+
+        /* 'condition ise:' is an if statement */
+        if separator.is_keyword() && (parser.check_operator(&KaramelOperatorType::ColonMark) || parser.get_newline().0) {
+            parser.set_index(index_backup);
+            return Ok(condition);
+        }
+
+        let true_expression = ExpressionParser::parse(parser)?;
+        if true_expression == KaramelAstType::None {
+            return Err(KaramelErrorType::InvalidExpression);
+        }
+
+        parser.cleanup_whitespaces();
+        let separator_found = match &separator {
+            KaramelTokenType::Operator(operator) => parser.match_operator(&[*operator]).is_some(),
+            _ => parser.match_keyword(KaramelKeywordType::Else)
+        };
+
+        if !separator_found {
+            return Err(KaramelErrorType::ConditionalElseNotFound);
+        }
+
+        parser.cleanup_whitespaces();
+        let false_expression = ExpressionParser::parse(parser)?;
+        if false_expression == KaramelAstType::None {
+            return Err(KaramelErrorType::InvalidExpression);
+        }
+
+        /* Values are needed at stack */
+        update_functions_for_temp_return(&condition);
+        update_functions_for_temp_return(&true_expression);
+        update_functions_for_temp_return(&false_expression);
+
+        Ok(KaramelAstType::Conditional {
+            condition: Rc::new(condition),
+            true_expression: Rc::new(true_expression),
+            false_expression: Rc::new(false_expression)
+        })
+    }
 }
@@ -2,7 +2,7 @@ use std::cell::Cell;
 use std::rc::Rc;
 
 use crate::types::*;
-use crate::syntax::util::update_functions_for_temp_return;
+use crate::syntax::util::{is_value_used, update_functions_for_temp_return};
 use crate::syntax::{SyntaxParser, SyntaxParserTrait, SyntaxFlag, ExtensionSyntaxParser};
 use crate::syntax::expression::ExpressionParser;
 use crate::syntax::primative::PrimativeParser;
@@ -56,6 +56,7 @@ impl ExtensionSyntaxParser for FuncCallParser {
         let index_backup = parser.get_index();
         let parser_flags  = parser.flags.get();
         parser.cleanup_whitespaces();
+        let position = parser.current_position();
 
         if let Some(_) = parser.match_operator(&[KaramelOperatorType::LeftParentheses]) {
             let mut arguments = Vec::new();
@@ -68,7 +69,7 @@ impl ExtensionSyntaxParser for FuncCallParser {
             while continue_to_parse {
                 parser.cleanup_whitespaces();
                 
-                let param_expression = ExpressionParser::parse(parser);
+                let param_expression = FuncCallParser::parse_argument(parser);
                 match param_expression {
                     Err(_) => return param_expression,
                     _ => ()
@@ -91,25 +92,27 @@ impl ExtensionSyntaxParser for FuncCallParser {
 
                 match param_expression {
                     Ok(KaramelAstType::None) => (),
-                    Ok(data) => arguments.push(Rc::new(data)),
+                    Ok(data) => {
+                        FuncCallParser::validate_argument(&arguments, &data)?;
+                        arguments.push(Rc::new(data))
+                    },
                     _ => (),
                 };
             }
 
+            /* Call position is kept with the function name */
+            let func_name_expression = Rc::new(ast.clone());
+            parser.add_position(&func_name_expression, position);
+
             parser.flags.set(inner_parser_flags);
             return Ok(KaramelAstType::FuncCall {
-                func_name_expression: Rc::new(ast.clone()),
+                func_name_expression,
                 arguments,
-                assign_to_temp: Cell::new(parser.flags.get().contains(SyntaxFlag::IN_EXPRESSION)
-                                || parser.flags.get().contains(SyntaxFlag::IN_ASSIGNMENT)
-                                || parser.flags.get().contains(SyntaxFlag::IN_FUNCTION_ARG)
-                                || parser.flags.get().contains(SyntaxFlag::IN_RETURN))
+                assign_to_temp: Cell::new(is_value_used(parser))
             });
         }
         /* parse for 'object.method()' */
         else if let Some(_) = parser.match_operator(&[KaramelOperatorType::Dot]) {
-            
-
             let sub_ast = FuncCallParser::parse(parser)?;
 
             return match &sub_ast {
@@ -124,7 +127,7 @@ impl ExtensionSyntaxParser for FuncCallParser {
                             Ok(KaramelAstType::AccessorFuncCall {
                                 source: Rc::new(ast.clone()),
                                 indexer: Rc::new(sub_ast),
-                                assign_to_temp: Cell::new(true)
+                                assign_to_temp: Cell::new(is_value_used(parser))
                             })
                         },
                         _ => {
@@ -133,11 +136,64 @@ impl ExtensionSyntaxParser for FuncCallParser {
                         }
                     }
                 }
-                _ => Ok(sub_ast)
+
+                /* 'object.field' and 'object.field.method()' are parsed by unary parser */
+                _ => {
+                    parser.set_index(index_backup);
+                    Ok(KaramelAstType::None)
+                }
             };
         }
 
         parser.flags.set(parser_flags);
         return Ok(KaramelAstType::None);
     }
-}
\ No newline at end of file
+}
+
+impl FuncCallParser {
+    /* parse for 'name = value' or 'value' */
+    fn parse_argument(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        if let Ok(token) = parser.peek_token() {
+            if let KaramelTokenType::Symbol(name) = &token.token_type {
+                parser.consume_token();
+                parser.cleanup_whitespaces();
+
+                if let Some(_) = parser.match_operator(&[KaramelOperatorType::Assign]) {
+                    parser.cleanup_whitespaces();
+                    let expression = ExpressionParser::parse(parser)?;
+                    if expression == KaramelAstType::None {
+                        return Err(KaramelErrorType::InvalidExpression);
+                    }
+
+                    return Ok(KaramelAstType::NamedArgument {
+                        name: name.to_string(),
+                        expression: Rc::new(expression)
+                    });
+                }
+
+                parser.set_index(index_backup);
+            }
+        }
+
+        ExpressionParser::parse(parser)
+    }
+
+    fn validate_argument(arguments: &[Rc<KaramelAstType>], argument: &KaramelAstType) -> Result<(), KaramelErrorType> {
+        let mut named_arguments = arguments.iter().filter_map(|argument| match &**argument {
+            KaramelAstType::NamedArgument { name, expression: _ } => Some(name),
+            _ => None
+        });
+
+        match argument {
+            KaramelAstType::NamedArgument { name, expression: _ } => match named_arguments.any(|argument| argument == name) {
+                true => Err(KaramelErrorType::ArgumentAlreadyDefined(name.to_string())),
+                false => Ok(())
+            },
+            _ => match named_arguments.next() {
+                Some(_) => Err(KaramelErrorType::PositionalArgumentAfterNamed),
+                None => Ok(())
+            }
+        }
+    }
+}
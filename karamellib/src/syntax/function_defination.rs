@@ -1,9 +1,12 @@
 use crate::types::*;
 use crate::syntax::{SyntaxParser, SyntaxParserTrait, SyntaxFlag};
 use crate::syntax::primative::PrimativeParser;
-use crate::compiler::ast::{KaramelAstType};
+use crate::syntax::expression::ExpressionParser;
+use crate::syntax::util::{update_functions_for_temp_return, with_flag};
+use crate::compiler::ast::{KaramelAstType, KaramelFunctionArgument};
 use crate::syntax::block::{SingleLineBlockParser, MultiLineBlockParser};
 use crate::error::KaramelErrorType;
+use crate::constants::MAX_DEFAULT_ARGUMENT_INDEX;
 use std::rc::Rc;
 
 pub struct FunctionDefinationParser;
@@ -18,7 +21,6 @@ impl SyntaxParserTrait for FunctionDefinationParser {
 
             parser.cleanup_whitespaces();
 
-            let mut arguments = Vec::new();
             let name_expression = PrimativeParser::parse_symbol(parser)?;
             let function_name = match name_expression {
                 KaramelAstType::Symbol(text) => text,
@@ -30,30 +32,7 @@ impl SyntaxParserTrait for FunctionDefinationParser {
             parser.cleanup_whitespaces();
 
             /* Arguments */
-            if let Some(_) = parser.match_operator(&[KaramelOperatorType::LeftParentheses]) {
-                loop {
-                    parser.cleanup_whitespaces();
-
-                    if parser.check_operator(&KaramelOperatorType::RightParentheses) {
-                        break;
-                    }
-
-                    let argument = PrimativeParser::parse_symbol(parser)?;
-                    match argument {
-                        KaramelAstType::Symbol(text) => arguments.push(text),
-                        _ => return Err(KaramelErrorType::ArgumentMustBeText)
-                    };
-
-                    parser.cleanup_whitespaces();
-                    if let None = parser.match_operator(&[KaramelOperatorType::Comma]) {
-                        break;
-                    }
-                }
-
-                if let None = parser.match_operator(&[KaramelOperatorType::RightParentheses]) {
-                    return Err(KaramelErrorType::RightParanthesesMissing);
-                }
-            }
+            let arguments = FunctionDefinationParser::parse_arguments(parser)?;
 
             parser.cleanup_whitespaces();
             if let None = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
@@ -90,7 +69,7 @@ impl SyntaxParserTrait for FunctionDefinationParser {
                         KaramelAstType::Block(blocks)
                     },
                     _ => {
-                        KaramelAstType::Block([Rc::new(body), Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None)))].to_vec())
+                        KaramelAstType::Block([parser.create_body(body), Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None)))].to_vec())
                     }
                 }
             }
@@ -100,7 +79,7 @@ impl SyntaxParserTrait for FunctionDefinationParser {
 
             let function_defination_ast = KaramelAstType::FunctionDefination {
                 name: function_name,
-                body: Rc::new(body),
+                body: parser.create_body(body),
                 arguments: arguments
             };
 
@@ -112,3 +91,75 @@ impl SyntaxParserTrait for FunctionDefinationParser {
         return Ok(KaramelAstType::None);
     }
 }
+
+impl FunctionDefinationParser {
+    /* parse for '(a, b = 10, *others)' */
+    pub fn parse_arguments(parser: &SyntaxParser) -> Result<Vec<KaramelFunctionArgument>, KaramelErrorType> {
+        let mut arguments: Vec<KaramelFunctionArgument> = Vec::new();
+        if let Some(_) = parser.match_operator(&[KaramelOperatorType::LeftParentheses]) {
+            loop {
+                parser.cleanup_whitespaces();
+
+                if parser.check_operator(&KaramelOperatorType::RightParentheses) {
+                    break;
+                }
+
+                /* Variadic argument must be the last one */
+                if arguments.last().map_or(false, |argument| argument.variadic) {
+                    return Err(KaramelErrorType::VariadicArgumentMustBeLast);
+                }
+
+                let variadic = parser.match_operator(&[KaramelOperatorType::Multiplication]).is_some();
+                let argument = PrimativeParser::parse_symbol(parser)?;
+                let name = match argument {
+                    KaramelAstType::Symbol(text) => text,
+                    _ => return Err(KaramelErrorType::ArgumentMustBeText)
+                };
+
+                if arguments.iter().any(|argument| argument.name == name) {
+                    return Err(KaramelErrorType::ArgumentAlreadyDefined(name));
+                }
+
+                parser.cleanup_whitespaces();
+                let argument = match parser.match_operator(&[KaramelOperatorType::Assign]) {
+                    Some(_) if variadic => return Err(KaramelErrorType::VariadicArgumentMustBeLast),
+                    Some(_) => {
+                        parser.cleanup_whitespaces();
+                        let default = with_flag(SyntaxFlag::IN_EXPRESSION, parser, || ExpressionParser::parse(parser))?;
+                        if default == KaramelAstType::None {
+                            return Err(KaramelErrorType::InvalidExpression);
+                        }
+
+                        update_functions_for_temp_return(&default);
+                        KaramelFunctionArgument::with_default(name, Rc::new(default))
+                    },
+                    None if variadic => KaramelFunctionArgument::variadic(name),
+                    None => KaramelFunctionArgument::new(name)
+                };
+
+                if argument.default.is_some() && arguments.len() >= MAX_DEFAULT_ARGUMENT_INDEX {
+                    return Err(KaramelErrorType::FunctionDefinationNotValid);
+                }
+
+                /* Arguments after the default valued one need default value */
+                let has_default = arguments.iter().any(|argument| argument.default.is_some());
+                if has_default && argument.default.is_none() && !argument.variadic {
+                    return Err(KaramelErrorType::DefaultArgumentOrderNotValid);
+                }
+
+                arguments.push(argument);
+
+                parser.cleanup_whitespaces();
+                if let None = parser.match_operator(&[KaramelOperatorType::Comma]) {
+                    break;
+                }
+            }
+
+            if let None = parser.match_operator(&[KaramelOperatorType::RightParentheses]) {
+                return Err(KaramelErrorType::RightParanthesesMissing);
+            }
+        }
+
+        Ok(arguments)
+    }
+}
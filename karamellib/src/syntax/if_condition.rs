@@ -7,6 +7,7 @@ use crate::compiler::ast::{KaramelAstType, KaramelIfStatementElseItem};
 use crate::syntax::block::{SingleLineBlockParser, MultiLineBlockParser};
 use crate::error::KaramelErrorType;
 use crate::syntax::control::OrParser;
+use crate::syntax::util::update_functions_for_temp_return;
 
 pub struct IfConditiontParser;
 
@@ -20,6 +21,8 @@ impl SyntaxParserTrait for IfConditiontParser {
         parser.cleanup_whitespaces();
 
         if parser.match_keyword(KaramelKeywordType::If) {
+            /* Condition value is needed at stack */
+            update_functions_for_temp_return(&expression);
             parser.cleanup_whitespaces();
             if let None = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
                 return Err(KaramelErrorType::ColonMarkMissing);
@@ -38,6 +41,7 @@ impl SyntaxParserTrait for IfConditiontParser {
             if true_body == KaramelAstType::None {
                 return Err(KaramelErrorType::IfConditionBodyNotFound);
             }
+            let true_body = parser.create_body(true_body);
 
             parser.cleanup_whitespaces();
 
@@ -61,6 +65,7 @@ impl SyntaxParserTrait for IfConditiontParser {
                             if !parser.match_keyword(KaramelKeywordType::If) {
                                 return Err(KaramelErrorType::MissingIf);
                             }
+                            update_functions_for_temp_return(&else_condition);
                         }
                     };
                     
@@ -89,8 +94,8 @@ impl SyntaxParserTrait for IfConditiontParser {
                     parser.set_indentation(indentation);
 
                     match else_condition {
-                        KaramelAstType::None => else_body = Some(Rc::new(body)),
-                        _                  => else_if.push(Rc::new(KaramelIfStatementElseItem::new(Rc::new(else_condition), Rc::new(body))))
+                        KaramelAstType::None => else_body = Some(parser.create_body(body)),
+                        _                  => else_if.push(Rc::new(KaramelIfStatementElseItem::new(Rc::new(else_condition), parser.create_body(body))))
                     };
                 }
                 else {
@@ -104,7 +109,7 @@ impl SyntaxParserTrait for IfConditiontParser {
 
             let assignment_ast = KaramelAstType::IfStatement {
                 condition: Rc::new(expression),
-                body: Rc::new(true_body),
+                body: true_body,
                 else_body,
                 else_if: else_if.to_vec()
             };
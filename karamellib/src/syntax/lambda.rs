@@ -0,0 +1,50 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait, SyntaxFlag};
+use crate::syntax::function_defination::FunctionDefinationParser;
+use crate::syntax::expression::ExpressionParser;
+use crate::compiler::ast::KaramelAstType;
+use crate::error::KaramelErrorType;
+
+use super::util::with_flag;
+
+/* Function without name that could be used as a value. Example: fonk(a, b): a + b */
+pub struct LambdaParser;
+
+impl SyntaxParserTrait for LambdaParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.cleanup_whitespaces();
+
+        if !parser.match_keyword(KaramelKeywordType::Fn) {
+            parser.set_index(index_backup);
+            return Ok(KaramelAstType::None);
+        }
+
+        parser.cleanup_whitespaces();
+        if !parser.check_operator(&KaramelOperatorType::LeftParentheses) {
+            return Err(KaramelErrorType::FunctionDefinationNotValid);
+        }
+
+        let arguments = FunctionDefinationParser::parse_arguments(parser)?;
+
+        parser.cleanup_whitespaces();
+        if let None = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
+            return Err(KaramelErrorType::ColonMarkMissing);
+        }
+
+        parser.cleanup_whitespaces();
+        let body = with_flag(SyntaxFlag::IN_EXPRESSION, parser, || ExpressionParser::parse(parser))?;
+        if let KaramelAstType::None = body {
+            return Err(KaramelErrorType::FunctionConditionBodyNotFound);
+        }
+
+        Ok(KaramelAstType::Lambda {
+            arguments,
+            body: Rc::new(KaramelAstType::Return(Rc::new(body))),
+            storage_index: Cell::new(0)
+        })
+    }
+}
@@ -8,6 +8,7 @@ use crate::syntax::expression::ExpressionParser;
 use crate::error::KaramelErrorType;
 
 use super::assignment::AssignmentParser;
+use super::primative::PrimativeParser;
 use super::util::{map_parser_with_flag, with_flag};
 #[derive(Debug)]
 #[derive(Clone)]
@@ -19,6 +20,10 @@ pub enum LoopType {
         control: Rc<KaramelAstType>,
         increment: Rc<KaramelAstType>
     },
+    Iterate {
+        variables: Vec<String>,
+        source: Rc<KaramelAstType>
+    },
     Endless
 }
 
@@ -39,6 +44,16 @@ impl SyntaxParserTrait for WhileLoopParser {
                 /* AssignmentParser has indentation check so we need to move indentation forward */
                 parser.cleanup_whitespaces();
 
+                /*
+                Iteration loop
+                Example:
+                    döngü kayıt içinde liste:
+                    döngü anahtar, değer içinde sözlük:
+                */
+                if let Some(loop_type) = WhileLoopParser::parse_iterate(parser)? {
+                    return WhileLoopParser::parse_body(parser, loop_type, indentation);
+                }
+
                 /*
 
                 We need to detect while loop type. We are checking body loop control section to understand while loop type.
@@ -106,6 +121,54 @@ impl SyntaxParserTrait for WhileLoopParser {
             }
         };
 
+        WhileLoopParser::parse_body(parser, loop_type, indentation)
+    }
+}
+
+impl WhileLoopParser {
+    fn parse_iterate(parser: &SyntaxParser) -> Result<Option<LoopType>, KaramelErrorType> {
+        let index_backup = parser.get_index();
+        let mut variables = Vec::new();
+
+        loop {
+            match PrimativeParser::parse_symbol(parser)? {
+                KaramelAstType::Symbol(variable) => variables.push(variable),
+                _ => {
+                    parser.set_index(index_backup);
+                    return Ok(None);
+                }
+            };
+
+            parser.cleanup_whitespaces();
+            if parser.match_operator(&[KaramelOperatorType::Comma]).is_none() {
+                break;
+            }
+            parser.cleanup_whitespaces();
+        }
+
+        /* It is not an iteration loop, other loop parsers should try it */
+        if !parser.match_keyword(KaramelKeywordType::In) {
+            parser.set_index(index_backup);
+            return Ok(None);
+        }
+
+        if variables.len() > 2 {
+            return Err(KaramelErrorType::LoopVariableNotValid);
+        }
+
+        parser.cleanup_whitespaces();
+        let source = with_flag(SyntaxFlag::IN_EXPRESSION, parser, || ExpressionParser::parse(parser))?;
+        if let KaramelAstType::None = source {
+            return Err(KaramelErrorType::InvalidExpression);
+        }
+
+        Ok(Some(LoopType::Iterate {
+            variables,
+            source: Rc::new(source)
+        }))
+    }
+
+    fn parse_body(parser: &SyntaxParser, loop_type: LoopType, indentation: usize) -> AstResult {
         parser.cleanup_whitespaces();
         if let None = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
             return Err(KaramelErrorType::ColonMarkMissing);
@@ -129,7 +192,7 @@ impl SyntaxParserTrait for WhileLoopParser {
 
         return Ok(KaramelAstType::Loop {
             loop_type: loop_type,
-            body: Rc::new(body)
+            body: parser.create_body(body)
         });
     }
 }
@@ -141,7 +204,7 @@ mod tests {
     use crate::parser::*;
     use crate::syntax::*;
     use crate::compiler::value::KaramelPrimative;
-    use crate::compiler::ast::KaramelAstType;
+    use crate::compiler::ast::{KaramelAstType, KaramelDictItem};
     use crate::syntax::loops::*;
     use std::cell::Cell;
     use std::rc::Rc;
@@ -265,4 +328,31 @@ doğru
 test_compare!(scalar_5, r#"döngü i = 1, i < 1,
 doğru
 "#, Err(KaramelError::new(0, 19, KaramelErrorType::ColonMarkMissing)));
+test_compare!(iterate_1, r#"döngü a içinde liste:
+    doğru
+"#, Ok(Rc::new(KaramelAstType::Loop {
+        loop_type: LoopType::Iterate {
+            variables: ["a".to_string()].to_vec(),
+            source: Rc::new(KaramelAstType::Symbol("liste".to_string()))
+        },
+        body: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Bool(true))))
+    } )));
+test_compare!(iterate_2, r#"döngü anahtar, değer icinde {'a': 1}:
+    doğru
+"#, Ok(Rc::new(KaramelAstType::Loop {
+        loop_type: LoopType::Iterate {
+            variables: ["anahtar".to_string(), "değer".to_string()].to_vec(),
+            source: Rc::new(KaramelAstType::Dict([Rc::new(KaramelDictItem {
+                key: Rc::new(KaramelPrimative::Text(Rc::new("a".to_string()))),
+                value: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0))))
+            })].to_vec()))
+        },
+        body: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Bool(true))))
+    } )));
+test_compare!(iterate_3, r#"döngü a, b, c içinde liste:
+doğru
+"#, Err(KaramelError::new(0, 20, KaramelErrorType::LoopVariableNotValid)));
+test_compare!(iterate_4, r#"döngü a içinde:
+doğru
+"#, Err(KaramelError::new(0, 15, KaramelErrorType::InvalidExpression)));
 }
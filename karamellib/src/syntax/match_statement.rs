@@ -0,0 +1,224 @@
+use std::rc::Rc;
+
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait};
+use crate::compiler::ast::{KaramelAstType, KaramelMatchCase, KaramelPattern};
+use crate::compiler::value::KaramelPrimative;
+use crate::syntax::block::{SingleLineBlockParser, MultiLineBlockParser};
+use crate::syntax::expression::ExpressionParser;
+use crate::syntax::primative::PrimativeParser;
+use crate::syntax::util::update_functions_for_temp_return;
+use crate::error::KaramelErrorType;
+
+pub struct MatchParser;
+
+impl SyntaxParserTrait for MatchParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.indentation_check()?;
+
+        let indentation = parser.get_indentation();
+        if !parser.match_keyword(KaramelKeywordType::Match) {
+            parser.set_index(index_backup);
+            return Ok(KaramelAstType::None);
+        }
+
+        parser.cleanup_whitespaces();
+        let expression = ExpressionParser::parse(parser)?;
+        if expression == KaramelAstType::None {
+            return Err(KaramelErrorType::InvalidExpression);
+        }
+
+        /* Value is needed at stack */
+        update_functions_for_temp_return(&expression);
+
+        parser.cleanup_whitespaces();
+        if let None = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
+            return Err(KaramelErrorType::ColonMarkMissing);
+        }
+
+        /* Cases must be defined at new lines */
+        parser.cleanup_whitespaces();
+        if !parser.get_newline().0 {
+            return Err(KaramelErrorType::MatchCaseNotFound);
+        }
+
+        parser.in_indication()?;
+        let case_indentation = parser.get_indentation();
+        if case_indentation <= indentation {
+            return Err(KaramelErrorType::MatchCaseNotFound);
+        }
+
+        let mut cases = Vec::new();
+        loop {
+            cases.push(Rc::new(MatchParser::parse_case(parser, case_indentation)?));
+
+            if !parser.is_same_indentation(case_indentation) {
+                break;
+            }
+        }
+
+        parser.set_indentation(indentation);
+        Ok(KaramelAstType::Match {
+            expression: Rc::new(expression),
+            cases
+        })
+    }
+}
+
+impl MatchParser {
+    /* parse for 'pattern eğer guard: body' */
+    fn parse_case(parser: &SyntaxParser, indentation: usize) -> Result<KaramelMatchCase, KaramelErrorType> {
+        let pattern = MatchParser::parse_pattern(parser)?;
+
+        parser.cleanup_whitespaces();
+        let guard = match parser.match_keyword(KaramelKeywordType::When) {
+            true => {
+                parser.cleanup_whitespaces();
+                let guard = ExpressionParser::parse(parser)?;
+                if guard == KaramelAstType::None {
+                    return Err(KaramelErrorType::InvalidExpression);
+                }
+
+                /* Guard value is needed at stack */
+                update_functions_for_temp_return(&guard);
+                Some(Rc::new(guard))
+            },
+            false => None
+        };
+
+        parser.cleanup_whitespaces();
+        if let None = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
+            return Err(KaramelErrorType::ColonMarkMissing);
+        }
+
+        parser.cleanup_whitespaces();
+        let body = match parser.get_newline() {
+            (true, _) => {
+                parser.in_indication()?;
+                MultiLineBlockParser::parse(parser)
+            },
+            (false, _) => SingleLineBlockParser::parse(parser)
+        }?;
+
+        parser.set_indentation(indentation);
+        if body == KaramelAstType::None {
+            return Err(KaramelErrorType::InvalidExpression);
+        }
+
+        Ok(KaramelMatchCase {
+            pattern: Rc::new(pattern),
+            guard,
+            body: parser.create_body(body)
+        })
+    }
+
+    fn parse_pattern(parser: &SyntaxParser) -> Result<KaramelPattern, KaramelErrorType> {
+        parser.cleanup_whitespaces();
+
+        if parser.match_operator(&[KaramelOperatorType::SquareBracketStart]).is_some() {
+            return MatchParser::parse_list_pattern(parser);
+        }
+
+        if parser.match_operator(&[KaramelOperatorType::CurveBracketStart]).is_some() {
+            return MatchParser::parse_dict_pattern(parser);
+        }
+
+        if let KaramelAstType::Symbol(name) = PrimativeParser::parse_symbol(parser)? {
+            return Ok(match &name[..] {
+                "_" => KaramelPattern::Wildcard,
+                _ => KaramelPattern::Variable(name)
+            });
+        }
+
+        /* Negative numbers */
+        let negative = parser.match_operator(&[KaramelOperatorType::Subtraction]).is_some();
+        match (PrimativeParser::parse_basic_primatives(parser)?, negative) {
+            (KaramelAstType::Primative(primative), false) => Ok(KaramelPattern::Literal(Rc::new(KaramelAstType::Primative(primative)))),
+            (KaramelAstType::Primative(primative), true) => match &*primative {
+                KaramelPrimative::Number(number) => Ok(KaramelPattern::Literal(Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(-number)))))),
+                _ => Err(KaramelErrorType::PatternNotValid)
+            },
+            _ => Err(KaramelErrorType::PatternNotValid)
+        }
+    }
+
+    /* parse for '[first, second, *others]' */
+    fn parse_list_pattern(parser: &SyntaxParser) -> Result<KaramelPattern, KaramelErrorType> {
+        let mut items = Vec::new();
+        let mut rest = None;
+
+        loop {
+            parser.cleanup_whitespaces();
+            if parser.check_operator(&KaramelOperatorType::SquareBracketEnd) {
+                break;
+            }
+
+            /* Rest of the items must be the last one */
+            if rest.is_some() {
+                return Err(KaramelErrorType::PatternNotValid);
+            }
+
+            if parser.match_operator(&[KaramelOperatorType::Multiplication]).is_some() {
+                match PrimativeParser::parse_symbol(parser)? {
+                    KaramelAstType::Symbol(name) => rest = Some(name),
+                    _ => return Err(KaramelErrorType::PatternNotValid)
+                };
+            }
+            else {
+                items.push(Rc::new(MatchParser::parse_pattern(parser)?));
+            }
+
+            parser.cleanup_whitespaces();
+            if parser.match_operator(&[KaramelOperatorType::Comma]).is_none() {
+                break;
+            }
+        }
+
+        parser.cleanup_whitespaces();
+        if parser.match_operator(&[KaramelOperatorType::SquareBracketEnd]).is_none() {
+            return Err(KaramelErrorType::ArrayNotClosed);
+        }
+
+        Ok(KaramelPattern::List { items, rest })
+    }
+
+    /* parse for '{'key': pattern}' */
+    fn parse_dict_pattern(parser: &SyntaxParser) -> Result<KaramelPattern, KaramelErrorType> {
+        let mut items = Vec::new();
+
+        loop {
+            parser.cleanup_whitespaces();
+            if parser.check_operator(&KaramelOperatorType::CurveBracketEnd) {
+                break;
+            }
+
+            let key = match PrimativeParser::parse_basic_primatives(parser)? {
+                KaramelAstType::Primative(primative) => match &*primative {
+                    KaramelPrimative::Text(_) => primative.clone(),
+                    _ => return Err(KaramelErrorType::DictionaryKeyNotValid)
+                },
+                _ => return Err(KaramelErrorType::DictionaryKeyNotValid)
+            };
+
+            parser.cleanup_whitespaces();
+            if parser.match_operator(&[KaramelOperatorType::ColonMark]).is_none() {
+                return Err(KaramelErrorType::ColonMarkMissing);
+            }
+
+            items.push((key, Rc::new(MatchParser::parse_pattern(parser)?)));
+
+            parser.cleanup_whitespaces();
+            if parser.match_operator(&[KaramelOperatorType::Comma]).is_none() {
+                break;
+            }
+        }
+
+        parser.cleanup_whitespaces();
+        if parser.match_operator(&[KaramelOperatorType::CurveBracketEnd]).is_none() {
+            return Err(KaramelErrorType::DictNotClosed);
+        }
+
+        Ok(KaramelPattern::Dict(items))
+    }
+}
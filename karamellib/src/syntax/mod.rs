@@ -10,20 +10,25 @@ pub mod newline;
 pub mod if_condition;
 pub mod statement;
 pub mod function_defination;
+pub mod lambda;
+pub mod class_defination;
 pub mod function_return;
 pub mod loops;
 pub mod loop_item;
+pub mod try_catch;
+pub mod match_statement;
 pub mod expression;
 pub mod load_module;
 
 use std::borrow::Borrow;
 use std::rc::Rc;
 use std::vec::Vec;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 
 use crate::types::*;
 use self::block::MultiLineBlockParser;
 use crate::compiler::ast::KaramelAstType;
+use crate::compiler::source_map::{SourcePosition, StatementPositions};
 use crate::error::*;
 
 use bitflags::bitflags;
@@ -34,7 +39,11 @@ pub struct SyntaxParser {
     pub tokens: Vec<Token>,
     pub index: Cell<usize>,
     pub indentation: Cell<usize>,
-    pub flags: Cell<SyntaxFlag>
+    pub flags: Cell<SyntaxFlag>,
+    pub positions: RefCell<StatementPositions>,
+
+    /* Block with single statement returns the statement itself, position is saved while creating its reference */
+    pub single_statement_position: Cell<Option<SourcePosition>>
 }
 
 bitflags! {
@@ -65,11 +74,41 @@ impl SyntaxParser {
             tokens,
             index: Cell::new(0),
             indentation: Cell::new(0),
-            flags: Cell::new(SyntaxFlag::NONE)
+            flags: Cell::new(SyntaxFlag::NONE),
+            positions: RefCell::new(StatementPositions::new()),
+            single_statement_position: Cell::new(None)
+        }
+    }
+
+    /// Position of the next token that is not whitespace or new line
+    pub fn current_position(&self) -> SourcePosition {
+        let token = self.tokens.iter().skip(self.index.get()).find(|token| !matches!(token.token_type, KaramelTokenType::NewLine(_) | KaramelTokenType::WhiteSpace(_)));
+        match token {
+            Some(token) => SourcePosition { line: token.line, column: token.start },
+            None => SourcePosition::default()
         }
     }
 
+    pub fn add_position(&self, ast: &Rc<KaramelAstType>, position: SourcePosition) {
+        self.positions.borrow_mut().push((ast.clone(), position));
+    }
+
+    /// Create reference for the body that parsed by the block parsers. Must be called just after the block is parsed.
+    pub fn create_body(&self, body: KaramelAstType) -> Rc<KaramelAstType> {
+        let body = Rc::new(body);
+        if let Some(position) = self.single_statement_position.take() {
+            self.add_position(&body, position);
+        }
+        body
+    }
+
+    /// Statement positions of the parsed code
+    pub fn positions(&self) -> StatementPositions {
+        self.positions.borrow().clone()
+    }
+
     pub fn parse(&self) -> Result<Rc<KaramelAstType>, KaramelError> {
+        let position = self.current_position();
         return match MultiLineBlockParser::parse(&self) {
             Ok(ast) => {
                 self.cleanup();
@@ -82,7 +121,10 @@ impl SyntaxParser {
                         column: token.start
                     });
                 }
-                Ok(Rc::new(ast))
+                /* Single statement code is not a block */
+                let ast = Rc::new(ast);
+                self.add_position(&ast, position);
+                Ok(ast)
             },
             Err(error) => {
                 if let Ok(token) = self.valid_token() {
@@ -2,7 +2,7 @@ use std::rc::Rc;
 
 use crate::types::*;
 use crate::syntax::util::*;
-use crate::syntax::{SyntaxParser, SyntaxParserTrait};
+use crate::syntax::{SyntaxParser, SyntaxParserTrait, SyntaxFlag};
 use crate::syntax::expression::ExpressionParser;
 use crate::compiler::value::KaramelPrimative;
 use crate::compiler::ast::{KaramelAstType, KaramelDictItem};
@@ -24,6 +24,7 @@ impl PrimativeParser {
             KaramelTokenType::Integer(int)      => Ok(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(*int as f64)))),
             KaramelTokenType::Double(double)    => Ok(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(*double)))),
             KaramelTokenType::Text(text)        => Ok(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::clone(text))))),
+            KaramelTokenType::InterpolatedText(parts) => PrimativeParser::parse_interpolation(parts),
             KaramelTokenType::Keyword(keyword)  => {
                 match keyword {
                     KaramelKeywordType::True  => Ok(KaramelAstType::Primative(Rc::new(KaramelPrimative::Bool(true)))),
@@ -48,6 +49,33 @@ impl PrimativeParser {
         }
     }
 
+    fn parse_interpolation(parts: &[KaramelTextPart]) -> AstResult {
+        let mut items = Vec::with_capacity(parts.len());
+
+        for part in parts {
+            let item = match part {
+                KaramelTextPart::Text(text) => KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::clone(text)))),
+                KaramelTextPart::Expression(tokens) => {
+                    let parser = SyntaxParser::new(tokens.to_vec());
+                    let ast = with_flag(SyntaxFlag::IN_EXPRESSION, &parser, || ExpressionParser::parse(&parser))?;
+                    parser.cleanup_whitespaces();
+
+                    /* Only one expression could be used */
+                    if ast == KaramelAstType::None || parser.peek_token().is_ok() {
+                        return Err(KaramelErrorType::InterpolationNotValid);
+                    }
+
+                    /* Expression value is needed at stack */
+                    update_functions_for_temp_return(&ast);
+                    ast
+                }
+            };
+            items.push(Rc::new(item));
+        }
+
+        Ok(KaramelAstType::Interpolation(items))
+    }
+
     pub fn parse_list(parser: &SyntaxParser) -> AstResult {
         let index_backup = parser.get_index();
         if parser.match_operator(&[KaramelOperatorType::SquareBracketStart]).is_some() {
@@ -7,11 +7,13 @@ use crate::syntax::load_module::LoadModuleParser;
 use crate::syntax::function_return::FunctionReturnParser;
 use crate::syntax::loop_item::LoopItemParser;
 use crate::syntax::loops::WhileLoopParser;
+use crate::syntax::try_catch::{TryCatchParser, ThrowParser};
+use crate::syntax::match_statement::MatchParser;
 
 pub struct StatementParser;
 
 impl SyntaxParserTrait for StatementParser {
     fn parse(parser: &SyntaxParser) -> AstResult {
-        return map_parser(parser, &[LoadModuleParser::parse, LoopItemParser::parse, WhileLoopParser::parse, FunctionReturnParser::parse, AssignmentParser::parse, IfConditiontParser::parse]);
+        return map_parser(parser, &[LoadModuleParser::parse, LoopItemParser::parse, WhileLoopParser::parse, TryCatchParser::parse, ThrowParser::parse, MatchParser::parse, FunctionReturnParser::parse, AssignmentParser::parse, IfConditiontParser::parse]);
     }
 }
@@ -0,0 +1,98 @@
+use std::rc::Rc;
+
+use crate::types::*;
+use crate::syntax::{SyntaxParser, SyntaxParserTrait};
+use crate::compiler::ast::KaramelAstType;
+use crate::syntax::block::{SingleLineBlockParser, MultiLineBlockParser};
+use crate::syntax::expression::ExpressionParser;
+use crate::syntax::util::update_functions_for_temp_return;
+use crate::error::KaramelErrorType;
+
+use super::primative::PrimativeParser;
+
+pub struct TryCatchParser;
+pub struct ThrowParser;
+
+impl SyntaxParserTrait for TryCatchParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.indentation_check()?;
+
+        let indentation = parser.get_indentation();
+        if !parser.match_keyword(KaramelKeywordType::Try) {
+            parser.set_index(index_backup);
+            return Ok(KaramelAstType::None);
+        }
+
+        let body = TryCatchParser::parse_body(parser, indentation)?;
+
+        if !parser.is_same_indentation(indentation) || !parser.match_keyword(KaramelKeywordType::Catch) {
+            return Err(KaramelErrorType::CatchBlockNotFound);
+        }
+
+        /* Error information could be assigned to variable */
+        parser.cleanup_whitespaces();
+        let variable = match parser.check_operator(&KaramelOperatorType::ColonMark) {
+            true => None,
+            false => match PrimativeParser::parse_symbol(parser)? {
+                KaramelAstType::Symbol(variable) => Some(variable),
+                _ => return Err(KaramelErrorType::CatchVariableNotValid)
+            }
+        };
+
+        let catch_body = TryCatchParser::parse_body(parser, indentation)?;
+        return Ok(KaramelAstType::TryCatch {
+            body,
+            variable,
+            catch_body
+        });
+    }
+}
+
+impl TryCatchParser {
+    fn parse_body(parser: &SyntaxParser, indentation: usize) -> Result<Rc<KaramelAstType>, KaramelErrorType> {
+        parser.cleanup_whitespaces();
+        if let None = parser.match_operator(&[KaramelOperatorType::ColonMark]) {
+            return Err(KaramelErrorType::ColonMarkMissing);
+        }
+
+        parser.cleanup_whitespaces();
+        let body = match parser.get_newline() {
+            (true, _) => {
+                parser.in_indication()?;
+                MultiLineBlockParser::parse(parser)
+            },
+            (false, _) => SingleLineBlockParser::parse(parser)
+        }?;
+
+        parser.set_indentation(indentation);
+        if body == KaramelAstType::None {
+            return Err(KaramelErrorType::InvalidExpression);
+        }
+
+        Ok(parser.create_body(body))
+    }
+}
+
+impl SyntaxParserTrait for ThrowParser {
+    fn parse(parser: &SyntaxParser) -> AstResult {
+        let index_backup = parser.get_index();
+        parser.cleanup_whitespaces();
+
+        if parser.match_keyword(KaramelKeywordType::Throw) {
+            parser.cleanup_whitespaces();
+
+            let ast = ExpressionParser::parse(parser)?;
+            if ast == KaramelAstType::None {
+                return Err(KaramelErrorType::ThrowValueNotFound);
+            }
+
+            /* Error value is needed at stack */
+            update_functions_for_temp_return(&ast);
+            return Ok(KaramelAstType::Throw(Rc::new(ast)));
+        }
+
+        parser.set_index(index_backup);
+        return Ok(KaramelAstType::None);
+    }
+}
@@ -1,14 +1,14 @@
 use crate::types::*;
-use crate::syntax::{SyntaxParser, SyntaxParserTrait};
+use crate::syntax::{SyntaxParser, SyntaxParserTrait, ExtensionSyntaxParser};
 use crate::syntax::util::map_parser;
 use crate::syntax::primative::PrimativeParser;
 use crate::syntax::func_call::FuncCallParser;
-use crate::syntax::util::is_ast_empty;
+use crate::syntax::lambda::LambdaParser;
+use crate::syntax::util::{is_value_used, update_functions_for_temp_return};
 use crate::compiler::ast::KaramelAstType;
 use crate::compiler::value::KaramelPrimative;
 use crate::syntax::expression::ExpressionParser;
 use crate::error::KaramelErrorType;
-use crate::syntax::SyntaxFlag;
 
 use std::cell::Cell;
 use std::rc::Rc;
@@ -17,24 +17,53 @@ pub struct UnaryParser;
 
 impl SyntaxParserTrait for UnaryParser {
     fn parse(parser: &SyntaxParser) -> AstResult {
-        let ast = map_parser(parser, &[Self::parse_prefix_unary, Self::parse_suffix_unary, FuncCallParser::parse, PrimativeParser::parse])?;
-        
-        let index_backup = parser.get_index();
-        parser.cleanup_whitespaces();
-        
-        if parser.match_operator(&[KaramelOperatorType::SquareBracketStart]).is_some() {
-            parser.cleanup_whitespaces();
+        let mut ast = map_parser(parser, &[Self::parse_prefix_unary, Self::parse_suffix_unary, FuncCallParser::parse, LambdaParser::parse, PrimativeParser::parse])?;
+        if let KaramelAstType::None = ast {
+            return Ok(ast);
+        }
 
-            let indexer_ast = ExpressionParser::parse(parser)?;
+        loop {
+            let index_backup = parser.get_index();
             parser.cleanup_whitespaces();
+            let position = parser.current_position();
+
+            /* parse for 'object[index]' */
+            if parser.match_operator(&[KaramelOperatorType::SquareBracketStart]).is_some() {
+                /* Indexer position is kept with the indexed object */
+                let body = Rc::new(ast.clone());
+                parser.add_position(&body, position);
+                let indexer_ast = Self::parse_indexer_body(body, parser)?;
+
+                if parser.match_operator(&[KaramelOperatorType::SquareBracketEnd]).is_some() {
+                    ast = indexer_ast;
+                    continue;
+                }
+            }
+
+            /* parse for 'object.field' and 'object.field.method()' */
+            else if parser.match_operator(&[KaramelOperatorType::Dot]).is_some() {
+                if let KaramelAstType::Symbol(symbol) = PrimativeParser::parse_symbol(parser)? {
+                    ast = match FuncCallParser::parsable(parser) {
+                        true => {
+                            update_functions_for_temp_return(&ast);
+                            let mut function_name = KaramelAstType::Symbol(symbol);
+                            KaramelAstType::AccessorFuncCall {
+                                source: Rc::new(ast),
+                                indexer: Rc::new(FuncCallParser::parse_suffix(&mut function_name, parser)?),
+                                assign_to_temp: Cell::new(is_value_used(parser))
+                            }
+                        },
 
-            if parser.match_operator(&[KaramelOperatorType::SquareBracketEnd]).is_some() {
-                return Ok(KaramelAstType::Indexer { body: Rc::new(ast), indexer: Rc::new(indexer_ast) });   
+                        /* Convert symbol to text */
+                        false => KaramelAstType::Indexer { body: Rc::new(ast), indexer: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new(symbol))))) }
+                    };
+                    continue;
+                }
             }
-        }
 
-        parser.set_index(index_backup);
-        return Ok(ast);
+            parser.set_index(index_backup);
+            return Ok(ast);
+        }
     }
 }
 
@@ -63,14 +92,19 @@ impl UnaryParser {
 
     pub fn parse_indexer(ast: Rc<KaramelAstType>, parser: &SyntaxParser) -> AstResult {
         let index_backup = parser.get_index();
+        let position = parser.current_position();
         if parser.match_operator(&[KaramelOperatorType::SquareBracketStart]).is_some() {
-            parser.cleanup_whitespaces();
+            parser.add_position(&ast, position);
+            let indexer_ast = Self::parse_indexer_body(ast, parser);
 
-            let indexer_ast = ExpressionParser::parse(parser);
-            parser.cleanup_whitespaces();
+            let is_valid = match &indexer_ast {
+                Ok(KaramelAstType::Indexer { body: _, indexer }) => **indexer != KaramelAstType::None,
+                Ok(_) => true,
+                Err(_) => false
+            };
 
-            if parser.match_operator(&[KaramelOperatorType::SquareBracketEnd]).is_some() && !is_ast_empty(&indexer_ast) {
-                return Ok(KaramelAstType::Indexer { body: ast, indexer: Rc::new(indexer_ast.unwrap()) });   
+            if parser.match_operator(&[KaramelOperatorType::SquareBracketEnd]).is_some() && is_valid {
+                return indexer_ast;
             }
         }
 
@@ -78,6 +112,38 @@ impl UnaryParser {
         return Ok(KaramelAstType::None);
     }
 
+    /* Parse 'index' or 'start:end:step' part of the indexer */
+    fn parse_indexer_body(body: Rc<KaramelAstType>, parser: &SyntaxParser) -> AstResult {
+        parser.cleanup_whitespaces();
+        let start = ExpressionParser::parse(parser)?;
+        parser.cleanup_whitespaces();
+
+        if parser.match_operator(&[KaramelOperatorType::ColonMark]).is_none() {
+            return Ok(KaramelAstType::Indexer { body, indexer: Rc::new(start) });
+        }
+
+        parser.cleanup_whitespaces();
+        let end = ExpressionParser::parse(parser)?;
+        parser.cleanup_whitespaces();
+
+        let step = match parser.match_operator(&[KaramelOperatorType::ColonMark]) {
+            Some(_) => {
+                parser.cleanup_whitespaces();
+                let step = ExpressionParser::parse(parser)?;
+                parser.cleanup_whitespaces();
+                step
+            },
+            None => KaramelAstType::None
+        };
+
+        Ok(KaramelAstType::Slice {
+            body,
+            start: Rc::new(start),
+            end: Rc::new(end),
+            step: Rc::new(step)
+        })
+    }
+
     fn parse_prefix_unary(parser: &SyntaxParser) -> AstResult {
         let index_backup = parser.get_index();
 
@@ -147,10 +213,7 @@ impl UnaryParser {
                 _ => Ok(KaramelAstType::PrefixUnary { 
                     operator, 
                     expression: Rc::new(unary_ast), 
-                    assign_to_temp: Cell::new(parser.flags.get().contains(SyntaxFlag::IN_EXPRESSION)
-                        || parser.flags.get().contains(SyntaxFlag::IN_ASSIGNMENT)
-                        || parser.flags.get().contains(SyntaxFlag::IN_FUNCTION_ARG)
-                        || parser.flags.get().contains(SyntaxFlag::IN_RETURN))
+                    assign_to_temp: Cell::new(is_value_used(parser))
                 })
             };
         }
@@ -47,6 +47,11 @@ pub fn err_or_message(ast: AstResult, none_error: KaramelErrorType) -> AstResult
     }
 }
 
+/* Calls at the statement level do not leave their results at the stack */
+pub fn is_value_used(parser: &SyntaxParser) -> bool {
+    parser.flags.get().intersects(SyntaxFlag::IN_EXPRESSION | SyntaxFlag::IN_ASSIGNMENT | SyntaxFlag::IN_FUNCTION_ARG | SyntaxFlag::IN_RETURN)
+}
+
 pub fn update_functions_for_temp_return(ast: &KaramelAstType) {
     match ast {
         KaramelAstType::FuncCall { func_name_expression: _, arguments: _, assign_to_temp } => {
@@ -57,7 +57,14 @@ pub enum KaramelKeywordType {
     Break,
     Continue,
     While,
-    Load
+    Load,
+    Class,
+    In,
+    Try,
+    Catch,
+    Throw,
+    Match,
+    When
 }
 
 impl KaramelKeywordType {
@@ -101,7 +108,19 @@ pub static KEYWORDS: &[(&str, KaramelKeywordType)] = &[
     ("döngü",         KaramelKeywordType::While),
     ("dongu",         KaramelKeywordType::While),
     ("yükle",          KaramelKeywordType::Load),
-    ("yukle",          KaramelKeywordType::Load)
+    ("yukle",          KaramelKeywordType::Load),
+    ("sınıf",          KaramelKeywordType::Class),
+    ("sinif",          KaramelKeywordType::Class),
+    ("içinde",         KaramelKeywordType::In),
+    ("icinde",         KaramelKeywordType::In),
+    ("dene",           KaramelKeywordType::Try),
+    ("yakala",         KaramelKeywordType::Catch),
+    ("hata_fırlat",    KaramelKeywordType::Throw),
+    ("hata_firlat",    KaramelKeywordType::Throw),
+    ("eşleştir",       KaramelKeywordType::Match),
+    ("eslestir",       KaramelKeywordType::Match),
+    ("eğer",           KaramelKeywordType::When),
+    ("eger",           KaramelKeywordType::When)
 ];
 
 #[derive(Clone, Copy)]
@@ -162,11 +181,21 @@ pub enum KaramelTokenType {
     Symbol(Rc<String>),
     Operator(KaramelOperatorType),
     Text(Rc<String>),
+    InterpolatedText(Vec<KaramelTextPart>),
     Keyword(KaramelKeywordType),
     WhiteSpace(u8),
     NewLine(u8)
 }
 
+/* Interpolated text is splitted into raw text and tokenized expressions */
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq)]
+pub enum KaramelTextPart {
+    Text(Rc<String>),
+    Expression(Vec<Token>)
+}
+
 #[repr(C)]
 #[derive(Clone)]
 #[derive(Debug)]
@@ -178,7 +207,7 @@ pub enum KaramelNumberSystem {
     Hexadecimal = 3
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Token {
     pub line      : u32,
     pub start    : u32,
@@ -219,6 +248,13 @@ impl Tokinizer<'_> {
         };
     }
 
+    pub fn get_third_char(&mut self) -> char {
+        return match self.iter_third.peek() {
+            Some(&c) => c,
+            None => '\0'
+        };
+    }
+
     pub fn add_token(&mut self, start: u32, token_type: KaramelTokenType) {
         let token = Token {
             line: self.line,
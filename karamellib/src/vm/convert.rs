@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::compiler::{GetType, KaramelPrimative};
+use crate::compiler::value::EMPTY_OBJECT;
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+
+/// Rust value that could be used by the Karamel code
+pub trait IntoKaramel {
+    fn into_karamel(self) -> VmObject;
+}
+
+/// Rust value that could be created from a Karamel value
+pub trait FromKaramel: Sized {
+    fn from_karamel(value: VmObject) -> Result<Self, KaramelErrorType>;
+}
+
+/// Arguments of a function call that made from Rust
+pub trait IntoKaramelArguments {
+    fn into_arguments(self) -> Vec<VmObject>;
+}
+
+fn conversion_error<T>(expected: &str, value: &KaramelPrimative) -> Result<T, KaramelErrorType> {
+    Err(KaramelErrorType::ValueConversionFailed {
+        expected: expected.to_string(),
+        found: value.get_type()
+    })
+}
+
+impl IntoKaramel for VmObject {
+    fn into_karamel(self) -> VmObject { self }
+}
+
+impl IntoKaramel for () {
+    fn into_karamel(self) -> VmObject { EMPTY_OBJECT }
+}
+
+impl IntoKaramel for f64 {
+    fn into_karamel(self) -> VmObject { VmObject::from(self) }
+}
+
+impl IntoKaramel for bool {
+    fn into_karamel(self) -> VmObject { VmObject::from(self) }
+}
+
+impl IntoKaramel for String {
+    fn into_karamel(self) -> VmObject { VmObject::from(Rc::new(self)) }
+}
+
+impl IntoKaramel for &str {
+    fn into_karamel(self) -> VmObject { VmObject::from(Rc::new(self.to_string())) }
+}
+
+impl<T: IntoKaramel> IntoKaramel for Option<T> {
+    fn into_karamel(self) -> VmObject {
+        match self {
+            Some(value) => value.into_karamel(),
+            None => EMPTY_OBJECT
+        }
+    }
+}
+
+impl<T: IntoKaramel> IntoKaramel for Vec<T> {
+    fn into_karamel(self) -> VmObject {
+        VmObject::from(self.into_iter().map(IntoKaramel::into_karamel).collect::<Vec<VmObject>>())
+    }
+}
+
+impl<T: IntoKaramel> IntoKaramel for HashMap<String, T> {
+    fn into_karamel(self) -> VmObject {
+        VmObject::from(self.into_iter().map(|(key, value)| (key, value.into_karamel())).collect::<HashMap<String, VmObject>>())
+    }
+}
+
+/* Value is ignored */
+impl FromKaramel for () {
+    fn from_karamel(_: VmObject) -> Result<Self, KaramelErrorType> { Ok(()) }
+}
+
+impl FromKaramel for f64 {
+    fn from_karamel(value: VmObject) -> Result<Self, KaramelErrorType> {
+        match &*value.deref() {
+            KaramelPrimative::Number(number) => Ok(*number),
+            value => conversion_error("sayı", value)
+        }
+    }
+}
+
+impl FromKaramel for bool {
+    fn from_karamel(value: VmObject) -> Result<Self, KaramelErrorType> {
+        match &*value.deref() {
+            KaramelPrimative::Bool(value) => Ok(*value),
+            value => conversion_error("bool", value)
+        }
+    }
+}
+
+impl FromKaramel for String {
+    fn from_karamel(value: VmObject) -> Result<Self, KaramelErrorType> {
+        match &*value.deref() {
+            KaramelPrimative::Text(text) => Ok(text.to_string()),
+            value => conversion_error("yazı", value)
+        }
+    }
+}
+
+/* 'boş' is converted to 'None' */
+impl<T: FromKaramel> FromKaramel for Option<T> {
+    fn from_karamel(value: VmObject) -> Result<Self, KaramelErrorType> {
+        match &*value.deref() {
+            KaramelPrimative::Empty => Ok(None),
+            _ => T::from_karamel(value).map(Some)
+        }
+    }
+}
+
+impl<T: FromKaramel> FromKaramel for Vec<T> {
+    fn from_karamel(value: VmObject) -> Result<Self, KaramelErrorType> {
+        match &*value.deref() {
+            KaramelPrimative::List(items) => items.borrow().iter().map(|item| T::from_karamel(*item)).collect(),
+            value => conversion_error("liste", value)
+        }
+    }
+}
+
+impl<T: FromKaramel> FromKaramel for HashMap<String, T> {
+    fn from_karamel(value: VmObject) -> Result<Self, KaramelErrorType> {
+        match &*value.deref() {
+            KaramelPrimative::Dict(items) => items.borrow().iter().map(|(key, item)| Ok((key.to_string(), T::from_karamel(*item)?))).collect(),
+            value => conversion_error("sözlük", value)
+        }
+    }
+}
+
+impl IntoKaramelArguments for Vec<VmObject> {
+    fn into_arguments(self) -> Vec<VmObject> { self }
+}
+
+macro_rules! tuple_arguments {
+    ($($name:ident),*) => {
+        impl<$($name: IntoKaramel),*> IntoKaramelArguments for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn into_arguments(self) -> Vec<VmObject> {
+                let ($($name,)*) = self;
+                vec![$($name.into_karamel()),*]
+            }
+        }
+    };
+}
+
+tuple_arguments!();
+tuple_arguments!(A);
+tuple_arguments!(A, B);
+tuple_arguments!(A, B, C);
+tuple_arguments!(A, B, C, D);
+tuple_arguments!(A, B, C, D, E);
+tuple_arguments!(A, B, C, D, E, F);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primatives() {
+        assert_eq!(f64::from_karamel(10.5.into_karamel()), Ok(10.5));
+        assert_eq!(bool::from_karamel(true.into_karamel()), Ok(true));
+        assert_eq!(String::from_karamel("karamel".into_karamel()), Ok("karamel".to_string()));
+        assert_eq!(Option::<f64>::from_karamel(().into_karamel()), Ok(None));
+        assert_eq!(Option::<f64>::from_karamel(Some(1.0).into_karamel()), Ok(Some(1.0)));
+    }
+
+    #[test]
+    fn collections() {
+        let list = vec![vec![1.0, 2.0], vec![3.0]];
+        assert_eq!(Vec::<Vec<f64>>::from_karamel(list.clone().into_karamel()), Ok(list));
+
+        let mut dict = HashMap::new();
+        dict.insert("ad".to_string(), vec!["erhan".to_string()]);
+        assert_eq!(HashMap::<String, Vec<String>>::from_karamel(dict.clone().into_karamel()), Ok(dict));
+    }
+
+    #[test]
+    fn wrong_type() {
+        assert_eq!(f64::from_karamel("1".into_karamel()), Err(KaramelErrorType::ValueConversionFailed { expected: "sayı".to_string(), found: "yazı".to_string() }));
+        assert_eq!(Vec::<bool>::from_karamel(vec![1.0].into_karamel()), Err(KaramelErrorType::ValueConversionFailed { expected: "bool".to_string(), found: "sayı".to_string() }));
+    }
+
+    #[test]
+    fn arguments() {
+        assert_eq!(().into_arguments().len(), 0);
+
+        let arguments = (1.0, "a", vec![true]).into_arguments();
+        assert_eq!(arguments.len(), 3);
+        assert_eq!(String::from_karamel(arguments[1]), Ok("a".to_string()));
+    }
+}
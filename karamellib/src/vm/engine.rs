@@ -0,0 +1,189 @@
+use std::rc::Rc;
+
+use crate::buildin::Module;
+use crate::buildin::native_module::{ArgumentCount, ModuleBuilder, NativeModule};
+use crate::compiler::KaramelCompilerContext;
+use crate::compiler::function::{FunctionParameter, NativeCallResult};
+use crate::compiler::value::{KaramelPrimative, EMPTY_OBJECT};
+use crate::compiler::ast::KaramelAstType;
+use crate::constants::{EMBEDDED_MODULE_NAME, EMBEDDED_RESULT_VARIABLE};
+use crate::error::KaramelErrorType;
+use crate::types::VmObject;
+use crate::vm::convert::{FromKaramel, IntoKaramel, IntoKaramelArguments};
+use crate::syntax::util::update_functions_for_temp_return;
+use crate::types::KaramelOperatorType;
+use crate::vm::executer::{build_error_message, configure_context, prepare_source_with, ExecutionSource};
+use crate::vm::interpreter::{call_function, run_vm};
+use crate::vm::io::KaramelIo;
+use crate::vm::limits::{ExecutionLimits, InterruptHandle};
+
+/// Karamel engine for the Rust applications. Codes are compiled into the same context, so variables and functions
+/// of the evaluated codes are kept between the calls and functions could be called with 'call'.
+pub struct Karamel {
+    pub optimize: bool,
+    pub strict_types: bool,
+    pub limits: ExecutionLimits,
+    pub interrupt: InterruptHandle,
+
+    /* Functions that registered by the application are called without a module name */
+    module: Rc<NativeModule>,
+
+    /* Boxed, heap and trampoline addresses are used while the code is running */
+    context: Box<KaramelCompilerContext>
+}
+
+impl Default for Karamel {
+    fn default() -> Self {
+        Karamel::new()
+    }
+}
+
+fn is_expression(ast: &KaramelAstType) -> bool {
+    matches!(ast, KaramelAstType::FuncCall { .. } | KaramelAstType::AccessorFuncCall { .. } | KaramelAstType::Primative(_) |
+        KaramelAstType::Binary { .. } | KaramelAstType::Control { .. } | KaramelAstType::Symbol(_) | KaramelAstType::ModulePath(_) |
+        KaramelAstType::List(_) | KaramelAstType::Dict(_) | KaramelAstType::Indexer { .. } | KaramelAstType::Slice { .. } |
+        KaramelAstType::Lambda { .. } | KaramelAstType::Conditional { .. } | KaramelAstType::Interpolation(_))
+}
+
+/* Last expression of the code is assigned to a hidden variable */
+fn store_result(ast: Rc<KaramelAstType>) -> Rc<KaramelAstType> {
+    match &*ast {
+        KaramelAstType::Block(items) => match items.iter().rposition(|item| !matches!(**item, KaramelAstType::NewLine)) {
+            Some(index) => {
+                let mut items = items.clone();
+                items[index] = store_result(items[index].clone());
+                Rc::new(KaramelAstType::Block(items))
+            },
+            None => ast.clone()
+        },
+        expression if is_expression(expression) => {
+            update_functions_for_temp_return(expression);
+            Rc::new(KaramelAstType::Assignment {
+                variable: Rc::new(KaramelAstType::Symbol(EMBEDDED_RESULT_VARIABLE.to_string())),
+                operator: KaramelOperatorType::Assign,
+                expression: ast.clone()
+            })
+        },
+        _ => ast
+    }
+}
+
+impl Karamel {
+    pub fn new() -> Self {
+        let module = ModuleBuilder::new(EMBEDDED_MODULE_NAME).path(&[]).build();
+        let mut context = Box::new(KaramelCompilerContext::new());
+        context.add_module(module.clone());
+
+        Karamel {
+            optimize: true,
+            strict_types: false,
+            limits: ExecutionLimits::default(),
+            interrupt: InterruptHandle::new(),
+            module,
+            context
+        }
+    }
+
+    /// Compile and execute the code. Value of the last expression is returned, 'boş' if the code ends with a statement.
+    pub fn eval<T: FromKaramel>(&mut self, code: &str) -> Result<T, String> {
+        let context = &mut *self.context;
+        configure_context(context, self.optimize, self.strict_types, self.limits, Some(self.interrupt.clone()));
+
+        prepare_source_with(ExecutionSource::Code(code.to_string()), context, store_result)?;
+
+        /* Value of the previous code is not returned */
+        let result_location = context.storages[0].get_variable_location(EMBEDDED_RESULT_VARIABLE);
+        if let Some(location) = result_location {
+            context.stack[location as usize] = EMPTY_OBJECT;
+        }
+
+        if let Err(error) = unsafe { run_vm(context, false, false) } {
+            return Err(build_error_message(context, error));
+        }
+
+        let result = match result_location {
+            Some(location) => context.stack[location as usize],
+            None => EMPTY_OBJECT
+        };
+
+        T::from_karamel(result).map_err(|error| error.to_string())
+    }
+
+    /// Call the function that defined by the evaluated codes
+    pub fn call<T: FromKaramel, A: IntoKaramelArguments>(&mut self, name: &str, arguments: A) -> Result<T, String> {
+        let context = &mut *self.context;
+        if context.opcodes.is_empty() {
+            return Err(KaramelErrorType::FunctionCallNotAvailable.to_string());
+        }
+
+        let function = match context.get_function(name.to_string(), &Vec::new(), 0) {
+            Some(function) => function,
+            None => return Err(KaramelErrorType::FunctionNotFound(name.to_string()).to_string())
+        };
+
+        context.limiter.start();
+        context.error_location = None;
+        context.stack_trace.clear();
+
+        /* Function and the arguments are released by the heap */
+        let result = {
+            let _heap_guard = context.heap.activate();
+            let function = VmObject::from(Rc::new(KaramelPrimative::Function(function, None)));
+            let arguments = arguments.into_arguments();
+            unsafe { call_function(context, function, &arguments) }
+        };
+
+        let result = result.map_err(|error| build_error_message(context, error))?;
+        T::from_karamel(result).map_err(|error| error.to_string())
+    }
+
+    /// Global variable is used by the next evaluated code and the function calls
+    pub fn set_global<T: IntoKaramel>(&mut self, name: &str, value: T) {
+        let location = self.context.storages[0].add_variable(name);
+        let _heap_guard = self.context.heap.activate();
+        self.context.stack[location as usize] = value.into_karamel();
+    }
+
+    pub fn get_global<T: FromKaramel>(&self, name: &str) -> Result<T, String> {
+        match self.context.storages[0].get_variable_location(name) {
+            Some(location) if name != EMBEDDED_RESULT_VARIABLE => T::from_karamel(self.context.stack[location as usize]).map_err(|error| error.to_string()),
+            _ => Err(KaramelErrorType::VariableNotFound(name.to_string()).to_string())
+        }
+    }
+
+    /// Register a native function that could be called without a module name
+    pub fn register_function<F>(&mut self, name: &str, function: F) where F: Fn(FunctionParameter) -> NativeCallResult + 'static {
+        self.module.add_function(name, ArgumentCount::Any, function);
+    }
+
+    /// Register a module, e.g. a module that created with 'ModuleBuilder'
+    pub fn register_module(&mut self, module: Rc<dyn Module>) {
+        self.context.add_module(module);
+    }
+
+    /// Input and output of the evaluated codes, standard input and output are used by default
+    pub fn set_io(&mut self, io: Rc<dyn KaramelIo>) {
+        self.context.io = io;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vm::heap::Heap;
+
+    #[test]
+    fn values_are_tracked() {
+        let mut engine = Karamel::new();
+        engine.set_global("isim", "karamel");
+        assert!(engine.eval::<()>("fonk selam(isim): döndür ['merhaba ' + isim]").is_ok());
+        assert!(engine.eval::<()>("sonuç = selam(isim)").is_ok());
+        assert_eq!(engine.call::<Vec<String>, _>("selam", ("dünya",)), Ok(vec!["merhaba dünya".to_string()]));
+
+        /* Values at the global variables are still reachable after the collection */
+        unsafe { Heap::collect(&mut engine.context) };
+        assert_eq!(engine.get_global::<String>("isim"), Ok("karamel".to_string()));
+        assert_eq!(engine.get_global::<Vec<String>>("sonuç"), Ok(vec!["merhaba karamel".to_string()]));
+        assert_eq!(engine.call::<Vec<String>, _>("selam", ("dünya",)), Ok(vec!["merhaba dünya".to_string()]));
+    }
+}
@@ -1,14 +1,23 @@
 use std::borrow::Borrow;
 use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
 
 use crate::compiler::context::{ExecutionPathInfo, KaramelCompilerContext};
 use crate::file::read_module_or_script;
 use crate::{types::Token, vm::interpreter::run_vm};
 use crate::parser::*;
 use crate::compiler::*;
+use crate::compiler::ast::KaramelAstType;
 use crate::syntax::SyntaxParser;
 use crate::logger::{CONSOLE_LOGGER, write_stderr};
-use crate::error::generate_error_message;
+use crate::error::{generate_error_message, KaramelError, KaramelErrorType};
+use crate::constants::SOURCE_CODE_NAME;
+use crate::compiler::bytecode::{load_bytecode, save_bytecode};
+use crate::vm::limits::{ExecutionLimiter, ExecutionLimits, InterruptHandle};
+use crate::vm::heap::Heap;
+use crate::vm::io::{BufferedIo, KaramelIo};
+use crate::vm::convert::FromKaramel;
 
 use log;
 use crate::types::VmObject;
@@ -16,7 +25,8 @@ use crate::types::VmObject;
 
 pub enum ExecutionSource {
     Code(String),
-    File(String)
+    File(String),
+    Bytecode(Vec<u8>)
 }
 
 pub struct ExecutionParameters {
@@ -24,7 +34,56 @@ pub struct ExecutionParameters {
     pub return_opcode: bool,
     pub return_output: bool,
     pub dump_opcode: bool,
-    pub dump_memory: bool
+    pub dump_memory: bool,
+    pub optimize: bool,
+    pub strict_types: bool,
+    pub limits: ExecutionLimits,
+    pub interrupt: Option<InterruptHandle>,
+
+    /// Input and output of the program, returned output is not collected if it is given
+    pub io: Option<Rc<dyn KaramelIo>>
+}
+
+pub struct SessionParameters {
+    pub return_output: bool,
+    pub optimize: bool,
+    pub strict_types: bool,
+    pub limits: ExecutionLimits,
+    pub interrupt: Option<InterruptHandle>,
+
+    /// Input and output of the program, returned output is not collected if it is given
+    pub io: Option<Rc<dyn KaramelIo>>
+}
+
+/* Output is written to the standard output, optimizer is enabled */
+impl Default for ExecutionParameters {
+    fn default() -> Self {
+        ExecutionParameters {
+            source: ExecutionSource::Code(String::new()),
+            return_opcode: false,
+            return_output: false,
+            dump_opcode: false,
+            dump_memory: false,
+            optimize: true,
+            strict_types: false,
+            limits: ExecutionLimits::default(),
+            interrupt: None,
+            io: None
+        }
+    }
+}
+
+impl Default for SessionParameters {
+    fn default() -> Self {
+        SessionParameters {
+            return_output: false,
+            optimize: true,
+            strict_types: false,
+            limits: ExecutionLimits::default(),
+            interrupt: None,
+            io: None
+        }
+    }
 }
 
 #[derive(Default)]
@@ -36,13 +95,16 @@ pub struct ExecutionStatus {
     pub stderr: Option<RefCell<String>>,
     pub opcodes: Option<Vec<Token>>,
     pub memory_dump: Option<String>,
-    pub opcode_dump: Option<String>
+    pub opcode_dump: Option<String>,
+
+    /* Heap objects of the memory output are released with the status */
+    heap: Heap
 }
 
 pub fn get_execution_path<T: Borrow<ExecutionSource>>(source: T) -> ExecutionPathInfo {
     ExecutionPathInfo {
         path: match source.borrow() {
-            ExecutionSource::Code(_) => match std::env::current_exe() {
+            ExecutionSource::Code(_) | ExecutionSource::Bytecode(_) => match std::env::current_exe() {
                 Ok(path) => match path.parent() {
                     Some(parent_path) => parent_path.to_str().unwrap().to_string(),
                     _ => String::from(".")
@@ -55,8 +117,90 @@ pub fn get_execution_path<T: Borrow<ExecutionSource>>(source: T) -> ExecutionPat
     }
 }
 
-pub fn code_executer(parameters: ExecutionParameters) -> ExecutionStatus {
-    let mut status = ExecutionStatus::default();
+/* Source line and stack trace are added if the error location is known */
+pub(crate) fn build_error_message(context: &KaramelCompilerContext, error: KaramelErrorType) -> String {
+    let location = context.error_location.and_then(|location| context.source_map.get_file(location.file).map(|file| (file, location.position)));
+    let mut message = match location {
+        Some((file, position)) => generate_error_message(&file.code, KaramelError::new(position.line, position.column, error)),
+        None => format!("Program hata ile sonlandırıldı: {}", error)
+    };
+
+    if !context.stack_trace.is_empty() {
+        message.push_str("\r\nÇağrı yığını:");
+
+        /* Same frames that come from the recursive calls are written once */
+        let mut index = 0;
+        while index < context.stack_trace.len() {
+            let frame = &context.stack_trace[index];
+            let count = context.stack_trace[index..].iter().take_while(|item| *item == frame).count();
+            match count {
+                1 => message.push_str(&format!("\r\n    {}", frame)),
+                _ => message.push_str(&format!("\r\n    {} ({} kez)", frame, count))
+            };
+            index += count;
+        }
+    }
+
+    message
+}
+
+/* Source code is compiled or the compiled program is loaded. Error message is returned if it fails. */
+pub(crate) fn prepare_source(source: ExecutionSource, context: &mut KaramelCompilerContext) -> Result<Option<Vec<Token>>, String> {
+    prepare_source_with(source, context, |ast| ast)
+}
+
+/* Syntax tree of the code could be changed before the compilation */
+pub(crate) fn prepare_source_with<F>(source: ExecutionSource, context: &mut KaramelCompilerContext, transform: F) -> Result<Option<Vec<Token>>, String> where F: FnOnce(Rc<KaramelAstType>) -> Rc<KaramelAstType> {
+    let (source_name, data) = match source {
+        ExecutionSource::Bytecode(bytecode) => return match load_bytecode(&bytecode, context) {
+            Ok(_) => Ok(None),
+            Err(error) => Err(format!("Program hata ile sonlandırıldı: {}", error))
+        },
+        ExecutionSource::Code(code) => (SOURCE_CODE_NAME.to_string(), code),
+        ExecutionSource::File(filename) => match read_module_or_script(&filename[..], context) {
+            Ok(content) => (filename, content),
+            Err(error) => return Err(format!("Program hata ile sonlandırıldı: {}", error))
+        }
+    };
+
+    let mut parser = Parser::new(&data);
+    if let Err(error) = parser.parse() {
+        return Err(generate_error_message(&data, &error));
+    }
+
+    let syntax = SyntaxParser::new(parser.tokens().to_vec());
+    let ast = match syntax.parse() {
+        Ok(ast) => ast,
+        Err(error) => return Err(generate_error_message(&data, &error))
+    };
+
+    /* Source of the failed code is removed after the error message is built */
+    let snapshot = CompilationSnapshot::new(context);
+    context.source_map.add_file(source_name, data.to_string(), syntax.positions());
+    let ast = transform(ast);
+
+    let opcode_compiler = InterpreterCompiler {};
+    match opcode_compiler.compile_code(ast, context) {
+        Ok(_) => Ok(Some(parser.tokens())),
+        Err(error) => {
+            let message = build_error_message(context, error);
+            snapshot.restore(context);
+            Err(message)
+        }
+    }
+}
+
+/// Compile the code and serialize it. Compiled program could be executed with 'ExecutionSource::Bytecode'.
+pub fn compile_to_bytecode(source: ExecutionSource) -> Result<Vec<u8>, String> {
+    let mut context = KaramelCompilerContext::new();
+    context.execution_path = get_execution_path(&source);
+    context.optimize = true;
+
+    prepare_source(source, &mut context)?;
+    save_bytecode(&context).map_err(|error| format!("Program hata ile sonlandırıldı: {}", error))
+}
+
+fn set_logger() {
     match log::set_logger(&CONSOLE_LOGGER) {
         Ok(_) => {
             if cfg!(debug_assertions) {
@@ -67,84 +211,79 @@ pub fn code_executer(parameters: ExecutionParameters) -> ExecutionStatus {
         },
         _ => ()
     };
+}
 
-    let mut context: KaramelCompilerContext = KaramelCompilerContext::new();
-    context.execution_path = get_execution_path(&parameters.source);
-    log::debug!("Execution path: {}", context.execution_path.path);
+pub(crate) fn configure_context(context: &mut KaramelCompilerContext, optimize: bool, strict_types: bool, limits: ExecutionLimits, interrupt: Option<InterruptHandle>) {
+    context.optimize = optimize;
+    context.strict_types = strict_types;
 
-    if parameters.return_output {
-        context.stdout = Some(RefCell::new(String::new()));
-        context.stderr = Some(RefCell::new(String::new()));
+    if let Some(max_call_depth) = limits.max_call_depth {
+        context.max_call_depth = max_call_depth;
     }
+    context.limiter = ExecutionLimiter::new(limits);
 
-    let data = match parameters.source {
-        ExecutionSource::Code(code) => code,
-        ExecutionSource::File(filename) => {
-            match read_module_or_script(filename, &context) {
-                Ok(content) => content,
-                Err(error) => {
-                    write_stderr(&context, format!("Program hata ile sonlandırıldı: {}", error));
-                    log::error!("Program hata ile sonlandırıldı: {}", error);
-                    status.stdout = context.stdout;
-                    status.stderr = context.stderr;
-                    
-                    status.executed = false;
-                    return status
-                }
-            }
-        }
-    };
-
-    let mut parser = Parser::new(&data);
-    match parser.parse() {
-        Err(error) => {
-            write_stderr(&context, generate_error_message(&data, &error));
-            log::error!("{}", generate_error_message(&data, &error));
-            status.stdout = context.stdout;
-            status.stderr = context.stderr;
+    if let Some(interrupt) = interrupt {
+        context.interrupt = interrupt;
+    }
+}
 
-            return status;
+/* Output is collected to return it with the status, if the application did not give its own io */
+fn set_io(context: &mut KaramelCompilerContext, io: Option<Rc<dyn KaramelIo>>, return_output: bool) -> Option<Rc<BufferedIo>> {
+    match (io, return_output) {
+        (Some(io), _) => {
+            context.io = io;
+            None
         },
-        _ => ()
-    };
+        (None, true) => {
+            let buffer = Rc::new(BufferedIo::new());
+            context.io = buffer.clone();
+            Some(buffer)
+        },
+        (None, false) => None
+    }
+}
 
-    let syntax = SyntaxParser::new(parser.tokens().to_vec());
-    let ast = match syntax.parse() {
-        Ok(ast) => ast,
-        Err(error) => {
-            write_stderr(&context, generate_error_message(&data, &error));
-            log::error!("{}", generate_error_message(&data, &error));
-            status.stdout = context.stdout;
-            status.stderr = context.stderr;
+fn set_output(status: &mut ExecutionStatus, buffer: &Option<Rc<BufferedIo>>) {
+    if let Some(buffer) = buffer {
+        status.stdout = Some(RefCell::new(buffer.stdout()));
+        status.stderr = Some(RefCell::new(buffer.stderr()));
+    }
+}
 
-            return status;
-        }
-    };
+pub fn code_executer(parameters: ExecutionParameters) -> ExecutionStatus {
+    let mut status = ExecutionStatus::default();
+    set_logger();
 
-    let opcode_compiler = InterpreterCompiler {};
-    let execution_status = match opcode_compiler.compile(ast.clone(), &mut context) {
-        Ok(_) => unsafe { run_vm(&mut context, parameters.dump_opcode, parameters.dump_memory) },
+    let mut context: KaramelCompilerContext = KaramelCompilerContext::new();
+    context.execution_path = get_execution_path(&parameters.source);
+    log::debug!("Execution path: {}", context.execution_path.path);
+    configure_context(&mut context, parameters.optimize, parameters.strict_types, parameters.limits, parameters.interrupt);
+
+    let buffer = set_io(&mut context, parameters.io, parameters.return_output);
+
+    let tokens = match prepare_source(parameters.source, &mut context) {
+        Ok(tokens) => tokens,
         Err(message) => {
-            write_stderr(&context, format!("Program hata ile sonlandırıldı: {}", message));
-            log::error!("Program hata ile sonlandırıldı: {}", message);
-            status.stdout = context.stdout;
-            status.stderr = context.stderr;
+            write_stderr(&context, message.to_string());
+            log::error!("{}", message);
+            set_output(&mut status, &buffer);
 
             return status;
         }
     };
 
-    match execution_status {
+    match unsafe { run_vm(&mut context, parameters.dump_opcode, parameters.dump_memory) } {
         Ok(memory) => {
             status.compiled = true;
             status.executed = true;
-            status.memory_output = Some(memory)
+            status.memory_output = Some(memory);
+            status.heap = mem::take(&mut context.heap);
         },
         Err(error) => {
-            write_stderr(&context, format!("Program hata ile sonlandırıldı: {}", error));
-            log::error!("Program hata ile sonlandırıldı: {}", error);
-            status.stdout = context.stdout;
-            status.stderr = context.stderr;
+            let message = build_error_message(&context, error);
+            write_stderr(&context, message.to_string());
+            log::error!("{}", message);
+            set_output(&mut status, &buffer);
 
             return status;
         }
@@ -152,13 +291,75 @@ pub fn code_executer(parameters: ExecutionParameters) -> ExecutionStatus {
 
     log::info!("Program başarıyla çalıştırıldı");
     if parameters.return_opcode {
-        status.opcodes = Some(parser.tokens());
+        status.opcodes = tokens;
     }
 
-    status.stdout      = context.stdout;
-    status.stderr      = context.stderr;
+    set_output(&mut status, &buffer);
     status.memory_dump = context.memory_dump;
     status.opcode_dump = context.opcode_dump;
 
     status
 }
+
+/// Interpreter session for the REPLs and notebooks. Every code is compiled into the same context, so variables, functions
+/// and loaded modules of the previous codes could be used by the next ones. Code that fails to compile is not added.
+pub struct KaramelSession {
+    return_output: bool,
+
+    /* Boxed, heap and trampoline addresses are used while the code is running */
+    context: Box<KaramelCompilerContext>
+}
+
+impl KaramelSession {
+    pub fn new(parameters: SessionParameters) -> Self {
+        set_logger();
+
+        let mut context = Box::new(KaramelCompilerContext::new());
+        context.execution_path = get_execution_path(ExecutionSource::Code(String::new()));
+        configure_context(&mut context, parameters.optimize, parameters.strict_types, parameters.limits, parameters.interrupt);
+
+        let return_output = parameters.return_output && parameters.io.is_none();
+        set_io(&mut context, parameters.io, false);
+
+        KaramelSession {
+            return_output,
+            context
+        }
+    }
+
+    /// Compile the code into the session and execute it. Output is returned with the status if the session returns the output.
+    pub fn execute(&mut self, code: &str) -> ExecutionStatus {
+        let mut status = ExecutionStatus::default();
+        let context = &mut *self.context;
+
+        let buffer = set_io(context, None, self.return_output);
+
+        let result = match prepare_source(ExecutionSource::Code(code.to_string()), context) {
+            Ok(_) => {
+                status.compiled = true;
+                unsafe { run_vm(context, false, false) }.map_err(|error| build_error_message(context, error))
+            },
+            Err(message) => Err(message)
+        };
+
+        /* Heap objects are released by the next executions, memory output is not returned */
+        match result {
+            Ok(_) => status.executed = true,
+            Err(message) => {
+                write_stderr(context, message.to_string());
+                log::error!("{}", message);
+            }
+        };
+
+        set_output(&mut status, &buffer);
+        status
+    }
+
+    /// Value of the variable that defined by the executed codes, value is converted because the heap objects belong to the session
+    pub fn get_variable<T: FromKaramel>(&self, name: &str) -> Result<T, String> {
+        match self.context.storages[0].get_variable_location(name).and_then(|location| self.context.stack.get(location as usize)) {
+            Some(value) => T::from_karamel(*value).map_err(|error| error.to_string()),
+            None => Err(KaramelErrorType::VariableNotFound(name.to_string()).to_string())
+        }
+    }
+}
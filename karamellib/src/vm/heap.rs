@@ -0,0 +1,237 @@
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::ptr;
+use std::rc::Rc;
+
+use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::value::KaramelPrimative;
+use crate::buildin::ClassProperty;
+use crate::constants::HEAP_INITIAL_THRESHOLD;
+use crate::types::{VmObject, POINTER_FLAG, POINTER_MASK, QNAN};
+
+thread_local! {
+    /* Heap of the running virtual machine. Objects created outside of the execution are not tracked */
+    static ACTIVE_HEAP: Cell<*mut Heap> = Cell::new(ptr::null_mut());
+}
+
+/// Mark and sweep collector for the heap objects that created while the virtual machine is running.
+/// Every tracked pointer owns one strong reference of the 'Rc' that converted into 'VmObject'.
+/// Unreachable objects release their reference at the collection. Objects that are alive at the end of
+/// the execution are released when the heap is dropped, so result values could be used until then.
+pub struct Heap {
+    objects: Vec<*const KaramelPrimative>,
+    roots: Vec<VmObject>,
+    threshold: usize
+}
+
+impl Default for Heap {
+    fn default() -> Self {
+        Heap::new()
+    }
+}
+
+impl Heap {
+    pub fn new() -> Self {
+        Heap {
+            objects: Vec::new(),
+            roots: Vec::new(),
+            threshold: HEAP_INITIAL_THRESHOLD
+        }
+    }
+
+    /// Total tracked object count
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    #[inline]
+    pub fn should_collect(&self) -> bool {
+        self.objects.len() >= self.threshold
+    }
+
+    /// Values that hold by the native functions are not at the stack, they are kept alive until the roots are released
+    pub fn add_root(&mut self, object: VmObject) {
+        self.roots.push(object);
+    }
+
+    pub fn root_count(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Release the roots that added after the given root count
+    pub fn release_roots(&mut self, count: usize) {
+        self.roots.truncate(count);
+    }
+
+    /* Pointer is tracked only if there is an running virtual machine */
+    #[inline]
+    pub fn track(pointer: *const KaramelPrimative) {
+        ACTIVE_HEAP.with(|heap| {
+            let heap = heap.get();
+            if !heap.is_null() {
+                unsafe { (*heap).objects.push(pointer) };
+            }
+        });
+    }
+
+    /// Make the heap active for the current thread, previous one activated again when the guard dropped.
+    pub fn activate(&mut self) -> HeapGuard {
+        HeapGuard {
+            previous: ACTIVE_HEAP.with(|heap| heap.replace(self as *mut Heap))
+        }
+    }
+
+    /// Release the objects that could not be reached from stack, roots and constants. Must be called between opcodes,
+    /// all alive values should be at the stack or at the roots.
+    pub unsafe fn collect(context: &mut KaramelCompilerContext) {
+        let mut marked = HashSet::new();
+        let mut pending: Vec<VmObject> = Vec::new();
+
+        let stack_size = context.stack_ptr.offset_from(context.stack.as_ptr()) as usize;
+        pending.extend_from_slice(&context.stack[..stack_size]);
+        pending.extend_from_slice(&context.heap.roots);
+        for storage in context.storages.iter() {
+            pending.extend_from_slice(&storage.constants);
+        }
+
+        while let Some(object) = pending.pop() {
+            if let Some(pointer) = Heap::get_pointer(object) {
+                if marked.insert(pointer) {
+                    Heap::add_references(&*pointer, &mut pending);
+                }
+            }
+        }
+
+        context.heap.objects.retain(|pointer| {
+            let alive = marked.contains(pointer);
+            if !alive {
+                drop(Rc::from_raw(*pointer));
+            }
+            alive
+        });
+
+        context.heap.threshold = HEAP_INITIAL_THRESHOLD.max(context.heap.objects.len() * 2);
+    }
+
+    fn get_pointer(object: VmObject) -> Option<*const KaramelPrimative> {
+        match (object.0 & QNAN) == QNAN && (object.0 & POINTER_FLAG) == POINTER_FLAG {
+            true => Some((object.0 & POINTER_MASK) as *const KaramelPrimative),
+            false => None
+        }
+    }
+
+    fn add_references(primative: &KaramelPrimative, pending: &mut Vec<VmObject>) {
+        match primative {
+            KaramelPrimative::List(items) => pending.extend(items.borrow().iter()),
+            KaramelPrimative::Dict(items) => pending.extend(items.borrow().values()),
+            KaramelPrimative::Function(_, Some(base)) => pending.push(*base),
+            KaramelPrimative::Class(class) => {
+                pending.extend(class.references());
+                for (_, property) in class.properties() {
+                    if let ClassProperty::Field(field) = property {
+                        Heap::add_references(field, pending);
+                    }
+                }
+            },
+            _ => ()
+        };
+    }
+}
+
+impl Drop for Heap {
+    fn drop(&mut self) {
+        for pointer in self.objects.drain(..) {
+            unsafe { drop(Rc::from_raw(pointer)) };
+        }
+    }
+}
+
+pub struct HeapGuard {
+    previous: *mut Heap
+}
+
+impl Drop for HeapGuard {
+    fn drop(&mut self) {
+        ACTIVE_HEAP.with(|heap| heap.set(self.previous));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::compiler::context::KaramelCompilerContext;
+    use crate::types::VmObject;
+    use super::Heap;
+
+    #[test]
+    fn untracked_objects() {
+        let mut context = KaramelCompilerContext::new();
+        let _ = VmObject::from("karamel".to_string());
+        assert!(context.heap.is_empty());
+
+        let _guard = context.heap.activate();
+        let _ = VmObject::from("karamel".to_string());
+        let _ = VmObject::from(10.0);
+        assert_eq!(context.heap.len(), 1);
+    }
+
+    #[test]
+    fn collect_unreachable() {
+        let mut context = KaramelCompilerContext::new();
+        let _guard = context.heap.activate();
+        unsafe {
+            context.stack_ptr = context.stack.as_mut_ptr();
+            let text = Rc::new("karamel".to_string());
+            *context.stack_ptr = VmObject::from(vec![VmObject::from(text.clone())]);
+            context.stack_ptr = context.stack_ptr.add(1);
+            let _ = VmObject::from(vec![VmObject::from(1.0)]);
+            assert_eq!(context.heap.len(), 3);
+
+            Heap::collect(&mut context);
+            assert_eq!(context.heap.len(), 2);
+            assert_eq!(Rc::strong_count(&text), 2);
+
+            context.stack_ptr = context.stack_ptr.sub(1);
+            Heap::collect(&mut context);
+            assert!(context.heap.is_empty());
+            assert_eq!(Rc::strong_count(&text), 1);
+        }
+    }
+
+    #[test]
+    fn collect_roots() {
+        let mut context = KaramelCompilerContext::new();
+        let _guard = context.heap.activate();
+        unsafe {
+            context.stack_ptr = context.stack.as_mut_ptr();
+            let count = context.heap.root_count();
+            context.heap.add_root(VmObject::from(vec![VmObject::from(1.0)]));
+
+            Heap::collect(&mut context);
+            assert_eq!(context.heap.len(), 1);
+
+            context.heap.release_roots(count);
+            Heap::collect(&mut context);
+            assert!(context.heap.is_empty());
+        }
+    }
+
+    #[test]
+    fn release_at_drop() {
+        let text = Rc::new("karamel".to_string());
+        let mut context = KaramelCompilerContext::new();
+        {
+            let _guard = context.heap.activate();
+            let _ = VmObject::from(vec![VmObject::from(text.clone())]);
+        }
+
+        assert_eq!(Rc::strong_count(&text), 2);
+        drop(context);
+        assert_eq!(Rc::strong_count(&text), 1);
+    }
+}
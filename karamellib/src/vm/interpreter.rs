@@ -1,6 +1,9 @@
-use crate::compiler::context::KaramelCompilerContext;
+use crate::compiler::context::{KaramelCompilerContext, ExceptionHandler};
+use crate::constants::{ERROR_CODE_NAME, ERROR_MESSAGE_NAME, MAX_ARGUMENT_SIZE, OPCODE_LOCATION_SIZE};
 use crate::compiler::scope::Scope;
+use crate::compiler::function::FunctionType;
 use crate::error::KaramelErrorType;
+use strum::EnumMessage;
 use crate::logger::write_stdout;
 use crate::{pop, inc_memory_index, dec_memory_index, get_memory_index, karamel_dbg};
 use crate::types::{VmObject};
@@ -15,6 +18,10 @@ use std::io::{self, Write};
 use std::ptr;
 use colored::*;
 use crate::buildin::ClassProperty;
+use crate::buildin::class::instance::ClassInstance;
+use crate::buildin::class::slice_indices;
+use crate::vm::heap::Heap;
+use crate::vm::limits::check_limits;
 
 #[cfg(all(feature = "NONONO"))]
 pub unsafe fn dump_opcode<W: Write>(index: usize, context: &mut KaramelCompilerContext, log_update: &mut LogUpdate<W>) {
@@ -43,9 +50,6 @@ pub unsafe fn dump_opcode<W: Write>(index: usize, context: &mut KaramelCompilerC
 
 pub unsafe fn run_vm(context: &mut KaramelCompilerContext, dump_code: bool, dump_memory: bool) -> Result<Vec<VmObject>, KaramelErrorType>
 {
-    #[cfg(any(feature = "liveOpcodeView", feature = "dumpOpcodes"))]
-    let mut log_update = LogUpdate::new(stdout()).unwrap();
-    
     #[cfg(feature = "dumpMemory")] {
         context.storages[0].dump();
     }
@@ -56,439 +60,892 @@ pub unsafe fn run_vm(context: &mut KaramelCompilerContext, dump_code: bool, dump
         //log_update.render(&generated[..]);
     }
 
+    /* Heap objects created by the execution are tracked by the garbage collector */
+    let _heap_guard = context.heap.activate();
+    context.limiter.start();
+
+    /* Previous execution of the session could be stopped inside a function */
+    context.scope_index   = 0;
+    context.current_scope = context.scopes_ptr;
+    context.exception_handlers.clear();
+    context.callback_locations.clear();
+    context.error_location = None;
+    context.stack_trace.clear();
+
     // Save top stack for main storage
     let top_stack = context.stack.as_mut_ptr();
 
     // Move stack pointer to forward. First slots are reserved for variable memories.
-    context.stack_ptr = top_stack.add(context.storages[0].variables.len());
+    context.stack_ptr = top_stack;
+    inc_memory_index!(context, context.storages[0].variables.len());
     context.storages_ptr = context.storages.as_mut_ptr();
     {
         context.scopes[context.scope_index] = Scope {
             location: ptr::null_mut(),
             call_return_assign_to_temp: false,
             top_stack: top_stack,
-            constant_ptr: context.storages[0].constants.as_ptr()
+            constant_ptr: context.storages[0].constants.as_ptr(),
+            missing_arguments: 0,
+            function: ptr::null()
         };
 
-        loop {
-            let opcode = mem::transmute::<u8, VmOpCode>(*context.opcodes_ptr);
-            #[cfg(all(feature = "liveOpcodeView"))] {
-                dump_opcode(context.opcode_index, context, &mut log_update);
-            }
-            
-            match karamel_dbg_any!(opcode) {
-                VmOpCode::Subraction => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
+        if let Err(error) = run_with_handlers(context, 0) {
+            record_error(context);
+            return Err(error);
+        }
+        
+        if dump_memory {
+            let dump = context.storages[0].dump();
+            context.memory_dump = Some(dump);
+        }
+    }
+    
+    let mut result = Vec::with_capacity(get_memory_index!(context) as usize);
+    for index in 0..get_memory_index!(context) {
+        result.push(*top_stack.add(context.storages[0].variables.len() + index as usize));
+    }
 
-                    karamel_print_level2!("Subraction: {:?} - {:?}", left, right);
+    Ok(result)
+}
 
-                    *context.stack_ptr = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) - karamel_dbg!(r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    inc_memory_index!(context, 1);
-                    dump_data!(context, "result");
-                },
+/// Execute the function from a native function and return its result. Caller state is restored after the call, errors
+/// that are not handled by the 'dene' blocks of the called function are returned to the native function.
+pub unsafe fn call_function(context: &mut KaramelCompilerContext, function: VmObject, arguments: &[VmObject]) -> Result<VmObject, KaramelErrorType> {
+    let value = function.deref();
+    let (reference, base) = match &*value {
+        KaramelPrimative::Function(reference, base) => (reference.clone(), *base),
+        _ => return Err(KaramelErrorType::NotCallable(value.clone()))
+    };
+
+    if arguments.len() > MAX_ARGUMENT_SIZE {
+        return Err(KaramelErrorType::ArgumentLimitExceeded(MAX_ARGUMENT_SIZE));
+    }
 
-                VmOpCode::Addition => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("Addition: {:?} + {:?}", left, right);
+    check_limits(context)?;
+    context.check_stack(arguments.len())?;
 
-                    *context.stack_ptr = match (&left.deref_clean(), &right.deref_clean()) {
-                        (KaramelPrimative::Number(l_value),  KaramelPrimative::Number(r_value)) => VmObject::from(karamel_dbg!(l_value) + karamel_dbg!(r_value)),
-                        (KaramelPrimative::Text(l_value),    KaramelPrimative::Text(r_value))   => VmObject::from(Rc::new((&**l_value).to_owned() + &**r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
-
-                VmOpCode::Load => {
-                    let tmp   = *context.opcodes_ptr.offset(1) as usize;
-                    let scope = &mut *context.current_scope;
-                    *context.stack_ptr = karamel_dbg!(*scope.top_stack.offset(tmp as isize));
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    karamel_print_level2!("Load: [{:?}]: {:?}", tmp, *context.stack_ptr);
-                    dump_data!(context, "loaded");
-                    inc_memory_index!(context, 1);
-                },
-
-                VmOpCode::Constant => {
-                    let tmp   = *context.opcodes_ptr.offset(1) as usize;
-                    let scope = &mut *context.current_scope;        
-                    *context.stack_ptr = karamel_dbg!(*scope.constant_ptr.offset(tmp as isize));        
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    karamel_print_level2!("Constant: [{:?}]: {:?}", tmp, *context.stack_ptr);
-                    dump_data!(context, "constant loaded");
-                    inc_memory_index!(context, 1);
-                },
-
-                VmOpCode::Store => {
-                    let tmp = *context.opcodes_ptr.offset(1) as usize;
-                    dec_memory_index!(context, 1);
-                    *(*context.current_scope).top_stack.offset(tmp as isize) = karamel_dbg!(*context.stack_ptr);
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    karamel_print_level2!("Store: [{:?}]: {:?}", tmp, *context.stack_ptr);
-                },
-
-                VmOpCode::CopyToStore => {
-                    let tmp = *context.opcodes_ptr.offset(1) as usize;
-                    *(*context.current_scope).top_stack.offset(tmp as isize) = karamel_dbg!(*context.stack_ptr.sub(1));
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    karamel_print_level2!("CopyToStore: [{:?}]: {:?}", tmp, *context.stack_ptr);
-                },
-
-                VmOpCode::FastStore => {
-                    let destination = *context.opcodes_ptr.offset(1) as usize;
-                    let source      = *context.opcodes_ptr.offset(2) as usize;
-                    *(*context.current_scope).top_stack.offset(destination as isize) = karamel_dbg!(*(*context.current_scope).constant_ptr.offset(source as isize));
-                    context.opcodes_ptr = context.opcodes_ptr.offset(2);
-                    karamel_print_level2!("FastStore: {:?}: {:?} => {:?}", *(*context.current_scope).top_stack.offset(destination as isize), source, destination);
-                },
-
-                VmOpCode::Not => {
-                    *context.stack_ptr.sub(1) = VmObject::from(!(*context.stack_ptr.sub(1)).deref_clean().is_true());
-                    dump_data!(context, "result");
-                    karamel_print_level2!("Not: {:?}", *context.stack_ptr.sub(1));
-                },
-
-                VmOpCode::Dublicate => {
-                    *context.stack_ptr = karamel_dbg!(*context.stack_ptr.sub(1));
-                    karamel_print_level2!("Dublicate: {:?}", *context.stack_ptr);
-                    inc_memory_index!(context, 1);
-                },
+    let opcodes_ptr   = context.opcodes_ptr;
+    let stack_ptr     = context.stack_ptr;
+    let scope_index   = context.scope_index;
+    let handler_count = context.exception_handlers.len();
 
-                VmOpCode::And => {
-                    let left  = pop!(context, "left");
-                    let right = pop!(context, "right");
-                    karamel_print_level2!("And: {:?} && {:?}", left, right);
+    for argument in arguments {
+        *context.stack_ptr = *argument;
+        context.stack_ptr = context.stack_ptr.add(1);
+    }
 
-                    *context.stack_ptr = VmObject::from(karamel_dbg!(left.is_true()) && karamel_dbg!(right.is_true()));
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+    /* Called function returns to the 'Halt' opcode of the trampoline */
+    context.callback_trampoline[1] = arguments.len() as u8;
+    context.opcodes_ptr = context.callback_trampoline.as_mut_ptr();
+    context.callback_locations.push(opcodes_ptr);
+
+    let result = match reference.execute(context, base) {
+        Ok(()) => match reference.callback {
+            FunctionType::Native(_) | FunctionType::NativeClosure(_) => Ok(()),
+            FunctionType::Opcode => {
+                /* Function location starts with the argument count */
+                context.opcodes_ptr = context.opcodes_ptr.add(1);
+                run_with_handlers(context, handler_count)
+            }
+        },
+        Err(error) => Err(error)
+    };
+
+    let result = match result {
+        Ok(()) => Ok(pop_raw!(context, "callback result")),
+        Err(error) => {
+            /* First failed location is kept while the error is passing through the native functions */
+            if context.is_callback_location(context.opcodes_ptr) {
+                context.opcodes_ptr = opcodes_ptr;
+            }
+            record_error(context);
+            Err(error)
+        }
+    };
+
+    context.callback_locations.pop();
+    context.exception_handlers.truncate(handler_count);
+    context.opcodes_ptr   = opcodes_ptr;
+    context.stack_ptr     = stack_ptr;
+    context.scope_index   = scope_index;
+    context.current_scope = context.scopes_ptr.add(scope_index);
+    result
+}
 
-                VmOpCode::Or => {
-                    let left  = pop!(context, "left");
-                    let right = pop!(context, "right");
-                    karamel_print_level2!("Or: {:?} || {:?}", left, right);
+/* Errors are handled by the 'dene' blocks that started after the given handler count */
+unsafe fn run_with_handlers(context: &mut KaramelCompilerContext, handler_count: usize) -> Result<(), KaramelErrorType> {
+    loop {
+        match run_opcodes(context) {
+            Ok(()) => return Ok(()),
+            Err(error) if context.exception_handlers.len() > handler_count && !error.stops_execution() => {
+                let handler = context.exception_handlers.pop().unwrap();
+                catch_error(context, handler, error)?
+            },
+            Err(error) => return Err(error)
+        };
+    }
+}
 
-                    *context.stack_ptr = VmObject::from(karamel_dbg!(left.is_true()) || karamel_dbg!(right.is_true()));
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+/* Location information is used to show the error */
+unsafe fn record_error(context: &mut KaramelCompilerContext) {
+    if context.stack_trace.is_empty() {
+        context.error_location = context.source_map.find(context.opcodes_ptr as usize - context.opcodes_top_ptr as usize);
+        context.stack_trace = context.build_stack_trace();
+    }
+}
 
-                VmOpCode::Multiply => {
-                    let right = pop!(context, "right");
-                    let left  = pop!(context, "left");
-                    karamel_print_level2!("Multiply: {:?} * {:?}", left, right);
+/* Unsupported operand types produce 'boş', strict mode raises an error. Texts and numbers are not converted
+implicitly, only a text could be repeated by multiplying with a number. Comparisons are not named with the
+operator, '<' is compiled as a swapped '>'. */
+fn type_mismatch(context: &KaramelCompilerContext, operator: &str, left: VmObject, right: VmObject) -> Result<VmObject, KaramelErrorType> {
+    match context.strict_types {
+        true => Err(KaramelErrorType::OperatorTypeMismatch {
+            operator: operator.to_string(),
+            left: left.deref().get_type(),
+            right: right.deref().get_type()
+        }),
+        false => Ok(EMPTY_OBJECT)
+    }
+}
 
-                    *context.stack_ptr = match (&*left, &*right) {
-                        (KaramelPrimative::Number(l_value),  KaramelPrimative::Number(r_value))   => VmObject::from(*l_value * *r_value),
-                        (KaramelPrimative::Text(l_value),    KaramelPrimative::Number(r_value))   => VmObject::from((*l_value).repeat((*r_value) as usize)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+fn unary_type_mismatch(context: &KaramelCompilerContext, operator: &str, value: VmObject) -> Result<VmObject, KaramelErrorType> {
+    match context.strict_types {
+        true => Err(KaramelErrorType::UnaryOperatorTypeMismatch { operator: operator.to_string(), value: value.deref().get_type() }),
+        false => Ok(EMPTY_OBJECT)
+    }
+}
 
-                VmOpCode::Division => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("Division: {:?} / {:?}", left, right);
+/* Empty slice part is defaulted by slice function */
+fn slice_position(value: Rc<KaramelPrimative>) -> Result<Option<f64>, KaramelErrorType> {
+    match &*value {
+        KaramelPrimative::Empty => Ok(None),
+        KaramelPrimative::Number(number) => Ok(Some(*number)),
+        _ => Err(KaramelErrorType::IndexerMustBeNumber(value.clone()))
+    }
+}
 
-                    let calculation = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => (l_value / r_value),
-                        _ => std::f64::NAN
-                    };
+/* Wide prefixed operands are two bytes with little endian order */
+#[inline]
+unsafe fn read_operand(opcodes_ptr: *mut u8, offset: usize, wide: bool) -> usize {
+    match wide {
+        true => u16::from_le_bytes([*opcodes_ptr.add(offset), *opcodes_ptr.add(offset + 1)]) as usize,
+        false => *opcodes_ptr.add(offset) as usize
+    }
+}
 
-                    *context.stack_ptr = if calculation.is_nan() {
-                        EMPTY_OBJECT
-                    }
-                    else {
-                        VmObject::from(calculation)
-                    };
+#[inline]
+unsafe fn read_location(opcodes_ptr: *mut u8, offset: usize) -> usize {
+    u32::from_le_bytes([*opcodes_ptr.add(offset), *opcodes_ptr.add(offset + 1), *opcodes_ptr.add(offset + 2), *opcodes_ptr.add(offset + 3)]) as usize
+}
 
-                    inc_memory_index!(context, 1);
-                },
+/* Unwind scopes and stack to 'dene' block and continue from 'yakala' block */
+unsafe fn catch_error(context: &mut KaramelCompilerContext, handler: ExceptionHandler, error: KaramelErrorType) -> Result<(), KaramelErrorType> {
+    context.scope_index   = handler.scope_index;
+    context.current_scope = context.scopes_ptr.add(handler.scope_index);
+    context.stack_ptr     = handler.stack_ptr;
+    context.opcodes_ptr   = context.opcodes_top_ptr.add(handler.location);
+    context.error_location = None;
+    context.stack_trace.clear();
+
+    if handler.has_variable {
+        let code = match error.get_message() {
+            Some(code) => code.parse::<f64>().unwrap_or_default(),
+            None => 0.0
+        };
 
-                VmOpCode::Module => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("Module: {:?} / {:?}", left, right);
+        let mut error_info = HashMap::new();
+        error_info.insert(ERROR_CODE_NAME.to_string(), VmObject::from(code));
+        error_info.insert(ERROR_MESSAGE_NAME.to_string(), VmObject::from(Rc::new(format!("{}", error))));
 
-                    *context.stack_ptr = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) % karamel_dbg!(r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+        *context.stack_ptr = VmObject::from(error_info);
+        inc_memory_index!(context, 1);
+    }
+    Ok(())
+}
 
-                VmOpCode::Equal => {
-                    let right = pop!(context, "right");
-                    let left  = pop!(context, "left");
-                    karamel_print_level2!("Equal: {:?} == {:?}", left, right);
-                    
-                    *context.stack_ptr = VmObject::from(karamel_dbg!(left) == karamel_dbg!(right));
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+unsafe fn run_opcodes(context: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    #[cfg(any(feature = "liveOpcodeView", feature = "dumpOpcodes"))]
+    let mut log_update = LogUpdate::new(stdout()).unwrap();
 
+    let mut wide = false;
 
-                VmOpCode::NotEqual => {
-                    let right = pop!(context, "right");
-                    let left  = pop!(context, "left");
-                    karamel_print_level2!("NotEqual: {:?} != {:?}", left, right);
-                    
-                    *context.stack_ptr = VmObject::from(karamel_dbg!(left) != karamel_dbg!(right));
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+    loop {
+        /* All alive values are at the stack between opcodes */
+        if context.heap.should_collect() {
+            Heap::collect(context);
+        }
 
-                VmOpCode::GreaterThan => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("GreaterThan: {:?} > {:?}", left, right);
-                    
-                    *context.stack_ptr = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) > karamel_dbg!(r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+        let opcode = mem::transmute::<u8, VmOpCode>(*context.opcodes_ptr);
+        context.limiter.executed_opcodes += 1;
 
-                VmOpCode::GreaterEqualThan => {
-                    let right = pop_raw!(context, "right");
-                    let left = pop_raw!(context, "left");
-                    karamel_print_level2!("GreaterEqualThan {:?} >= {:?}", left, right);
-                    
-                    *context.stack_ptr = match (left.as_number(), right.as_number()) {
-                        (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) >= karamel_dbg!(r_value)),
-                        _ => EMPTY_OBJECT
-                    };
-                    dump_data!(context, "result");
-                    inc_memory_index!(context, 1);
-                },
+        /* Operand size of the current opcode, prefix is valid only for the next opcode */
+        let is_wide = mem::replace(&mut wide, false);
+        let operand_size = if is_wide { 2 } else { 1 };
+        #[cfg(all(feature = "liveOpcodeView"))] {
+            dump_opcode(context.opcode_index, context, &mut log_update);
+        }
+        
+        match karamel_dbg_any!(opcode) {
+            VmOpCode::Subraction => {
+                let right = pop_raw!(context, "right");
+                let left = pop_raw!(context, "left");
+
+                karamel_print_level2!("Subraction: {:?} - {:?}", left, right);
+
+                *context.stack_ptr = match (left.as_number(), right.as_number()) {
+                    (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) - karamel_dbg!(r_value)),
+                    _ => type_mismatch(context, "-", left, right)?
+                };
+                inc_memory_index!(context, 1);
+                dump_data!(context, "result");
+            },
+
+            VmOpCode::Addition => {
+                let right = pop_raw!(context, "right");
+                let left = pop_raw!(context, "left");
+                karamel_print_level2!("Addition: {:?} + {:?}", left, right);
+
+                *context.stack_ptr = match (&left.deref_clean(), &right.deref_clean()) {
+                    (KaramelPrimative::Number(l_value),  KaramelPrimative::Number(r_value)) => VmObject::from(karamel_dbg!(l_value) + karamel_dbg!(r_value)),
+                    (KaramelPrimative::Text(l_value),    KaramelPrimative::Text(r_value))   => VmObject::from(Rc::new((&**l_value).to_owned() + &**r_value)),
+                    _ => type_mismatch(context, "+", left, right)?
+                };
+                dump_data!(context, "result");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Load => {
+                let tmp   = read_operand(context.opcodes_ptr, 1, is_wide);
+                let scope = &mut *context.current_scope;
+                *context.stack_ptr = karamel_dbg!(*scope.top_stack.offset(tmp as isize));
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size);
+                karamel_print_level2!("Load: [{:?}]: {:?}", tmp, *context.stack_ptr);
+                dump_data!(context, "loaded");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Constant => {
+                let tmp   = read_operand(context.opcodes_ptr, 1, is_wide);
+                let scope = &mut *context.current_scope;        
+                *context.stack_ptr = karamel_dbg!(*scope.constant_ptr.offset(tmp as isize));        
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size);
+                karamel_print_level2!("Constant: [{:?}]: {:?}", tmp, *context.stack_ptr);
+                dump_data!(context, "constant loaded");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Store => {
+                let tmp = read_operand(context.opcodes_ptr, 1, is_wide);
+                dec_memory_index!(context, 1);
+                *(*context.current_scope).top_stack.offset(tmp as isize) = karamel_dbg!(*context.stack_ptr);
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size);
+                karamel_print_level2!("Store: [{:?}]: {:?}", tmp, *context.stack_ptr);
+            },
+
+            VmOpCode::CopyToStore => {
+                let tmp = read_operand(context.opcodes_ptr, 1, is_wide);
+                *(*context.current_scope).top_stack.offset(tmp as isize) = karamel_dbg!(*context.stack_ptr.sub(1));
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size);
+                karamel_print_level2!("CopyToStore: [{:?}]: {:?}", tmp, *context.stack_ptr);
+            },
+
+            VmOpCode::FastStore => {
+                let destination = read_operand(context.opcodes_ptr, 1, is_wide);
+                let source      = read_operand(context.opcodes_ptr, 1 + operand_size, is_wide);
+                *(*context.current_scope).top_stack.offset(destination as isize) = karamel_dbg!(*(*context.current_scope).constant_ptr.offset(source as isize));
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size * 2);
+                karamel_print_level2!("FastStore: {:?}: {:?} => {:?}", *(*context.current_scope).top_stack.offset(destination as isize), source, destination);
+            },
+
+            VmOpCode::Move => {
+                let destination = read_operand(context.opcodes_ptr, 1, is_wide);
+                let source      = read_operand(context.opcodes_ptr, 1 + operand_size, is_wide);
+                let scope       = &mut *context.current_scope;
+                *scope.top_stack.offset(destination as isize) = karamel_dbg!(*scope.top_stack.offset(source as isize));
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size * 2);
+                karamel_print_level2!("Move: {:?} => {:?}", source, destination);
+            },
+
+            VmOpCode::Not => {
+                *context.stack_ptr.sub(1) = VmObject::from(!(*context.stack_ptr.sub(1)).deref_clean().is_true());
+                dump_data!(context, "result");
+                karamel_print_level2!("Not: {:?}", *context.stack_ptr.sub(1));
+            },
+
+            VmOpCode::Dublicate => {
+                *context.stack_ptr = karamel_dbg!(*context.stack_ptr.sub(1));
+                karamel_print_level2!("Dublicate: {:?}", *context.stack_ptr);
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::And => {
+                let left  = pop!(context, "left");
+                let right = pop!(context, "right");
+                karamel_print_level2!("And: {:?} && {:?}", left, right);
+
+                *context.stack_ptr = VmObject::from(karamel_dbg!(left.is_true()) && karamel_dbg!(right.is_true()));
+                dump_data!(context, "result");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Or => {
+                let left  = pop!(context, "left");
+                let right = pop!(context, "right");
+                karamel_print_level2!("Or: {:?} || {:?}", left, right);
+
+                *context.stack_ptr = VmObject::from(karamel_dbg!(left.is_true()) || karamel_dbg!(right.is_true()));
+                dump_data!(context, "result");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Multiply => {
+                let right = pop_raw!(context, "right");
+                let left  = pop_raw!(context, "left");
+                karamel_print_level2!("Multiply: {:?} * {:?}", left, right);
+
+                *context.stack_ptr = match (&*left.deref(), &*right.deref()) {
+                    (KaramelPrimative::Number(l_value),  KaramelPrimative::Number(r_value))   => VmObject::from(*l_value * *r_value),
+                    (KaramelPrimative::Text(l_value),    KaramelPrimative::Number(r_value))   => VmObject::from((*l_value).repeat((*r_value) as usize)),
+                    _ => type_mismatch(context, "*", left, right)?
+                };
+                dump_data!(context, "result");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Division => {
+                let right = pop_raw!(context, "right");
+                let left = pop_raw!(context, "left");
+                karamel_print_level2!("Division: {:?} / {:?}", left, right);
+
+                let calculation = match (left.as_number(), right.as_number()) {
+                    (Some(l_value),  Some(r_value))   => (l_value / r_value),
+                    _ => {
+                        type_mismatch(context, "/", left, right)?;
+                        std::f64::NAN
+                    }
+                };
+
+                *context.stack_ptr = if calculation.is_nan() {
+                    EMPTY_OBJECT
+                }
+                else {
+                    VmObject::from(calculation)
+                };
+
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Module => {
+                let right = pop_raw!(context, "right");
+                let left = pop_raw!(context, "left");
+                karamel_print_level2!("Module: {:?} / {:?}", left, right);
+
+                *context.stack_ptr = match (left.as_number(), right.as_number()) {
+                    (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) % karamel_dbg!(r_value)),
+                    _ => type_mismatch(context, "mod", left, right)?
+                };
+                dump_data!(context, "result");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Equal => {
+                let right = pop!(context, "right");
+                let left  = pop!(context, "left");
+                karamel_print_level2!("Equal: {:?} == {:?}", left, right);
+                
+                *context.stack_ptr = VmObject::from(karamel_dbg!(left) == karamel_dbg!(right));
+                dump_data!(context, "result");
+                inc_memory_index!(context, 1);
+            },
 
-                VmOpCode::Call => {
-                    let func_location   = *context.opcodes_ptr.offset(1) as usize;
-                    context.opcodes_ptr = context.opcodes_ptr.offset(1);
-                    
-                    let value = (*(*context.current_scope).constant_ptr.offset(func_location as isize)).deref();
 
-                    karamel_print_level2!("Call: {:?}", value);
-                    if let KaramelPrimative::Function(reference, _) = karamel_dbg!(&*value) {
-                        reference.execute(context, None)?;
-                    }
-                    else {
-                        return Err(KaramelErrorType::NotCallable(value.clone()));
+            VmOpCode::NotEqual => {
+                let right = pop!(context, "right");
+                let left  = pop!(context, "left");
+                karamel_print_level2!("NotEqual: {:?} != {:?}", left, right);
+                
+                *context.stack_ptr = VmObject::from(karamel_dbg!(left) != karamel_dbg!(right));
+                dump_data!(context, "result");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::GreaterThan => {
+                let right = pop_raw!(context, "right");
+                let left = pop_raw!(context, "left");
+                karamel_print_level2!("GreaterThan: {:?} > {:?}", left, right);
+                
+                *context.stack_ptr = match (left.as_number(), right.as_number()) {
+                    (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) > karamel_dbg!(r_value)),
+                    _ => type_mismatch(context, "karşılaştırma", left, right)?
+                };
+                dump_data!(context, "result");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::GreaterEqualThan => {
+                let right = pop_raw!(context, "right");
+                let left = pop_raw!(context, "left");
+                karamel_print_level2!("GreaterEqualThan {:?} >= {:?}", left, right);
+                
+                *context.stack_ptr = match (left.as_number(), right.as_number()) {
+                    (Some(l_value),  Some(r_value))   => VmObject::from(karamel_dbg!(l_value) >= karamel_dbg!(r_value)),
+                    _ => type_mismatch(context, "karşılaştırma", left, right)?
+                };
+                dump_data!(context, "result");
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Call => {
+                check_limits(context)?;
+                let func_location   = read_operand(context.opcodes_ptr, 1, is_wide);
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size);
+                
+                let value = (*(*context.current_scope).constant_ptr.offset(func_location as isize)).deref();
+
+                karamel_print_level2!("Call: {:?}", value);
+                if let KaramelPrimative::Function(reference, _) = karamel_dbg!(&*value) {
+                    reference.execute(context, None)?;
+                }
+                else {
+                    return Err(KaramelErrorType::NotCallable(value.clone()));
+                }
+            },
+
+            VmOpCode::CallStack => {
+                check_limits(context)?;
+                let function = pop_raw!(context, "function");
+                let value =  function.deref();
+                karamel_print_level2!("CallStack {:?}", value);
+                
+                match &*value {
+                    KaramelPrimative::Function(reference, base) => reference.execute(context, *base)?,
+                    _ => {
+                        log::debug!("{:?} not callable", &*function.deref());
+                    return Err(KaramelErrorType::NotCallable(value.clone()));
                     }
-                },
+                };
+            },
+
+            VmOpCode::Return => {
+                let return_value               = *context.stack_ptr.sub(1);
+                context.opcodes_ptr            = (*context.current_scope).location;
+                let call_return_assign_to_temp = (*context.current_scope).call_return_assign_to_temp;
+                context.scope_index           -= 1;
+
+                /* 'dene' blocks inside the function are not active anymore */
+                while matches!(context.exception_handlers.last(), Some(handler) if handler.scope_index > context.scope_index) {
+                    context.exception_handlers.pop();
+                }
+
+                context.stack_ptr = (*context.current_scope).top_stack;
+                context.current_scope          = context.scopes_ptr.add(context.scope_index);              
+
+                if call_return_assign_to_temp {
+                    *context.stack_ptr = return_value;
+                    karamel_print_level2!("Return [{:?}] {:?}", get_memory_index!(context), *context.stack_ptr);
+                    inc_memory_index!(context, 1);
+                } else {
+                    karamel_print_level2!("Return");
+                }
+            },
+
+            VmOpCode::Increment => {
+                karamel_print_level2!("Increment");
+                *context.stack_ptr.sub(1) = match (*context.stack_ptr.sub(1)).as_number() {
+                    Some(value) => VmObject::from(karamel_dbg!(value + 1 as f64)),
+                    _ => unary_type_mismatch(context, "++", *context.stack_ptr.sub(1))?
+                };
+            },
+
+            VmOpCode::Decrement => {
+                karamel_print_level2!("Increment");
+                *context.stack_ptr.sub(1) = match (*context.stack_ptr.sub(1)).as_number() {
+                    Some(value) => VmObject::from(value - 1 as f64),
+                    _ => unary_type_mismatch(context, "--", *context.stack_ptr.sub(1))?
+                };
+            },
+
+            VmOpCode::Init => {
+                let init_type = *context.opcodes_ptr.offset(1) as usize;
+                let total_item = read_operand(context.opcodes_ptr, 2, is_wide);
+                karamel_print_level2!("Init: {:?} {:?}", init_type, total_item);
+
+                *context.stack_ptr = match init_type {
+                    // Dict
+                    0 => {
+                        let mut dict   = HashMap::new();
+    
+                        for _ in 0..total_item {
+                            let value = pop_raw!(context, "value");
+                            let key   = pop!(context, "key");
+                            
+                            dict.insert(key.get_text(), value);
+                        }
 
-                VmOpCode::CallStack => {
-                    let function = pop_raw!(context, "function");
-                    let value =  function.deref();
-                    karamel_print_level2!("CallStack {:?}", value);
-                    
-                    match &*value {
-                        KaramelPrimative::Function(reference, base) => reference.execute(context, *base)?,
-                        _ => {
-                            log::debug!("{:?} not callable", &*function.deref());
-                        return Err(KaramelErrorType::NotCallable(value.clone()));
+                        VmObject::from(dict)
+                    },
+
+                    // List
+                    1 => {
+                        let mut list = Vec::with_capacity(total_item.into());
+
+                        for i in 0..total_item {
+                            list.push(pop_raw!(context, i));
                         }
-                    };
-                },
-
-                VmOpCode::Return => {
-                    let return_value               = *context.stack_ptr.sub(1);
-                    context.opcodes_ptr            = (*context.current_scope).location;
-                    let call_return_assign_to_temp = (*context.current_scope).call_return_assign_to_temp;
-                    context.scope_index           -= 1;
-
-                    context.stack_ptr = (*context.current_scope).top_stack;
-                    context.current_scope          = context.scopes_ptr.add(context.scope_index);              
-
-                    if call_return_assign_to_temp {
-                        *context.stack_ptr = return_value;
-                        karamel_print_level2!("Return [{:?}] {:?}", get_memory_index!(context), *context.stack_ptr);
-                        inc_memory_index!(context, 1);
-                    } else {
-                        karamel_print_level2!("Return");
-                    }
-                },
+                        
+                        VmObject::from(list)
+                    },
 
-                VmOpCode::Increment => {
-                    karamel_print_level2!("Increment");
-                    *context.stack_ptr.sub(1) = match (*context.stack_ptr.sub(1)).as_number() {
-                        Some(value) => VmObject::from(karamel_dbg!(value + 1 as f64)),
-                        _ => EMPTY_OBJECT
-                    };
-                },
+                    // Class instance
+                    2 => {
+                        let class = pop!(context, "class");
+                        let mut fields = HashMap::new();
 
-                VmOpCode::Decrement => {
-                    karamel_print_level2!("Increment");
-                    *context.stack_ptr.sub(1) = match (*context.stack_ptr.sub(1)).as_number() {
-                        Some(value) => VmObject::from(value - 1 as f64),
-                        _ => EMPTY_OBJECT
-                    };
-                },
-
-                VmOpCode::Init => {
-                    let init_type = *context.opcodes_ptr.offset(1) as usize;
-                    let total_item = *context.opcodes_ptr.offset(2) as usize;
-                    karamel_print_level2!("Init: {:?} {:?}", init_type, total_item);
-
-                    *context.stack_ptr = match init_type {
-                        // Dict
-                        0 => {
-                            let mut dict   = HashMap::new();
-        
-                            for _ in 0..total_item {
-                                let value = pop_raw!(context, "value");
-                                let key   = pop!(context, "key");
-                                
-                                dict.insert(key.get_text(), value);
-                            }
-
-                            VmObject::from(dict)
-                        },
-
-                        // List
-                        1 => {
-                            let mut list = Vec::with_capacity(total_item.into());
-
-                            for i in 0..total_item {
-                                list.push(pop_raw!(context, i));
-                            }
-                            
-                            VmObject::from(list)
-                        },
-                         _ => return Err(KaramelErrorType::GeneralError("Geçersiz yükleme tipi".to_string()))
-                    };
-                    
-                    inc_memory_index!(context, 1);
-                    context.opcodes_ptr = context.opcodes_ptr.offset(2);
-                },
-
-                VmOpCode::Compare => {
-                    let condition = pop_raw!(context, "condition");
-                    karamel_print_level2!("Compare: {:?}", condition);
-
-                    let status = match &condition.deref_clean() {
-                        KaramelPrimative::Empty => false,
-                        KaramelPrimative::Bool(l_value) => *l_value,
-                        KaramelPrimative::Number(l_value) => *l_value > 0.0,
-                        KaramelPrimative::Text(l_value) => !(*l_value).is_empty(),
-                        _ => false
-                    };
-
-                    if status {
-                        context.opcodes_ptr = context.opcodes_ptr.offset(2);
-                    }
-                    else {
-                        let location = ((*context.opcodes_ptr.offset(2) as u16 * 256) + *context.opcodes_ptr.offset(1) as u16) as usize;
-                        context.opcodes_ptr = context.opcodes_ptr.offset(location as isize);
-                        continue;
-                    }
-                },
+                        for _ in 0..total_item {
+                            let value = pop_raw!(context, "value");
+                            let key   = pop!(context, "key");
 
-                VmOpCode::Jump => {
-                    let location = ((*context.opcodes_ptr.offset(2)  as u16 * 256) + *context.opcodes_ptr.offset(1)  as u16) as usize;
-                    karamel_print_level2!("Jump: {:?}", location);
-                    context.opcodes_ptr = context.opcodes.as_mut_ptr().offset(location as isize);
-                    continue;
-                },
-                
-                VmOpCode::SetItem => {
-                    let assign_item  = pop_raw!(context, "assign_item");
-                    let indexer = pop!(context, "indexer");
-                    let raw_object = pop_raw!(context, "raw_object");
-                    let object  = raw_object.deref();
-                    karamel_print_level2!("GetItem: object={:?}, indexer={:?}, item={:?}", object, indexer, assign_item);
-
-                    // todo: change all those codes with setter implementation
-                    match &*object {
-                        KaramelPrimative::List(value) => {
-                            let indexer_value = match &*indexer {
-                                KaramelPrimative::Number(number) => *number as usize,
-                                _ => return Err(KaramelErrorType::IndexerMustBeNumber(indexer.clone()))
-                            };
+                            fields.insert(key.get_text(), value);
+                        }
 
-                            value.borrow_mut()[indexer_value] = assign_item;
-                        },
-                        KaramelPrimative::Dict(value) => {
-                            let indexer_value = match &*indexer {
-                                KaramelPrimative::Text(text) => &*text,
-                                _ => return Err(KaramelErrorType::IndexerMustBeString(indexer.clone()))
-                            };
+                        match &*class {
+                            KaramelPrimative::Class(class) => VmObject::native_convert(KaramelPrimative::Class(Rc::new(ClassInstance::new(class.clone(), fields)))),
+                            _ => return Err(KaramelErrorType::GeneralError("Geçersiz sınıf".to_string()))
+                        }
+                    },
+
+                    // Iterator, total item is used as pair flag
+                    3 => {
+                        let raw_source = pop_raw!(context, "source");
+                        match &*raw_source.deref() {
+                            KaramelPrimative::List(_) => raw_source,
+                            KaramelPrimative::Text(text) => VmObject::from(text.chars().map(|letter| VmObject::native_convert(KaramelPrimative::Text(Rc::new(letter.to_string())))).collect::<Vec<VmObject>>()),
+                            KaramelPrimative::Dict(dict) => {
+                                let dict = dict.borrow();
+                                let mut keys = dict.keys().collect::<Vec<&String>>();
+                                keys.sort();
+
+                                VmObject::from(keys.iter().map(|key| {
+                                    let key_object = VmObject::native_convert(KaramelPrimative::Text(Rc::new(key.to_string())));
+                                    match total_item {
+                                        0 => key_object,
+                                        _ => VmObject::from(vec![key_object, dict[*key]])
+                                    }
+                                }).collect::<Vec<VmObject>>())
+                            },
+                            other => return Err(KaramelErrorType::ValueNotIterable(other.get_type()))
+                        }
+                    },
 
-                            value.borrow_mut().insert(indexer_value.to_string(), assign_item);
-                        },
-                        KaramelPrimative::Text(_) => {
-                            let indexer_value = match &*indexer {
-                                KaramelPrimative::Number(number) => *number,
-                                _ => return Err(KaramelErrorType::IndexerMustBeNumber(indexer.clone()))
-                            };
+                    // Closure, captured values are stored as function base
+                    4 => {
+                        let function = pop!(context, "function");
+                        let mut captures = Vec::with_capacity(total_item);
 
-                            match context.get_class(&object).get_setter() {
-                                Some(function) => function(raw_object, indexer_value, assign_item)?,
-                                _ => EMPTY_OBJECT
-                            };
-                        },
-                        
-                        _ => ()
-                    };
-                },
-
-                VmOpCode::GetItem => {
-                    let indexer = pop!(context, "indexer");
-                    let raw_object  = pop_raw!(context, "raw_object");
-                    let object = &*raw_object.deref();
-                    karamel_print_level2!("GetItem: object={:?}, indexer={:?}", object, indexer);
-
-                    *context.stack_ptr = match &*indexer {
-                        KaramelPrimative::Text(text) => {
-                             match context.get_class(object).get_element(Some(raw_object), text.clone()) {
-                                Some(element) => match element {
-                                    ClassProperty::Function(function) => VmObject::from(Rc::new(KaramelPrimative::Function(function.clone(), Some(raw_object)))),
-                                    ClassProperty::Field(field) => VmObject::from(field.clone())
+                        for _ in 0..total_item {
+                            captures.push(pop_raw!(context, "capture"));
+                        }
+                        captures.reverse();
+
+                        match &*function {
+                            KaramelPrimative::Function(reference, _) => VmObject::native_convert(KaramelPrimative::Function(reference.clone(), Some(VmObject::from(captures)))),
+                            _ => return Err(KaramelErrorType::NotCallable(function.clone()))
+                        }
+                    },
+                     _ => return Err(KaramelErrorType::GeneralError("Geçersiz yükleme tipi".to_string()))
+                };
+                
+                inc_memory_index!(context, 1);
+                context.opcodes_ptr = context.opcodes_ptr.add(1 + operand_size);
+            },
+
+            VmOpCode::Compare => {
+                let condition = pop_raw!(context, "condition");
+                karamel_print_level2!("Compare: {:?}", condition);
+
+                let status = match &condition.deref_clean() {
+                    KaramelPrimative::Empty => false,
+                    KaramelPrimative::Bool(l_value) => *l_value,
+                    KaramelPrimative::Number(l_value) => *l_value > 0.0,
+                    KaramelPrimative::Text(l_value) => !(*l_value).is_empty(),
+                    _ => false
+                };
+
+                if status {
+                    context.opcodes_ptr = context.opcodes_ptr.add(OPCODE_LOCATION_SIZE);
+                }
+                else {
+                    let location = read_location(context.opcodes_ptr, 1);
+                    context.opcodes_ptr = context.opcodes_ptr.add(location);
+                    continue;
+                }
+            },
+
+            VmOpCode::Iterate => {
+                let source_location = read_operand(context.opcodes_ptr, 1, is_wide) as isize;
+                let index_location = read_operand(context.opcodes_ptr, 1 + operand_size, is_wide) as isize;
+                let pair = *context.opcodes_ptr.add(1 + operand_size * 2) == 1;
+                let top_stack = (*context.current_scope).top_stack;
+
+                let index = (*top_stack.offset(index_location)).as_number().unwrap_or(0.0) as usize;
+                let item = match &*(*top_stack.offset(source_location)).deref() {
+                    KaramelPrimative::List(items) => items.borrow().get(index).copied(),
+                    _ => None
+                };
+                karamel_print_level2!("Iterate: {:?} {:?}", index, item);
+
+                match item {
+                    Some(item) => {
+                        if pair {
+                            match &*item.deref() {
+                                KaramelPrimative::List(values) if values.borrow().len() == 2 => {
+                                    let values = values.borrow();
+                                    *context.stack_ptr = values[0];
+                                    inc_memory_index!(context, 1);
+                                    *context.stack_ptr = values[1];
+                                    inc_memory_index!(context, 1);
                                 },
-                                _ => EMPTY_OBJECT
-                            }
-                        },
-                        KaramelPrimative::Number(index) => match context.get_class(object).get_getter() {
-                            Some(function) => function(raw_object, *index)?,
+                                _ => return Err(KaramelErrorType::LoopValueCouldNotUnpack)
+                            };
+                        }
+                        else {
+                            *context.stack_ptr = item;
+                            inc_memory_index!(context, 1);
+                        }
+
+                        *top_stack.offset(index_location) = VmObject::from((index + 1) as f64);
+                        context.opcodes_ptr = context.opcodes_ptr.add(1 + operand_size * 2 + OPCODE_LOCATION_SIZE);
+                    },
+                    None => {
+                        /* Location is calculated from the start of the wide prefix */
+                        let location = read_location(context.opcodes_ptr, 2 + operand_size * 2);
+                        context.opcodes_ptr = context.opcodes_ptr.add(location).sub(is_wide as usize);
+                        continue;
+                    }
+                };
+            },
+
+            VmOpCode::Jump => {
+                let location = read_location(context.opcodes_ptr, 1);
+                karamel_print_level2!("Jump: {:?}", location);
+
+                /* Every infinite loop has a backward jump */
+                if context.opcodes_top_ptr.add(location) <= context.opcodes_ptr {
+                    check_limits(context)?;
+                }
+                context.opcodes_ptr = context.opcodes.as_mut_ptr().offset(location as isize);
+                continue;
+            },
+            
+            VmOpCode::SetItem => {
+                let assign_item  = pop_raw!(context, "assign_item");
+                let indexer = pop!(context, "indexer");
+                let raw_object = pop_raw!(context, "raw_object");
+                let object  = raw_object.deref();
+                karamel_print_level2!("GetItem: object={:?}, indexer={:?}, item={:?}", object, indexer, assign_item);
+
+                // todo: change all those codes with setter implementation
+                match &*object {
+                    KaramelPrimative::List(_) => {
+                        let indexer_value = match &*indexer {
+                            KaramelPrimative::Number(number) => *number,
+                            _ => return Err(KaramelErrorType::IndexerMustBeNumber(indexer.clone()))
+                        };
+
+                        match context.get_class(&object).get_setter() {
+                            Some(function) => function(raw_object, indexer_value, assign_item)?,
+                            _ => EMPTY_OBJECT
+                        };
+                    },
+                    KaramelPrimative::Dict(value) => {
+                        let indexer_value = match &*indexer {
+                            KaramelPrimative::Text(text) => &*text,
+                            _ => return Err(KaramelErrorType::IndexerMustBeString(indexer.clone()))
+                        };
+
+                        value.borrow_mut().insert(indexer_value.to_string(), assign_item);
+                    },
+                    KaramelPrimative::Text(_) => {
+                        let indexer_value = match &*indexer {
+                            KaramelPrimative::Number(number) => *number,
+                            _ => return Err(KaramelErrorType::IndexerMustBeNumber(indexer.clone()))
+                        };
+
+                        match context.get_class(&object).get_setter() {
+                            Some(function) => function(raw_object, indexer_value, assign_item)?,
+                            _ => EMPTY_OBJECT
+                        };
+                    },
+                    KaramelPrimative::Class(_) => {
+                        let indexer_value = match &*indexer {
+                            KaramelPrimative::Text(text) => text.clone(),
+                            _ => return Err(KaramelErrorType::IndexerMustBeString(indexer.clone()))
+                        };
+
+                        context.get_class(&object).set_element(Some(raw_object), indexer_value, assign_item);
+                    },
+                    
+                    _ => ()
+                };
+            },
+
+            VmOpCode::GetItem => {
+                let indexer = pop!(context, "indexer");
+                let raw_object  = pop_raw!(context, "raw_object");
+                let object = &*raw_object.deref();
+                karamel_print_level2!("GetItem: object={:?}, indexer={:?}", object, indexer);
+
+                *context.stack_ptr = match &*indexer {
+                    KaramelPrimative::Text(text) => {
+                         match context.get_class(object).get_element(Some(raw_object), text.clone()) {
+                            Some(element) => match element {
+                                ClassProperty::Function(function) => VmObject::from(Rc::new(KaramelPrimative::Function(function.clone(), Some(raw_object)))),
+                                ClassProperty::Field(field) => VmObject::from(field.clone())
+                            },
                             _ => EMPTY_OBJECT
                         }
+                    },
+                    KaramelPrimative::Number(index) => match context.get_class(object).get_getter() {
+                        Some(function) => function(raw_object, *index)?,
                         _ => EMPTY_OBJECT
-                    };
-
-                    inc_memory_index!(context, 1);
-                },
-
-                VmOpCode::Halt => {
-                    karamel_print_level2!("Halt");
-                    break;
-                },
-            }
-
-            context.opcodes_ptr = context.opcodes_ptr.offset(1);
-        }
-        
-        if dump_memory {
-            let dump = context.storages[0].dump();
-            context.memory_dump = Some(dump);
+                    }
+                    _ => EMPTY_OBJECT
+                };
+
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::Slice => {
+                let step = slice_position(pop!(context, "step"))?;
+                let end = slice_position(pop!(context, "end"))?;
+                let start = slice_position(pop!(context, "start"))?;
+                let raw_object = pop_raw!(context, "raw_object");
+                karamel_print_level2!("Slice: object={:?}, start={:?}, end={:?}, step={:?}", raw_object, start, end, step);
+
+                *context.stack_ptr = match &*raw_object.deref() {
+                    KaramelPrimative::List(list) => {
+                        let list = list.borrow();
+                        let indices = slice_indices(list.len(), start, end, step)?;
+                        VmObject::from(indices.into_iter().map(|index| list[index]).collect::<Vec<VmObject>>())
+                    },
+                    KaramelPrimative::Text(text) => {
+                        let chars = text.chars().collect::<Vec<char>>();
+                        let indices = slice_indices(chars.len(), start, end, step)?;
+                        VmObject::from(indices.into_iter().map(|index| chars[index]).collect::<String>())
+                    },
+                    other => return Err(KaramelErrorType::SliceNotSupported(other.get_type()))
+                };
+
+                inc_memory_index!(context, 1);
+            },
+
+            VmOpCode::DefaultArgument => {
+                let location = *context.opcodes_ptr.offset(1) as u64;
+                let missing = (*context.current_scope).missing_arguments & (1 << location) != 0;
+                karamel_print_level2!("DefaultArgument: {:?} {:?}", location, missing);
+
+                *context.stack_ptr = VmObject::from(missing);
+                inc_memory_index!(context, 1);
+                context.opcodes_ptr = context.opcodes_ptr.offset(1);
+            },
+
+            VmOpCode::MatchList => {
+                let size = read_operand(context.opcodes_ptr, 1, is_wide);
+                let has_rest = *context.opcodes_ptr.add(1 + operand_size) != 0;
+                let value = pop!(context, "value");
+                karamel_print_level2!("MatchList: {:?} {:?} {:?}", value, size, has_rest);
+
+                *context.stack_ptr = VmObject::from(match &*value {
+                    KaramelPrimative::List(list) => list.borrow().len() == size || (has_rest && list.borrow().len() > size),
+                    _ => false
+                });
+                inc_memory_index!(context, 1);
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size + 1);
+            },
+
+            VmOpCode::MatchDict => {
+                let size = read_operand(context.opcodes_ptr, 1, is_wide);
+                let value = pop!(context, "value");
+                karamel_print_level2!("MatchDict: {:?} {:?}", value, size);
+
+                let mut found = matches!(&*value, KaramelPrimative::Dict(_));
+                for _ in 0..size {
+                    let key = pop!(context, "key");
+                    if let (KaramelPrimative::Dict(dict), KaramelPrimative::Text(key)) = (&*value, &*key) {
+                        found &= dict.borrow().contains_key(&key[..]);
+                    }
+                }
+
+                *context.stack_ptr = VmObject::from(found);
+                inc_memory_index!(context, 1);
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size);
+            },
+
+            VmOpCode::TryStart => {
+                let has_variable = *context.opcodes_ptr.offset(1) != 0;
+                let location = read_location(context.opcodes_ptr, 2);
+                karamel_print_level2!("TryStart: {:?} {:?}", has_variable, location);
+
+                context.exception_handlers.push(ExceptionHandler {
+                    scope_index: context.scope_index,
+                    stack_ptr: context.stack_ptr,
+                    location,
+                    has_variable
+                });
+                context.opcodes_ptr = context.opcodes_ptr.add(1 + OPCODE_LOCATION_SIZE);
+            },
+
+            VmOpCode::TryEnd => {
+                karamel_print_level2!("TryEnd");
+                context.exception_handlers.pop();
+            },
+
+            VmOpCode::Throw => {
+                let value = pop!(context, "error");
+                karamel_print_level2!("Throw: {:?}", value);
+
+                /* Dictionary could have error message information */
+                let value = match &*value {
+                    KaramelPrimative::Dict(dict) => match dict.borrow().get(ERROR_MESSAGE_NAME) {
+                        Some(message) => message.deref(),
+                        None => value.clone()
+                    },
+                    _ => value.clone()
+                };
+
+                let message = match &*value {
+                    KaramelPrimative::Text(text) => text.to_string(),
+                    _ => format!("{}", value)
+                };
+                return Err(KaramelErrorType::UserError(message));
+            },
+
+            VmOpCode::Concat => {
+                let total_item = read_operand(context.opcodes_ptr, 1, is_wide);
+                karamel_print_level2!("Concat: {:?}", total_item);
+
+                let mut items = Vec::with_capacity(total_item);
+                for _ in 0..total_item {
+                    items.push(pop!(context, "item").to_display_text());
+                }
+
+                items.reverse();
+                *context.stack_ptr = VmObject::from(items.concat());
+                inc_memory_index!(context, 1);
+                context.opcodes_ptr = context.opcodes_ptr.add(operand_size);
+            },
+
+            VmOpCode::Wide => {
+                karamel_print_level2!("Wide");
+                wide = true;
+            },
+
+            VmOpCode::Halt => {
+                karamel_print_level2!("Halt");
+                return Ok(());
+            },
         }
-    }
-    
-    let mut result = Vec::with_capacity(get_memory_index!(context) as usize);
-    for index in 0..get_memory_index!(context) {
-        result.push(*top_stack.add(context.storages[0].variables.len() + index as usize));
-    }
 
-    Ok(result)
-}
\ No newline at end of file
+        context.opcodes_ptr = context.opcodes_ptr.offset(1);
+    }
+}
@@ -0,0 +1,101 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+/// Input and output of the programs, 'gç' module reads and writes with it. Applications could give their own
+/// implementation to stream the output or to give the input of the interactive programs.
+pub trait KaramelIo {
+    /// Next line of the input without the line ending, 'None' if there is no input
+    fn read_line(&self) -> Option<String>;
+    fn write(&self, data: &str);
+    fn write_err(&self, data: &str);
+}
+
+/// Standard input and output of the process
+#[derive(Default)]
+pub struct StandardIo;
+
+impl KaramelIo for StandardIo {
+    fn read_line(&self) -> Option<String> {
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end_matches(&['\r', '\n'][..]).to_string())
+        }
+    }
+
+    fn write(&self, data: &str) {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(data.as_bytes());
+        let _ = stdout.flush();
+    }
+
+    fn write_err(&self, data: &str) {
+        let _ = io::stderr().write_all(data.as_bytes());
+    }
+}
+
+/// Output is kept at the buffers. Input is read from the given lines, standard input is used if the lines are not given.
+#[derive(Default)]
+pub struct BufferedIo {
+    input: Option<RefCell<VecDeque<String>>>,
+    stdout: RefCell<String>,
+    stderr: RefCell<String>
+}
+
+impl BufferedIo {
+    pub fn new() -> Self {
+        BufferedIo::default()
+    }
+
+    pub fn with_input<T: ToString>(lines: &[T]) -> Self {
+        BufferedIo {
+            input: Some(RefCell::new(lines.iter().map(|line| line.to_string()).collect())),
+            ..BufferedIo::default()
+        }
+    }
+
+    pub fn stdout(&self) -> String {
+        self.stdout.borrow().to_string()
+    }
+
+    pub fn stderr(&self) -> String {
+        self.stderr.borrow().to_string()
+    }
+}
+
+impl KaramelIo for BufferedIo {
+    fn read_line(&self) -> Option<String> {
+        match &self.input {
+            Some(input) => input.borrow_mut().pop_front(),
+            None => StandardIo.read_line()
+        }
+    }
+
+    fn write(&self, data: &str) {
+        self.stdout.borrow_mut().push_str(data);
+    }
+
+    fn write_err(&self, data: &str) {
+        self.stderr.borrow_mut().push_str(data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffered_io() {
+        let io = BufferedIo::with_input(&["ilk", "ikinci"]);
+        assert_eq!(io.read_line(), Some("ilk".to_string()));
+        assert_eq!(io.read_line(), Some("ikinci".to_string()));
+        assert_eq!(io.read_line(), None);
+
+        io.write("merhaba ");
+        io.write("dünya");
+        io.write_err("hata");
+        assert_eq!(io.stdout(), "merhaba dünya");
+        assert_eq!(io.stderr(), "hata");
+    }
+}
@@ -0,0 +1,193 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use lazy_static::*;
+
+use crate::compiler::context::KaramelCompilerContext;
+use crate::constants::TIME_LIMIT_CHECK_INTERVAL;
+use crate::error::KaramelErrorType;
+use crate::vm::heap::Heap;
+
+lazy_static! {
+    static ref CLOCK_START: Instant = Instant::now();
+}
+
+/// Time since an unspecified point, only the difference between two calls is used
+pub type Clock = fn() -> Duration;
+
+/* 'Instant' is not supported by every target, web build gives its own clock */
+pub fn system_clock() -> Duration {
+    CLOCK_START.elapsed()
+}
+
+/// Optional limits for running untrusted code. Limits are checked at the backward jumps and the function calls.
+#[derive(Clone, Copy)]
+pub struct ExecutionLimits {
+    pub max_opcodes: Option<u64>,
+    pub max_call_depth: Option<usize>,
+    pub max_heap_objects: Option<usize>,
+    pub timeout: Option<Duration>,
+    pub clock: Clock
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        ExecutionLimits {
+            max_opcodes: None,
+            max_call_depth: None,
+            max_heap_objects: None,
+            timeout: None,
+            clock: system_clock
+        }
+    }
+}
+
+impl ExecutionLimits {
+    pub fn is_empty(&self) -> bool {
+        self.max_opcodes.is_none() && self.max_heap_objects.is_none() && self.timeout.is_none()
+    }
+}
+
+/// Shared flag to stop the running code from an another thread. Code stops at the next backward jump or function call.
+#[derive(Clone, Default)]
+pub struct InterruptHandle(Arc<AtomicBool>);
+
+impl InterruptHandle {
+    pub fn new() -> Self {
+        InterruptHandle::default()
+    }
+
+    pub fn interrupt(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Handle stays interrupted until it is reset
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_interrupted(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Execution counters for the active limits
+#[derive(Default)]
+pub struct ExecutionLimiter {
+    pub limits: ExecutionLimits,
+    pub executed_opcodes: u64,
+    enabled: bool,
+    started: Duration,
+    checks: u32
+}
+
+impl ExecutionLimiter {
+    pub fn new(limits: ExecutionLimits) -> Self {
+        ExecutionLimiter {
+            limits,
+            executed_opcodes: 0,
+            enabled: !limits.is_empty(),
+            started: Duration::default(),
+            checks: 0
+        }
+    }
+
+    /// Reset counters before the execution
+    pub fn start(&mut self) {
+        self.executed_opcodes = 0;
+        self.checks = 0;
+        if self.limits.timeout.is_some() {
+            self.started = (self.limits.clock)();
+        }
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn check_time(&mut self) -> Result<(), KaramelErrorType> {
+        /* Clock could be expensive, it is not asked at every check */
+        self.checks = self.checks.wrapping_add(1);
+        if self.checks % TIME_LIMIT_CHECK_INTERVAL != 0 {
+            return Ok(());
+        }
+
+        match self.limits.timeout {
+            Some(timeout) if (self.limits.clock)().saturating_sub(self.started) > timeout => Err(KaramelErrorType::TimeLimitExceeded(timeout.as_millis())),
+            _ => Ok(())
+        }
+    }
+}
+
+/// Check the interrupt request, opcode budget, heap object count and the execution time
+pub unsafe fn check_limits(context: &mut KaramelCompilerContext) -> Result<(), KaramelErrorType> {
+    if context.interrupt.is_interrupted() {
+        return Err(KaramelErrorType::Interrupted);
+    }
+
+    if !context.limiter.is_enabled() {
+        return Ok(());
+    }
+
+    if let Some(max_opcodes) = context.limiter.limits.max_opcodes {
+        if context.limiter.executed_opcodes > max_opcodes {
+            return Err(KaramelErrorType::OpcodeBudgetExceeded(max_opcodes));
+        }
+    }
+
+    if let Some(max_heap_objects) = context.limiter.limits.max_heap_objects {
+        /* Unreachable objects are not counted */
+        if context.heap.len() > max_heap_objects {
+            Heap::collect(context);
+            if context.heap.len() > max_heap_objects {
+                return Err(KaramelErrorType::HeapLimitExceeded(max_heap_objects));
+            }
+        }
+    }
+
+    context.limiter.check_time()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    thread_local! {
+        static NOW: Cell<u64> = Cell::new(0);
+    }
+
+    fn test_clock() -> Duration {
+        Duration::from_millis(NOW.with(|now| now.get()))
+    }
+
+    #[test]
+    fn time_limit() {
+        let mut limiter = ExecutionLimiter::new(ExecutionLimits {
+            timeout: Some(Duration::from_millis(100)),
+            clock: test_clock,
+            ..ExecutionLimits::default()
+        });
+
+        NOW.with(|now| now.set(1000));
+        limiter.start();
+        NOW.with(|now| now.set(1100));
+
+        for _ in 0..TIME_LIMIT_CHECK_INTERVAL {
+            assert!(limiter.check_time().is_ok());
+        }
+
+        NOW.with(|now| now.set(1101));
+        let result = (0..TIME_LIMIT_CHECK_INTERVAL).map(|_| limiter.check_time()).find(|result| result.is_err());
+        assert_eq!(result, Some(Err(KaramelErrorType::TimeLimitExceeded(100))));
+    }
+
+    #[test]
+    fn disabled_limits() {
+        assert!(!ExecutionLimiter::new(ExecutionLimits::default()).is_enabled());
+        assert!(!ExecutionLimiter::new(ExecutionLimits { max_call_depth: Some(10), ..ExecutionLimits::default() }).is_enabled());
+        assert!(ExecutionLimiter::new(ExecutionLimits { max_opcodes: Some(10), ..ExecutionLimits::default() }).is_enabled());
+    }
+}
@@ -1,2 +1,7 @@
 pub mod interpreter;
-pub mod executer;
\ No newline at end of file
+pub mod executer;
+pub mod heap;
+pub mod limits;
+pub mod convert;
+pub mod engine;
+pub mod io;
@@ -0,0 +1,94 @@
+extern crate karamellib;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::parser::*;
+    use crate::karamellib::compiler::*;
+    use crate::karamellib::vm::*;
+    use crate::karamellib::syntax::*;
+    use crate::karamellib::error::KaramelErrorType;
+    use crate::karamellib::constants::{MAX_ARGUMENT_SIZE, MAX_ITEM_SIZE};
+
+    fn execute(context: &mut KaramelCompilerContext, code: &str) -> Result<(), KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        assert!(parser.parse().is_ok());
+
+        let syntax = SyntaxParser::new(parser.tokens().to_vec());
+        let ast = syntax.parse().unwrap();
+
+        let opcode_compiler = InterpreterCompiler {};
+        opcode_compiler.compile(ast, context)?;
+        unsafe { interpreter::run_vm(context, false, false) }?;
+        Ok(())
+    }
+
+    fn get_variable(context: &KaramelCompilerContext, name: &str) -> KaramelPrimative {
+        let location = context.storages[0].get_variable_location(name).unwrap();
+        (*context.stack[location as usize].deref()).clone()
+    }
+
+    #[test]
+    fn many_variables_and_constants() {
+        let mut code = String::new();
+        for index in 0..1000 {
+            code.push_str(&format!("değişken_{} = {}\n", index, index * 3));
+        }
+        code.push_str("toplam = değişken_0 + değişken_299 + değişken_999\n");
+        code.push_str("döngü eleman içinde [1, 2, 3]:\n    toplam += eleman\n");
+
+        let mut context = KaramelCompilerContext::new();
+        execute(&mut context, &code).unwrap();
+
+        assert!(context.storages[0].constants.len() > 256);
+        assert_eq!(get_variable(&context, "değişken_300"), KaramelPrimative::Number(900.0));
+        assert_eq!(get_variable(&context, "toplam"), KaramelPrimative::Number(3900.0));
+    }
+
+    #[test]
+    fn large_list_literal() {
+        let items = (0..1000).map(|index| index.to_string()).collect::<Vec<String>>().join(", ");
+        let code = format!("veriler = [{}]\nuzunluk = veriler.uzunluk()\nson = veriler[999]", items);
+
+        let mut context = KaramelCompilerContext::new();
+        execute(&mut context, &code).unwrap();
+
+        assert_eq!(get_variable(&context, "uzunluk"), KaramelPrimative::Number(1000.0));
+        assert_eq!(get_variable(&context, "son"), KaramelPrimative::Number(999.0));
+    }
+
+    #[test]
+    fn long_jumps() {
+        let mut code = String::from("sayaç = 0\ndurum = 0\nyanlış ise:\n");
+        for _ in 0..20000 {
+            code.push_str("    sayaç = sayaç + 1\n");
+        }
+        code.push_str("veya:\n    durum = 1\n");
+        code.push_str("döngü i = 0, i < 3, ++i:\n");
+        for _ in 0..20000 {
+            code.push_str("    sayaç = sayaç + 1\n");
+        }
+
+        let mut context = KaramelCompilerContext::new();
+        execute(&mut context, &code).unwrap();
+
+        assert!(context.opcodes.len() > u16::MAX as usize);
+        assert_eq!(get_variable(&context, "durum"), KaramelPrimative::Number(1.0));
+        assert_eq!(get_variable(&context, "sayaç"), KaramelPrimative::Number(60000.0));
+    }
+
+    #[test]
+    fn argument_limit() {
+        let arguments = (0..300).map(|index| index.to_string()).collect::<Vec<String>>().join(", ");
+        let code = format!("gç::satıryaz({})", arguments);
+
+        let mut context = KaramelCompilerContext::new();
+        assert_eq!(execute(&mut context, &code), Err(KaramelErrorType::ArgumentLimitExceeded(MAX_ARGUMENT_SIZE)));
+    }
+
+    #[test]
+    fn item_limit() {
+        let items = vec!["0"; MAX_ITEM_SIZE + 1].join(", ");
+        let mut context = KaramelCompilerContext::new();
+        assert_eq!(execute(&mut context, &format!("veriler = [{}]", items)), Err(KaramelErrorType::ItemLimitExceeded(MAX_ITEM_SIZE)));
+    }
+}
@@ -0,0 +1,63 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::compiler::bytecode::is_bytecode;
+    use crate::karamellib::vm::executer::{self, ExecutionParameters, ExecutionSource};
+    use crate::common::{execute_source};
+
+    #[test]
+    fn save_and_load() {
+        let bytecode = executer::compile_to_bytecode(ExecutionSource::Code(r#"fonk topla(a, b=2, *diğerleri):
+    döndür a + b + diğerleri.uzunluk()
+
+sınıf Sepet:
+    ürünler = []
+    fonk ekle(ad):
+        bu.ürünler.ekle(ad)
+        döndür bu.ürünler.uzunluk()
+
+sepet = Sepet()
+sepet.ekle('elma')
+kare = fonk(x): x * x
+gç::satıryaz(topla(1), topla(1, 5, 7, 8), sepet.ekle('armut'), kare(4))"#.to_string())).unwrap();
+
+        assert!(is_bytecode(&bytecode));
+
+        let status = execute_source(ExecutionSource::Bytecode(bytecode), ExecutionParameters::default());
+        assert!(status.executed);
+        assert_eq!(status.stdout.unwrap().borrow().trim(), "38216");
+    }
+
+    #[test]
+    fn runtime_error() {
+        let bytecode = executer::compile_to_bytecode(ExecutionSource::Code(r#"fonk böl(a, b):
+    b == 0 ise: hata_fırlat 'sıfıra bölme'
+    döndür a / b
+böl(1, 0)"#.to_string())).unwrap();
+
+        let status = execute_source(ExecutionSource::Bytecode(bytecode), ExecutionParameters::default());
+        assert!(!status.executed);
+
+        let stderr = status.stderr.unwrap().borrow().to_string();
+        assert!(stderr.contains("sıfıra bölme"));
+        assert!(stderr.contains("    böl\r\n    ana"));
+    }
+
+    #[test]
+    fn other_version() {
+        let mut bytecode = executer::compile_to_bytecode(ExecutionSource::Code("gç::satıryaz(1)".to_string())).unwrap();
+        bytecode[4] = bytecode[4].wrapping_add(1);
+
+        let status = execute_source(ExecutionSource::Bytecode(bytecode), ExecutionParameters::default());
+        assert!(!status.executed);
+        assert!(status.stderr.unwrap().borrow().contains("sürümü"));
+    }
+
+    #[test]
+    fn compile_error() {
+        assert!(executer::compile_to_bytecode(ExecutionSource::Code("fonk (".to_string())).is_err());
+    }
+}
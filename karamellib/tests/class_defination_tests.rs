@@ -0,0 +1,107 @@
+extern crate karamellib;
+
+#[cfg(test)]
+mod tests {
+    use karamellib::error::{KaramelError, KaramelErrorType};
+
+    use crate::karamellib::parser::*;
+    use crate::karamellib::types::*;
+    use crate::karamellib::syntax::*;
+    use crate::karamellib::compiler::value::KaramelPrimative;
+    use crate::karamellib::compiler::ast::{KaramelAstType, KaramelDictItem, KaramelFunctionArgument};
+    use std::rc::Rc;
+
+    #[warn(unused_macros)]
+    macro_rules! test_compare {
+        ($name:ident, $text:expr, $result:expr) => {
+            #[test]
+            fn $name () {
+                let mut parser = Parser::new($text);
+                match parser.parse() {
+                    Err(_) => assert_eq!(true, false),
+                    _ => ()
+                };
+
+                let syntax = SyntaxParser::new(parser.tokens().to_vec());
+                assert_eq!(syntax.parse(), $result);
+            }
+        };
+    }
+
+    test_compare!(class_def_1, r#"
+sınıf Nokta:
+    x = 0"#, Ok(Rc::new(KaramelAstType::ClassDefination {
+        name: "Nokta".to_string(),
+        fields: [Rc::new(KaramelDictItem {
+            key: Rc::new(KaramelPrimative::Text(Rc::new("x".to_string()))),
+            value: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(0.0))))
+        })].to_vec(),
+        methods: Vec::new()
+    })));
+    test_compare!(class_def_2, r#"
+sinif Nokta:
+    x = 0
+    fonk al():
+        döndür bu.x"#, Ok(Rc::new(KaramelAstType::ClassDefination {
+        name: "Nokta".to_string(),
+        fields: [Rc::new(KaramelDictItem {
+            key: Rc::new(KaramelPrimative::Text(Rc::new("x".to_string()))),
+            value: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(0.0))))
+        })].to_vec(),
+        methods: [Rc::new(KaramelAstType::FunctionDefination {
+            name: "al".to_string(),
+            arguments: Vec::new(),
+            body: Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::Indexer {
+                body: Rc::new(KaramelAstType::Symbol("bu".to_string())),
+                indexer: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new("x".to_string())))))
+            })))
+        })].to_vec()
+    })));
+    test_compare!(class_def_3, r#"
+sınıf Nokta:
+    fonk başlat(x):
+        bu.x = x"#, Ok(Rc::new(KaramelAstType::ClassDefination {
+        name: "Nokta".to_string(),
+        fields: Vec::new(),
+        methods: [Rc::new(KaramelAstType::FunctionDefination {
+            name: "başlat".to_string(),
+            arguments: [KaramelFunctionArgument::new("x")].to_vec(),
+            body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
+                variable: Rc::new(KaramelAstType::Indexer {
+                    body: Rc::new(KaramelAstType::Symbol("bu".to_string())),
+                    indexer: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new("x".to_string())))))
+                }),
+                operator: KaramelOperatorType::Assign,
+                expression: Rc::new(KaramelAstType::Symbol("x".to_string()))
+            }),
+            Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None)))].to_vec()))
+        })].to_vec()
+    })));
+    test_compare!(class_def_4, r#"
+sınıf:
+    x = 0"#, Err(KaramelError {
+        error_type: KaramelErrorType::ClassNameNotDefined,
+        column: 6,
+        line: 1
+    }));
+    test_compare!(class_def_5, r#"
+sınıf Nokta
+    x = 0"#, Err(KaramelError {
+        error_type: KaramelErrorType::ColonMarkMissing,
+        column: 11,
+        line: 1
+    }));
+    test_compare!(class_def_6, r#"
+sınıf Nokta: x = 0"#, Err(KaramelError {
+        error_type: KaramelErrorType::ClassBodyNotValid,
+        column: 14,
+        line: 1
+    }));
+    test_compare!(class_def_7, r#"
+sınıf Nokta:
+    gç::satıryaz('merhaba')"#, Err(KaramelError {
+        error_type: KaramelErrorType::ClassBodyNotValid,
+        column: 27,
+        line: 2
+    }));
+}
@@ -0,0 +1,28 @@
+#![allow(dead_code)]
+
+use karamellib::vm::executer::{code_executer, ExecutionParameters, ExecutionSource, ExecutionStatus};
+
+/* Output of the program is returned with the status */
+pub fn execute_source(source: ExecutionSource, parameters: ExecutionParameters) -> ExecutionStatus {
+    code_executer(ExecutionParameters {
+        source,
+        return_output: true,
+        ..parameters
+    })
+}
+
+pub fn execute_with(code: &str, parameters: ExecutionParameters) -> ExecutionStatus {
+    execute_source(ExecutionSource::Code(code.to_string()), parameters)
+}
+
+pub fn execute(code: &str) -> ExecutionStatus {
+    execute_with(code, ExecutionParameters::default())
+}
+
+pub fn stdout(status: &ExecutionStatus) -> String {
+    status.stdout.as_ref().unwrap().borrow().trim().to_string()
+}
+
+pub fn stderr(status: &ExecutionStatus) -> String {
+    status.stderr.as_ref().unwrap().borrow().to_string()
+}
@@ -69,4 +69,30 @@ mod tests {
         operator: KaramelOperatorType::Or, 
         right: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(10.0))))
     })));
-}
\ No newline at end of file
+
+    test_compare!(conditional_1, "doğru ? 1 : 2", Ok(Rc::new(KaramelAstType::Conditional {
+        condition: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Bool(true)))),
+        true_expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0)))),
+        false_expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(2.0))))
+    })));
+
+    test_compare!(conditional_2, "(10 > 20 ise 'a' yoksa 'b')", Ok(Rc::new(KaramelAstType::Conditional {
+        condition: Rc::new(KaramelAstType::Control {
+            left: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(10.0)))),
+            operator: KaramelOperatorType::GreaterThan,
+            right: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(20.0))))
+        }),
+        true_expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new("a".to_string()))))),
+        false_expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new("b".to_string())))))
+    })));
+
+    test_compare!(conditional_3, "doğru ? 1 : yanlış ? 2 : 3", Ok(Rc::new(KaramelAstType::Conditional {
+        condition: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Bool(true)))),
+        true_expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0)))),
+        false_expression: Rc::new(KaramelAstType::Conditional {
+            condition: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Bool(false)))),
+            true_expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(2.0)))),
+            false_expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(3.0))))
+        })
+    })));
+}
@@ -0,0 +1,149 @@
+extern crate karamellib;
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use crate::karamellib::buildin::native_module::{ArgumentCount, ModuleBuilder};
+    use crate::karamellib::compiler::function::FunctionParameter;
+    use crate::karamellib::vm::convert::{FromKaramel, IntoKaramel};
+    use crate::karamellib::vm::engine::Karamel;
+    use crate::karamellib::vm::limits::ExecutionLimits;
+
+    #[test]
+    fn eval_result() {
+        let mut engine = Karamel::new();
+        assert_eq!(engine.eval::<f64>("1 + 2"), Ok(3.0));
+        assert_eq!(engine.eval::<String>("'kara' + 'mel'"), Ok("karamel".to_string()));
+        assert_eq!(engine.eval::<Vec<f64>>("[1, 2, 3]"), Ok(vec![1.0, 2.0, 3.0]));
+        assert_eq!(engine.eval::<f64>("değer = 1\ndeğer + 1"), Ok(2.0));
+        assert_eq!(engine.eval::<Option<f64>>("değer = 1"), Ok(None));
+        assert_eq!(engine.eval::<Option<f64>>("gç::satıryaz('karamel')"), Ok(None));
+
+        let mut expected = HashMap::new();
+        expected.insert("ad".to_string(), true);
+        assert_eq!(engine.eval::<HashMap<String, bool>>("{'ad': doğru}"), Ok(expected));
+    }
+
+    #[test]
+    fn globals() {
+        let mut engine = Karamel::new();
+        engine.set_global("taban", 10.0);
+        engine.set_global("isimler", vec!["ali", "ayşe"]);
+
+        assert!(engine.eval::<()>("sonuç = taban * isimler.uzunluk()").is_ok());
+        assert_eq!(engine.get_global::<f64>("sonuç"), Ok(20.0));
+
+        /* Variables of the previous code are kept */
+        assert!(engine.eval::<()>("sonuç += 1").is_ok());
+        assert_eq!(engine.get_global::<f64>("sonuç"), Ok(21.0));
+        assert_eq!(engine.get_global::<Vec<String>>("isimler"), Ok(vec!["ali".to_string(), "ayşe".to_string()]));
+    }
+
+    #[test]
+    fn call_function() {
+        let mut engine = Karamel::new();
+        assert!(engine.eval::<()>(r#"kayıtlar = []
+fonk topla(a, b):
+    döndür a + b
+
+fonk kaydet(liste_, değer):
+    liste_.ekle(değer)
+    döndür liste_.uzunluk()"#).is_ok());
+
+        assert_eq!(engine.call::<f64, _>("topla", (1.0, 2.0)), Ok(3.0));
+        assert_eq!(engine.call::<String, _>("topla", ("a", "b")), Ok("ab".to_string()));
+
+        assert_eq!(engine.eval::<f64>("kaydet(kayıtlar, 'ilk')"), Ok(1.0));
+        assert_eq!(engine.eval::<f64>("kaydet(kayıtlar, 'ikinci')"), Ok(2.0));
+        assert_eq!(engine.get_global::<Vec<String>>("kayıtlar"), Ok(vec!["ilk".to_string(), "ikinci".to_string()]));
+        assert_eq!(engine.call::<f64, _>("kaydet", (vec!["ilk"], "ikinci")), Ok(2.0));
+
+        /* Functions of the previous codes are kept */
+        assert!(engine.eval::<()>("fonk üç_kat(x): döndür topla(x, topla(x, x))").is_ok());
+        assert_eq!(engine.call::<f64, _>("üç_kat", (2.0,)), Ok(6.0));
+        assert_eq!(engine.call::<f64, _>("topla", (2.0, 2.0)), Ok(4.0));
+    }
+
+    #[test]
+    fn registered_function() {
+        let counter = Rc::new(Cell::new(0.0));
+        let mut engine = Karamel::new();
+
+        let total = counter.clone();
+        engine.register_function("say", move |parameter: FunctionParameter| {
+            for argument in parameter.iter() {
+                total.set(total.get() + f64::from_karamel(*argument)?);
+            }
+            Ok(total.get().into_karamel())
+        });
+
+        engine.register_function("uygula", |parameter: FunctionParameter| {
+            let mut arguments = parameter.iter();
+            let function = *arguments.next().unwrap();
+            let value = *arguments.next().unwrap();
+            parameter.call(function, &[value])
+        });
+
+        assert_eq!(engine.eval::<f64>("say(1, 2)\nsay(3)"), Ok(6.0));
+        assert_eq!(counter.get(), 6.0);
+        assert!(engine.eval::<()>("sonuç = say(4)").is_ok());
+        assert_eq!(engine.get_global::<f64>("sonuç"), Ok(10.0));
+        assert_eq!(engine.eval::<f64>("uygula(fonk(x): x * 2, 21)"), Ok(42.0));
+    }
+
+    #[test]
+    fn registered_module() {
+        let mut engine = Karamel::new();
+        engine.register_module(ModuleBuilder::new("ölçü")
+            .function("çarp", ArgumentCount::Exact(2), |parameter: FunctionParameter| {
+                let mut arguments = parameter.iter();
+                let left = f64::from_karamel(*arguments.next().unwrap())?;
+                let right = f64::from_karamel(*arguments.next().unwrap())?;
+                Ok((left * right).into_karamel())
+            })
+            .function("sürüm", ArgumentCount::Exact(0), |_| Ok("1.0".into_karamel()))
+            .build());
+
+        assert_eq!(engine.eval::<f64>("ölçü::çarp(2, 3) + ölçü::carp(1, 1)"), Ok(7.0));
+        assert_eq!(engine.eval::<String>("ölçü::surum()"), Ok("1.0".to_string()));
+
+        let error = engine.eval::<f64>("ölçü::çarp(2)").unwrap_err();
+        assert!(error.contains("'çarp' fonksiyon parametreleri eşleşmiyor. 2 adet beklenirken 1 adet bulundu"), "{}", error);
+    }
+
+    #[test]
+    fn errors() {
+        let mut engine = Karamel::new();
+        assert_eq!(engine.call::<(), _>("topla", ()), Err("Fonksiyon çağrısı sadece çalışan program içinden yapılabilir".to_string()));
+        assert!(engine.eval::<()>("fonk topla(a, b):").is_err());
+        assert!(engine.eval::<()>("hata_fırlat 'bozuk'").unwrap_err().contains("bozuk"));
+        assert_eq!(engine.get_global::<f64>("yok"), Err("'yok' değişkeni bulunamadı".to_string()));
+        assert_eq!(engine.eval::<f64>("'yazı'"), Err("'yazı' türündeki değer 'sayı' türüne çevrilemez".to_string()));
+
+        assert!(engine.eval::<()>("fonk böl(a, b):\n    b == 0 ise: hata_fırlat 'sıfıra bölme'\n    döndür a / b").is_ok());
+        assert!(engine.call::<f64, _>("bilinmeyen", ()).unwrap_err().contains("bilinmeyen"));
+
+        let error = engine.call::<f64, _>("böl", (1.0, 0.0)).unwrap_err();
+        assert!(error.contains("sıfıra bölme"));
+        assert!(error.contains("böl [<kod>:1:16]"));
+
+        /* Engine could be used after the error */
+        assert_eq!(engine.call::<f64, _>("böl", (1.0, 2.0)), Ok(0.5));
+        assert!(engine.call::<f64, _>("böl", (1.0,)).is_err());
+    }
+
+    #[test]
+    fn limits() {
+        let mut engine = Karamel::new();
+        engine.limits = ExecutionLimits { max_opcodes: Some(10_000), ..ExecutionLimits::default() };
+
+        assert!(engine.eval::<()>("fonk sonsuz_döngü():\n    sonsuz:\n        kır").is_ok());
+        assert!(engine.eval::<()>("sonsuz:\n    değer = 1").unwrap_err().contains("(#187)"));
+
+        assert!(engine.eval::<()>("fonk bekle():\n    sonsuz:\n        değer = 1").is_ok());
+        assert!(engine.call::<(), _>("bekle", ()).unwrap_err().contains("(#187)"));
+    }
+}
@@ -112,4 +112,23 @@ mod tests {
         })
     })));
     test_compare!(func_call_12, "gç::satıryaz", Ok(Rc::new(KaramelAstType::ModulePath(["gç".to_string(), "satıryaz".to_string()].to_vec()))));
-}
\ No newline at end of file
+    test_compare!(func_call_13, "bağlan(1, zamanaşımı=5)", Ok(Rc::new(KaramelAstType::FuncCall {
+        func_name_expression: Rc::new(KaramelAstType::Symbol("bağlan".to_string())),
+        arguments: [Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0)))),
+            Rc::new(KaramelAstType::NamedArgument {
+                name: "zamanaşımı".to_string(),
+                expression: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(5.0))))
+            })].to_vec(),
+        assign_to_temp: Cell::new(false)
+    })));
+    test_compare!(func_call_14, "bağlan(a=1, 2)", Err(KaramelError {
+        error_type: KaramelErrorType::PositionalArgumentAfterNamed,
+        column: 14,
+        line: 0
+    }));
+    test_compare!(func_call_15, "bağlan(a=1, a=2)", Err(KaramelError {
+        error_type: KaramelErrorType::ArgumentAlreadyDefined("a".to_string()),
+        column: 16,
+        line: 0
+    }));
+}
@@ -8,7 +8,8 @@ mod tests {
     use crate::karamellib::types::*;
     use crate::karamellib::syntax::*;
     use crate::karamellib::compiler::value::KaramelPrimative;
-    use crate::karamellib::compiler::ast::KaramelAstType;
+    use crate::karamellib::compiler::ast::{KaramelAstType, KaramelFunctionArgument};
+    use std::cell::Cell;
     use std::rc::Rc;
 
     #[warn(unused_macros)]
@@ -44,7 +45,7 @@ fonk test():
 fonk test(a):
     erhan=123"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
         name: "test".to_string(),
-        arguments: ["a".to_string()].to_vec(),
+        arguments: [KaramelFunctionArgument::new("a")].to_vec(),
         body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
             variable: Rc::new(KaramelAstType::Symbol("erhan".to_string())),
             operator: KaramelOperatorType::Assign,
@@ -56,7 +57,7 @@ fonk test(a):
 fonk test(a, b    ,   c):
     erhan=123"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
         name: "test".to_string(),
-        arguments: ["a".to_string(), "b".to_string(), "c".to_string()].to_vec(),
+        arguments: [KaramelFunctionArgument::new("a"), KaramelFunctionArgument::new("b"), KaramelFunctionArgument::new("c")].to_vec(),
         body: Rc::new(KaramelAstType::Block([Rc::new(KaramelAstType::Assignment {
             variable: Rc::new(KaramelAstType::Symbol("erhan".to_string())),
             operator: KaramelOperatorType::Assign,
@@ -177,4 +178,59 @@ fonk test():
     }),
     Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::None)))].to_vec()))
 })));
-}
\ No newline at end of file
+test_compare!(func_def_17, r#"topla = fonk(a, b): a + b"#, Ok(Rc::new(KaramelAstType::Assignment {
+    variable: Rc::new(KaramelAstType::Symbol("topla".to_string())),
+    operator: KaramelOperatorType::Assign,
+    expression: Rc::new(KaramelAstType::Lambda {
+        arguments: [KaramelFunctionArgument::new("a"), KaramelFunctionArgument::new("b")].to_vec(),
+        body: Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::Binary {
+            left: Rc::new(KaramelAstType::Symbol("a".to_string())),
+            operator: KaramelOperatorType::Addition,
+            right: Rc::new(KaramelAstType::Symbol("b".to_string()))
+        }))),
+        storage_index: Cell::new(0)
+    })
+})));
+test_compare!(func_def_18, r#"sabit = fonk(): 1"#, Ok(Rc::new(KaramelAstType::Assignment {
+    variable: Rc::new(KaramelAstType::Symbol("sabit".to_string())),
+    operator: KaramelOperatorType::Assign,
+    expression: Rc::new(KaramelAstType::Lambda {
+        arguments: Vec::new(),
+        body: Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0)))))),
+        storage_index: Cell::new(0)
+    })
+})));
+test_compare!(func_def_19, r#"topla = fonk(a, b) a + b"#, Err(KaramelError {
+    error_type: KaramelErrorType::ColonMarkMissing,
+    column: 20,
+    line: 0
+}));
+test_compare!(func_def_20, r#"topla = fonk(a, b):"#, Err(KaramelError {
+    error_type: KaramelErrorType::FunctionConditionBodyNotFound,
+    column: 19,
+    line: 0
+}));
+test_compare!(func_def_21, r#"
+fonk test(a, b=1, *c):
+    döndür a"#, Ok(Rc::new(KaramelAstType::FunctionDefination {
+    name: "test".to_string(),
+    arguments: [KaramelFunctionArgument::new("a"),
+        KaramelFunctionArgument::with_default("b", Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0))))),
+        KaramelFunctionArgument::variadic("c")].to_vec(),
+    body: Rc::new(KaramelAstType::Return(Rc::new(KaramelAstType::Symbol("a".to_string()))))
+})));
+test_compare!(func_def_22, r#"
+fonk test(a=1, b):
+    döndür a"#, Err(KaramelError {
+    error_type: KaramelErrorType::DefaultArgumentOrderNotValid,
+    column: 17,
+    line: 1
+}));
+test_compare!(func_def_23, r#"
+fonk test(*a, b):
+    döndür a"#, Err(KaramelError {
+    error_type: KaramelErrorType::VariadicArgumentMustBeLast,
+    column: 15,
+    line: 1
+}));
+}
@@ -0,0 +1,55 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::karamellib::vm::executer::ExecutionParameters;
+    use crate::karamellib::vm::limits::InterruptHandle;
+    use crate::common::{execute_with, stderr};
+
+    /* Returns execution status and the error output */
+    fn execute(code: &str, interrupt: InterruptHandle) -> (bool, String) {
+        let status = execute_with(code, ExecutionParameters { interrupt: Some(interrupt), ..ExecutionParameters::default() });
+        (status.executed, stderr(&status))
+    }
+
+    #[test]
+    fn interrupt_from_another_thread() {
+        let interrupt = InterruptHandle::new();
+        let handle = interrupt.clone();
+        let worker = thread::spawn(move || execute(r#"sonsuz:
+    dene:
+        değer = 1
+    yakala:
+        değer = 2"#, handle));
+
+        thread::sleep(Duration::from_millis(50));
+        interrupt.interrupt();
+
+        let (executed, stderr) = worker.join().unwrap();
+        assert!(!executed);
+        assert!(stderr.contains("(#190)"));
+    }
+
+    #[test]
+    fn interrupt_recursion() {
+        let interrupt = InterruptHandle::new();
+        interrupt.interrupt();
+
+        let (executed, stderr) = execute(r#"fonk topla(değer):
+    döndür değer + 1
+topla(1)"#, interrupt.clone());
+        assert!(!executed);
+        assert!(stderr.contains("(#190)"));
+
+        interrupt.reset();
+        let (executed, _) = execute(r#"fonk topla(değer):
+    döndür değer + 1
+topla(1)"#, interrupt);
+        assert!(executed);
+    }
+}
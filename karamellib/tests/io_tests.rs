@@ -0,0 +1,98 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::karamellib::vm::engine::Karamel;
+    use crate::karamellib::vm::executer::{ExecutionParameters, KaramelSession, SessionParameters};
+    use crate::karamellib::vm::io::{BufferedIo, KaramelIo};
+    use crate::common::{execute, execute_with};
+
+    /* Every write is kept separately to check that the output is streamed */
+    #[derive(Default)]
+    struct RecordingIo {
+        writes: RefCell<Vec<String>>,
+        errors: RefCell<Vec<String>>
+    }
+
+    impl KaramelIo for RecordingIo {
+        fn read_line(&self) -> Option<String> {
+            Some("karamel".to_string())
+        }
+
+        fn write(&self, data: &str) {
+            self.writes.borrow_mut().push(data.to_string());
+        }
+
+        fn write_err(&self, data: &str) {
+            self.errors.borrow_mut().push(data.to_string());
+        }
+    }
+
+    #[test]
+    fn scripted_input() {
+        let io = Rc::new(BufferedIo::with_input(&["ali", "  42 "]));
+        let status = execute_with(r#"isim = gç::satıroku()
+yaş = gç::satıroku()
+son = gç::satıroku()
+gç::yaz(isim, '-', yaş, '-', son)"#, ExecutionParameters { io: Some(io.clone()), ..ExecutionParameters::default() });
+
+        assert!(status.executed);
+        assert_eq!(io.stdout(), "\"ali\"\"-\"\"42\"\"-\"boş");
+
+        /* Output is written to the given io */
+        assert!(status.stdout.is_none());
+    }
+
+    #[test]
+    fn streamed_output() {
+        let io = Rc::new(RecordingIo::default());
+        let status = execute_with("döngü i = 0, i < 3, i++:\n    gç::yaz(i)\ngç::satıryaz(gç::satıroku())", ExecutionParameters { io: Some(io.clone()), ..ExecutionParameters::default() });
+
+        assert!(status.executed);
+        assert_eq!(*io.writes.borrow(), vec!["0", "1", "2", "\"karamel\"\r\n"]);
+        assert!(io.errors.borrow().is_empty());
+    }
+
+    #[test]
+    fn errors_are_written() {
+        let io = Rc::new(RecordingIo::default());
+        let status = execute_with("gç::yaz('önce')\nhata_fırlat 'bozuk'", ExecutionParameters { io: Some(io.clone()), ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        assert_eq!(*io.writes.borrow(), vec!["\"önce\""]);
+        assert_eq!(io.errors.borrow().len(), 1);
+        assert!(io.errors.borrow()[0].contains("bozuk"));
+
+        let status = execute_with("değer = (", ExecutionParameters { io: Some(io.clone()), ..ExecutionParameters::default() });
+        assert!(!status.compiled);
+        assert_eq!(io.errors.borrow().len(), 2);
+    }
+
+    #[test]
+    fn returned_output() {
+        let status = execute("gç::yaz('karamel')\nhata_fırlat 'bozuk'");
+        assert_eq!(status.stdout.as_ref().unwrap().borrow().to_string(), "\"karamel\"");
+        assert!(status.stderr.as_ref().unwrap().borrow().contains("bozuk"));
+    }
+
+    #[test]
+    fn session_and_engine() {
+        let io = Rc::new(BufferedIo::with_input(&["1", "2"]));
+        let mut session = KaramelSession::new(SessionParameters { io: Some(io.clone()), ..SessionParameters::default() });
+
+        assert!(session.execute("ilk = gç::satıroku()").executed);
+        assert!(session.execute("gç::yaz(ilk, gç::satıroku())").executed);
+        assert_eq!(io.stdout(), "\"1\"\"2\"");
+
+        let io = Rc::new(BufferedIo::with_input(&["dünya"]));
+        let mut engine = Karamel::new();
+        engine.set_io(io.clone());
+        assert_eq!(engine.eval::<String>("gç::yaz('merhaba')\n'merhaba ' + gç::satıroku()"), Ok("merhaba dünya".to_string()));
+        assert_eq!(io.stdout(), "\"merhaba\"");
+    }
+}
@@ -0,0 +1,97 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::karamellib::vm::executer::ExecutionParameters;
+    use crate::karamellib::vm::limits::ExecutionLimits;
+    use crate::common::{execute_with, stderr};
+
+    #[test]
+    fn opcode_budget() {
+        let status = execute_with(r#"gç::satıryaz('başladı')
+sonsuz:
+    değer = 1"#, ExecutionParameters { limits: ExecutionLimits { max_opcodes: Some(10_000), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#187)"));
+        assert!(status.stdout.unwrap().borrow().contains("başladı"));
+    }
+
+    #[test]
+    fn opcode_budget_is_not_catchable() {
+        let status = execute_with(r#"dene:
+    sonsuz:
+        değer = 1
+yakala:
+    gç::satıryaz('yakalandı')"#, ExecutionParameters { limits: ExecutionLimits { max_opcodes: Some(10_000), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#187)"));
+        assert!(status.stdout.unwrap().borrow().is_empty());
+    }
+
+    #[test]
+    fn opcode_budget_with_recursion() {
+        let status = execute_with(r#"fonk tekrarla(değer):
+    değer > 0 ise:
+        tekrarla(değer - 1)
+        tekrarla(değer - 1)
+tekrarla(30)"#, ExecutionParameters { limits: ExecutionLimits { max_opcodes: Some(10_000), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#187)"));
+    }
+
+    #[test]
+    fn enough_budget() {
+        let status = execute_with(r#"toplam = 0
+döngü i = 0, i < 100, ++i:
+    toplam += i
+hataayıklama::doğrula(toplam, 4950)"#, ExecutionParameters { limits: ExecutionLimits { max_opcodes: Some(10_000), timeout: Some(Duration::from_secs(60)), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(status.executed);
+    }
+
+    #[test]
+    fn call_depth() {
+        let status = execute_with(r#"fonk tekrarla(değer):
+    döndür 1 + tekrarla(değer + 1)
+tekrarla(0)"#, ExecutionParameters { limits: ExecutionLimits { max_call_depth: Some(100), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#186)"));
+        assert!(stderr(&status).contains("tekrarla (100 kez)"));
+        assert!(stderr(&status).contains("tekrarla [<kod>:1:23] (99 kez)"));
+    }
+
+    #[test]
+    fn heap_objects() {
+        let status = execute_with(r#"veriler = []
+sonsuz:
+    veriler.ekle([1, 2])"#, ExecutionParameters { limits: ExecutionLimits { max_heap_objects: Some(1_000), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#188)"));
+    }
+
+    #[test]
+    fn unreachable_heap_objects() {
+        let status = execute_with(r#"döngü i = 0, i < 10000, ++i:
+    veriler = [1, 2]"#, ExecutionParameters { limits: ExecutionLimits { max_heap_objects: Some(1_000), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(status.executed);
+    }
+
+    #[test]
+    fn timeout() {
+        let status = execute_with(r#"sonsuz:
+    değer = 1"#, ExecutionParameters { limits: ExecutionLimits { timeout: Some(Duration::from_millis(50)), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#189)"));
+    }
+}
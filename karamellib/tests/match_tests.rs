@@ -0,0 +1,98 @@
+extern crate karamellib;
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use karamellib::error::{KaramelError, KaramelErrorType};
+    use karamellib::compiler::ast::{KaramelAstType, KaramelMatchCase, KaramelPattern};
+    use karamellib::compiler::value::KaramelPrimative;
+
+    use crate::karamellib::parser::*;
+    use crate::karamellib::syntax::*;
+    #[warn(unused_macros)]
+    macro_rules! test_compare {
+        ($name:ident, $text:expr, $result:expr) => {
+            #[test]
+            fn $name () {
+                let mut parser = Parser::new($text);
+                match parser.parse() {
+                    Err(_) => assert_eq!(true, false),
+                    _ => ()
+                };
+
+                let syntax = SyntaxParser::new(parser.tokens().to_vec());
+                assert_eq!(syntax.parse(), $result);
+            }
+        };
+    }
+
+    fn number(value: f64) -> Rc<KaramelAstType> {
+        Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(value))))
+    }
+
+    test_compare!(match_1, r#"eşleştir a:
+    1: 2
+    _: 3"#, Ok(Rc::new(KaramelAstType::Match {
+        expression: Rc::new(KaramelAstType::Symbol("a".to_string())),
+        cases: vec![
+            Rc::new(KaramelMatchCase {
+                pattern: Rc::new(KaramelPattern::Literal(number(1.0))),
+                guard: None,
+                body: number(2.0)
+            }),
+            Rc::new(KaramelMatchCase {
+                pattern: Rc::new(KaramelPattern::Wildcard),
+                guard: None,
+                body: number(3.0)
+            })
+        ]
+    })));
+
+    test_compare!(match_2, r#"eslestir a:
+    [b, -1, *c] eger b:
+        b"#, Ok(Rc::new(KaramelAstType::Match {
+        expression: Rc::new(KaramelAstType::Symbol("a".to_string())),
+        cases: vec![
+            Rc::new(KaramelMatchCase {
+                pattern: Rc::new(KaramelPattern::List {
+                    items: vec![Rc::new(KaramelPattern::Variable("b".to_string())), Rc::new(KaramelPattern::Literal(number(-1.0)))],
+                    rest: Some("c".to_string())
+                }),
+                guard: Some(Rc::new(KaramelAstType::Symbol("b".to_string()))),
+                body: Rc::new(KaramelAstType::Symbol("b".to_string()))
+            })
+        ]
+    })));
+
+    test_compare!(match_3, r#"eşleştir a:
+    {'tür': [b]}: b"#, Ok(Rc::new(KaramelAstType::Match {
+        expression: Rc::new(KaramelAstType::Symbol("a".to_string())),
+        cases: vec![
+            Rc::new(KaramelMatchCase {
+                pattern: Rc::new(KaramelPattern::Dict(vec![(
+                    Rc::new(KaramelPrimative::Text(Rc::new("tür".to_string()))),
+                    Rc::new(KaramelPattern::List {
+                        items: vec![Rc::new(KaramelPattern::Variable("b".to_string()))],
+                        rest: None
+                    })
+                )])),
+                guard: None,
+                body: Rc::new(KaramelAstType::Symbol("b".to_string()))
+            })
+        ]
+    })));
+
+    test_compare!(match_4, r#"eşleştir a: 1"#, Err(KaramelError {
+        error_type: KaramelErrorType::MatchCaseNotFound,
+        column: 13,
+        line: 0
+    }));
+
+    test_compare!(match_5, r#"eşleştir a:
+    [*b, c]: c"#, Err(KaramelError {
+        error_type: KaramelErrorType::PatternNotValid,
+        column: 10,
+        line: 1
+    }));
+}
@@ -0,0 +1,54 @@
+extern crate karamellib;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::parser::*;
+    use crate::karamellib::compiler::*;
+    use crate::karamellib::vm::*;
+    use crate::karamellib::syntax::*;
+    use crate::karamellib::constants::HEAP_INITIAL_THRESHOLD;
+
+    fn execute(context: &mut KaramelCompilerContext, code: &str) {
+        let mut parser = Parser::new(code);
+        assert!(parser.parse().is_ok());
+
+        let syntax = SyntaxParser::new(parser.tokens().to_vec());
+        let ast = syntax.parse().unwrap();
+
+        let opcode_compiler = InterpreterCompiler {};
+        assert!(opcode_compiler.compile(ast, context).is_ok());
+        assert!(unsafe { interpreter::run_vm(context, false, false) }.is_ok());
+    }
+
+    fn get_variable(context: &KaramelCompilerContext, name: &str) -> KaramelPrimative {
+        let location = context.storages[0].get_variable_location(name).unwrap();
+        (*context.stack[location as usize].deref()).clone()
+    }
+
+    #[test]
+    fn concat_loop_memory() {
+        let mut context = KaramelCompilerContext::new();
+        execute(&mut context, r#"metin = ''
+toplam = 0
+döngü i = 0, i < 1000000, ++i:
+    metin = 'karamel' + $' {i}'
+    toplam += metin.uzunluk()"#);
+
+        assert_eq!(get_variable(&context, "metin"), KaramelPrimative::Text(std::rc::Rc::new("karamel 999999".to_string())));
+        assert_eq!(get_variable(&context, "toplam"), KaramelPrimative::Number(13888890.0));
+        assert!(context.heap.len() <= HEAP_INITIAL_THRESHOLD);
+    }
+
+    #[test]
+    fn nested_objects_are_alive() {
+        let mut context = KaramelCompilerContext::new();
+        execute(&mut context, r#"liste_değeri = []
+döngü i = 0, i < 50000, ++i:
+    geçici = [i, {'değer': $'{i}'}]
+    i mod 10000 == 0 ise:
+        liste_değeri.ekle(geçici)"#);
+
+        assert_eq!(format!("{}", get_variable(&context, "liste_değeri")), r#"[[0, {"değer": "0"}], [10000, {"değer": "10000"}], [20000, {"değer": "20000"}], [30000, {"değer": "30000"}], [40000, {"değer": "40000"}]]"#);
+        assert!(context.heap.len() <= HEAP_INITIAL_THRESHOLD);
+    }
+}
@@ -0,0 +1,151 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::vm::executer::ExecutionParameters;
+    use crate::karamellib::vm::limits::ExecutionLimits;
+    use crate::common::{execute, execute_with, stdout, stderr};
+
+    #[test]
+    fn map_and_filter() {
+        let status = execute(r#"fonk kare(x): döndür x * x
+gç::satıryaz([1, 2, 3, 4].haritala(kare).filtrele(fonk(x): x > 4))"#);
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "[9, 16]");
+    }
+
+    #[test]
+    fn native_function_as_callback() {
+        let status = execute("gç::satıryaz([1, 2].haritala(gç::satıryaz))");
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "1\r\n2\r\n[boş, boş]");
+    }
+
+    #[test]
+    fn execution_continues_after_callback() {
+        let status = execute(r#"fonk topla(sayılar):
+    toplam = 0
+    sayılar.haritala(fonk(x): x).filtrele(fonk(x): doğru)
+    döngü i = 0, i < sayılar.uzunluk(), i++:
+        toplam += sayılar[i]
+    döndür toplam
+
+gç::satıryaz(topla([1, 2, 3]) + topla([4]))"#);
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "10");
+    }
+
+    #[test]
+    fn uncaught_error_inside_callback() {
+        let status = execute(r#"fonk bozuk(x):
+    hata_fırlat 'bozuk'
+
+fonk dış(sayılar):
+    döndür sayılar.haritala(bozuk)
+
+dış([1])"#);
+
+        assert!(!status.executed);
+        let stderr = stderr(&status);
+        assert!(stderr.contains("bozuk [<kod>:1:4]"));
+        assert!(stderr.contains("dış [<kod>:4:27]"));
+        assert!(stderr.contains("ana [<kod>:6:3]"));
+    }
+
+    #[test]
+    fn caught_error_is_not_reported_later() {
+        let status = execute(r#"fonk bozuk(x): hata_fırlat 'bozuk'
+dene:
+    [1].haritala(bozuk)
+yakala:
+    gç::satıryaz('yakalandı')
+hata_fırlat 'ikinci'"#);
+
+        assert!(!status.executed);
+        assert_eq!(stdout(&status), "\"yakalandı\"");
+        assert!(stderr(&status).contains("ana [<kod>:5:0]"));
+    }
+
+    #[test]
+    fn wrong_callback_arguments() {
+        let status = execute("[1].haritala(fonk(a, b): a)");
+        assert!(!status.executed);
+
+        let status = execute("[1].haritala(1)");
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#148)"));
+    }
+
+    #[test]
+    fn sort_error() {
+        let status = execute("[1, 'a'].sırala()");
+        assert!(!status.executed);
+
+        let status = execute("[1, 2].sırala(fonk(a, b): 'a')");
+        assert!(!status.executed);
+    }
+
+    #[test]
+    fn endless_callback_is_limited() {
+        let status = execute_with(r#"fonk bitmeyen(x):
+    sonsuz:
+        x += 1
+[1].haritala(bitmeyen)"#, ExecutionParameters { limits: ExecutionLimits { max_opcodes: Some(100_000), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#187)"));
+    }
+
+    #[test]
+    fn callbacks_with_heap_collection() {
+        let status = execute(r#"sayılar = []
+döngü i = 0, i < 12000, i++:
+    sayılar.ekle(i)
+sonuç = sayılar.haritala(fonk(x): [x, x + 1]).filtrele(fonk(x): x[0] >= 11990)
+gç::satıryaz(sonuç.uzunluk() + sonuç[0][1])"#);
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "12001");
+    }
+
+    #[test]
+    fn collection_inside_callbacks() {
+        /* Source list is not at the stack, unreachable objects of the callbacks are released while the list is iterated */
+        let status = execute_with(r#"fonk sayılar(): döndür [[1], [2], [3]]
+fonk üret(x):
+    döngü i = 0, i < 2000, i++:
+        geçici = [i]
+    döndür [x[0] * 2]
+gç::satıryaz(sayılar().haritala(üret).filtrele(fonk(x): x[0] > 2))"#, ExecutionParameters { limits: ExecutionLimits { max_heap_objects: Some(1_000), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "[[4], [6]]");
+    }
+
+    #[test]
+    fn source_cleared_inside_callbacks() {
+        /* Items are not at the list anymore, copied items must be alive until the calls end */
+        let status = execute_with(r#"fonk üret():
+    döngü i = 0, i < 2000, i++:
+        geçici = [i]
+fonk doldur():
+    veri = []
+    döngü i = 0, i < 4, i++:
+        veri.ekle([i, [i * 10]])
+    döndür veri
+veri = doldur()
+gç::satıryaz(veri.haritala(fonk(x): [veri.temizle(), üret(), x[1][0] + 1][2]))
+veri = doldur()
+gç::satıryaz(veri.filtrele(fonk(x): [veri.temizle(), üret(), x[0] > 1][2]))
+veri = doldur()
+gç::satıryaz(veri.sırala(fonk(a, b): [veri.temizle(), üret(), b[0] - a[0]][2]))"#, ExecutionParameters { limits: ExecutionLimits { max_heap_objects: Some(1_000), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "[1, 11, 21, 31]\r\n[[2, [20]], [3, [30]]]\r\n[[3, [30]], [2, [20]], [1, [10]], [0, [0]]]");
+    }
+}
@@ -0,0 +1,81 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use crate::karamellib::parser::*;
+    use crate::karamellib::syntax::*;
+    use crate::karamellib::compiler::*;
+    use crate::karamellib::vm::executer::ExecutionParameters;
+    use crate::common::{execute, execute_with, stdout};
+
+    static CODE: &str = r#"fonk hesapla(değer):
+    döndür değer * (2 * 3 + 4)
+    gç::satıryaz('ulaşılamaz')
+
+toplam = 0
+sayaç = 0
+sonsuz:
+    sayaç += 1
+    toplam += hesapla(sayaç)
+    sayaç == 3 ise:
+        kır
+        gç::satıryaz('ulaşılamaz')
+
+kopya = toplam
+gç::satıryaz(kopya)
+gç::satıryaz('kara' + 'mel', 1 / 0 > 1)"#;
+
+    fn compile(code: &str, optimize: bool) -> KaramelCompilerContext {
+        let mut parser = Parser::new(code);
+        assert!(parser.parse().is_ok());
+
+        let syntax = SyntaxParser::new(parser.tokens().to_vec());
+        let ast = syntax.parse().unwrap();
+
+        let mut context = KaramelCompilerContext::new();
+        context.optimize = optimize;
+        assert!(InterpreterCompiler {}.compile(ast, &mut context).is_ok());
+        context
+    }
+
+    #[test]
+    fn same_output() {
+        let status = execute(CODE);
+        let unoptimized = execute_with(CODE, ExecutionParameters { optimize: false, ..ExecutionParameters::default() });
+        assert!(status.executed && unoptimized.executed);
+
+        let output = stdout(&status);
+        assert_eq!(output, stdout(&unoptimized));
+        assert!(output.starts_with("60"));
+        assert!(output.contains("karamel"));
+        assert!(output.contains("doğru"));
+        assert!(!output.contains("ulaşılamaz"));
+    }
+
+    #[test]
+    fn smaller_code() {
+        assert!(compile(CODE, true).opcodes.len() < compile(CODE, false).opcodes.len());
+    }
+
+    #[test]
+    fn folded_constants() {
+        let context = compile("değer = 2 * 3 + 4", true);
+        let constants: Vec<KaramelPrimative> = context.storages[0].constants.iter().map(|constant| (*constant.deref()).clone()).collect();
+        assert!(constants.contains(&KaramelPrimative::Number(10.0)));
+
+        let context = compile("değer = 'kara' + 'mel'", true);
+        let constants: Vec<KaramelPrimative> = context.storages[0].constants.iter().map(|constant| (*constant.deref()).clone()).collect();
+        assert!(constants.contains(&KaramelPrimative::Text(Rc::new("karamel".to_string()))));
+    }
+
+    #[test]
+    fn unoptimized_constants() {
+        let context = compile("değer = 2 * 3 + 4", false);
+        let constants: Vec<KaramelPrimative> = context.storages[0].constants.iter().map(|constant| (*constant.deref()).clone()).collect();
+        assert_eq!(constants, vec![KaramelPrimative::Number(2.0), KaramelPrimative::Number(3.0), KaramelPrimative::Number(4.0)]);
+    }
+}
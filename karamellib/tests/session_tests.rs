@@ -0,0 +1,138 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::vm::executer::{KaramelSession, SessionParameters};
+    use crate::karamellib::vm::limits::ExecutionLimits;
+    use crate::common::{stdout, stderr};
+
+    fn session() -> KaramelSession {
+        KaramelSession::new(SessionParameters { return_output: true, ..SessionParameters::default() })
+    }
+
+    #[test]
+    fn variables_are_kept() {
+        let mut session = session();
+        assert!(session.execute("sayaç = 1\nisimler = ['ali']").executed);
+        assert!(session.execute("sayaç += 2\nisimler.ekle('ayşe')").executed);
+
+        let status = session.execute("gç::satıryaz(sayaç, isimler)");
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "3[\"ali\", \"ayşe\"]");
+        assert_eq!(session.get_variable::<f64>("sayaç"), Ok(3.0));
+        assert!(session.get_variable::<f64>("yok").is_err());
+    }
+
+    #[test]
+    fn values_are_owned_by_the_caller() {
+        let mut session = session();
+        let status = session.execute("x = ['merhaba' + ' dünya', 'karamel']");
+        assert!(status.executed);
+        assert!(status.memory_output.is_none());
+
+        let value = session.get_variable::<Vec<String>>("x");
+        assert!(session.execute("x = boş\ndöngü i = 0, i < 20000, i++:\n    y = [i]").executed);
+        assert_eq!(value, Ok(vec!["merhaba dünya".to_string(), "karamel".to_string()]));
+    }
+
+    #[test]
+    fn functions_and_classes_are_kept() {
+        let mut session = session();
+        assert!(session.execute("fonk kare(x): döndür x * x").executed);
+        assert!(session.execute(r#"sınıf Nokta:
+    x = 0
+
+    fonk başlat(x):
+        bu.x = x"#).executed);
+        assert!(session.execute("fonk küp(x): döndür kare(x) * x").executed);
+
+        let status = session.execute("gç::satıryaz(kare(4) + küp(2) + Nokta(5).x)");
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "29");
+    }
+
+    #[test]
+    fn output_belongs_to_the_execution() {
+        let mut session = session();
+        assert_eq!(stdout(&session.execute("gç::satıryaz('ilk')")), "\"ilk\"");
+        assert_eq!(stdout(&session.execute("gç::satıryaz('ikinci')")), "\"ikinci\"");
+    }
+
+    #[test]
+    fn failed_compilation_is_not_added() {
+        let mut session = session();
+        assert!(session.execute("fonk değer(): döndür 1\nsonuç = 10").executed);
+
+        /* Syntax error */
+        let status = session.execute("sonuç = (");
+        assert!(!status.compiled);
+
+        /* Function is already defined, variables of the code are removed too */
+        let status = session.execute("yeni = 1\nfonk değer(): döndür 2");
+        assert!(!status.compiled);
+        assert!(stderr(&status).contains("değer"));
+        assert!(session.get_variable::<f64>("yeni").is_err());
+
+        let status = session.execute("gç::satıryaz(değer() + sonuç)");
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "11");
+    }
+
+    #[test]
+    fn error_location_after_failed_compilation() {
+        let mut session = session();
+        assert!(!session.execute("yeni = 1\nfonk değer(): döndür 1\nfonk değer(): döndür 2").compiled);
+
+        let status = session.execute("ilk = 1\nikinci = 2\nhata_fırlat 'bozuk'");
+        assert!(status.compiled);
+        assert!(!status.executed);
+
+        let stderr = stderr(&status);
+        assert!(stderr.contains("hata_fırlat 'bozuk'\r\n^ [2:0]"));
+        assert!(stderr.contains("ana [<kod>:2:0]"));
+    }
+
+    #[test]
+    fn runtime_error_does_not_stop_the_session() {
+        let mut session = session();
+        assert!(session.execute("fonk bozuk(x):\n    hata_fırlat 'bozuk'\nsayı_ = 1").executed);
+
+        let status = session.execute("fonk dış(): döndür bozuk(1)\ndış()");
+        assert!(status.compiled);
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("bozuk [<kod>:1:4]"));
+
+        let status = session.execute("sayı_ += 1\nhata_fırlat 'ikinci'");
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("ikinci"));
+        assert!(!stderr(&status).contains("bozuk"));
+
+        let status = session.execute("gç::satıryaz(sayı_)");
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "2");
+
+        let status = session.execute("dene:\n    dış()\nyakala hata:\n    gç::satıryaz(hata.mesaj)");
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "\"bozuk\"");
+    }
+
+    #[test]
+    fn limits_are_applied_to_every_execution() {
+        let mut session = KaramelSession::new(SessionParameters {
+            return_output: true,
+            limits: ExecutionLimits { max_opcodes: Some(10_000), ..ExecutionLimits::default() },
+            ..SessionParameters::default()
+        });
+
+        for _ in 0..3 {
+            let status = session.execute("döngü i = 0, i < 100, i++:\n    değer = i");
+            assert!(status.executed);
+        }
+
+        let status = session.execute("sonsuz:\n    değer = 1");
+        assert!(stderr(&status).contains("(#187)"));
+        assert!(session.execute("gç::satıryaz(değer)").executed);
+    }
+}
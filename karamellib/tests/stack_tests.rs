@@ -0,0 +1,85 @@
+extern crate karamellib;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::parser::*;
+    use crate::karamellib::compiler::*;
+    use crate::karamellib::vm::*;
+    use crate::karamellib::syntax::*;
+    use crate::karamellib::error::KaramelErrorType;
+
+    fn execute(context: &mut KaramelCompilerContext, code: &str) -> Result<(), KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        assert!(parser.parse().is_ok());
+
+        let syntax = SyntaxParser::new(parser.tokens().to_vec());
+        let ast = syntax.parse().unwrap();
+
+        let opcode_compiler = InterpreterCompiler {};
+        opcode_compiler.compile(ast, context)?;
+        unsafe { interpreter::run_vm(context, false, false) }?;
+        Ok(())
+    }
+
+    #[test]
+    fn recursion_depth() {
+        let mut context = KaramelCompilerContext::new();
+        context.max_call_depth = 100;
+
+        let result = execute(&mut context, r#"fonk say(n):
+    döndür 1 + say(n + 1)
+say(0)"#);
+        assert_eq!(result, Err(KaramelErrorType::StackOverflow(vec!["ana".to_string(), "say (100 kez)".to_string()])));
+    }
+
+    #[test]
+    fn call_chain() {
+        let mut context = KaramelCompilerContext::new();
+        context.set_stack_size(200);
+
+        let result = execute(&mut context, r#"fonk topla(n):
+    n == 0 ise:
+        döndür 0
+    döndür n + topla(n - 1)
+fonk hesapla(n):
+    döndür topla(n)
+hesapla(10)
+hesapla(1000)"#);
+
+        match result {
+            Err(KaramelErrorType::StackOverflow(chain)) => {
+                assert_eq!(chain.len(), 3);
+                assert_eq!(chain[0], "ana");
+                assert_eq!(chain[1], "hesapla");
+                assert!(chain[2].starts_with("topla ("));
+            },
+            _ => assert!(false, "Stack overflow expected")
+        };
+    }
+
+    #[test]
+    fn small_stack() {
+        let items = (0..300).map(|index| index.to_string()).collect::<Vec<String>>().join(", ");
+        let code = format!("veriler = [{}]", items);
+
+        let mut context = KaramelCompilerContext::new();
+        context.set_stack_size(100);
+        assert_eq!(execute(&mut context, &code), Err(KaramelErrorType::StackOverflow(vec!["ana".to_string()])));
+
+        let mut context = KaramelCompilerContext::new();
+        context.set_stack_size(400);
+        assert_eq!(execute(&mut context, &code), Ok(()));
+    }
+
+    #[test]
+    fn method_call_statement() {
+        let mut context = KaramelCompilerContext::new();
+        context.set_stack_size(100);
+        assert_eq!(execute(&mut context, r#"veriler = []
+sayaç = 0
+döngü sayaç < 200:
+    veriler.ekle(sayaç)
+    veriler.temizle()
+    sayaç += 1"#), Ok(()));
+    }
+}
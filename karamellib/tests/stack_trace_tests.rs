@@ -0,0 +1,121 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::parser::*;
+    use crate::karamellib::compiler::*;
+    use crate::karamellib::compiler::source_map::SourcePosition;
+    use crate::karamellib::vm::*;
+    use crate::karamellib::syntax::*;
+    use crate::karamellib::error::KaramelErrorType;
+    use crate::common::{execute, stdout, stderr};
+
+    fn run(context: &mut KaramelCompilerContext, code: &str) -> Result<(), KaramelErrorType> {
+        let mut parser = Parser::new(code);
+        assert!(parser.parse().is_ok());
+
+        let syntax = SyntaxParser::new(parser.tokens().to_vec());
+        let ast = syntax.parse().unwrap();
+        context.source_map.add_file("test.k".to_string(), code.to_string(), syntax.positions());
+
+        let opcode_compiler = InterpreterCompiler {};
+        opcode_compiler.compile(ast, context)?;
+        unsafe { interpreter::run_vm(context, false, false) }?;
+        Ok(())
+    }
+
+    #[test]
+    fn runtime_error_location() {
+        let mut context = KaramelCompilerContext::new();
+        let result = run(&mut context, r#"fonk kontrol(değer):
+    hataayıklama::doğrula(değer, 10)
+
+fonk hesapla(değer):
+    sonuç = değer * 2
+    kontrol(sonuç)
+
+hesapla(1)
+hesapla(2)"#);
+
+        assert!(result.is_err());
+        assert_eq!(context.error_location.map(|location| location.position), Some(SourcePosition { line: 1, column: 25 }));
+
+        let frames = context.stack_trace.iter().map(|frame| (frame.function.as_str(), frame.path.as_str(), frame.position.line)).collect::<Vec<_>>();
+        assert_eq!(frames, vec![("kontrol", "test.k", 1), ("hesapla", "test.k", 5), ("ana", "test.k", 7)]);
+    }
+
+    #[test]
+    fn single_statement_bodies() {
+        let mut context = KaramelCompilerContext::new();
+        let result = run(&mut context, r#"sayı_1 = 0
+sayı_1 == 0 ise:
+    hata_fırlat 'hata'"#);
+
+        assert_eq!(result, Err(KaramelErrorType::UserError("hata".to_string())));
+        assert_eq!(context.error_location.map(|location| location.position), Some(SourcePosition { line: 2, column: 4 }));
+        assert_eq!(context.stack_trace.len(), 1);
+    }
+
+    #[test]
+    fn handled_error_has_no_trace() {
+        let status = execute(r#"dene:
+    hata_fırlat 'hata'
+yakala:
+    gç::satıryaz('yakalandı')"#);
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "\"yakalandı\"");
+        assert!(stderr(&status).is_empty());
+    }
+
+    #[test]
+    fn module_load_error_location() {
+        let mut context = KaramelCompilerContext::new();
+        let result = run(&mut context, r#"gç::satıryaz('başla')
+bilinmeyen_modül yükle"#);
+
+        assert!(result.is_err());
+        assert_eq!(context.error_location.map(|location| location.position), Some(SourcePosition { line: 1, column: 0 }));
+    }
+
+    #[test]
+    fn module_load_error_message() {
+        let result = execute("gç::satıryaz('başla')\nbilinmeyen_modül yükle");
+        assert!(!result.compiled);
+
+        let stderr = stderr(&result);
+        assert!(stderr.contains("bilinmeyen_modül yükle\r\n^ [1:0]"), "{}", stderr);
+    }
+
+    #[test]
+    fn error_message_with_source_line() {
+        let result = execute(r#"fonk böl(a, b):
+    b == 0 ise: hata_fırlat 'sıfıra bölme'
+    döndür a / b
+böl(1, 0)"#);
+
+        assert!(!result.executed);
+        let stderr = stderr(&result);
+        assert!(stderr.contains("b == 0 ise: hata_fırlat 'sıfıra bölme'"));
+        assert!(stderr.contains("Çağrı yığını"));
+        assert!(stderr.contains("böl [<kod>:1:16]"));
+        assert!(stderr.contains("ana [<kod>:3:3]"));
+    }
+
+    #[test]
+    fn operation_error_location() {
+        let location = |code: &str| {
+            let mut context = KaramelCompilerContext::new();
+            context.strict_types = true;
+            assert!(run(&mut context, code).is_err());
+            context.error_location.map(|location| location.position)
+        };
+
+        assert_eq!(location("gç::satıryaz('a' - 1)"), Some(SourcePosition { line: 0, column: 17 }));
+        assert_eq!(location("değer = 1\nsonuç = [değer, değer < 'a']"), Some(SourcePosition { line: 1, column: 22 }));
+        assert_eq!(location("sayılar = [1, 2]\nsayılar[::0]"), Some(SourcePosition { line: 1, column: 7 }));
+        assert_eq!(location("fonk topla(a, b):\n    döndür a + b\n\nsonuç = 1 + topla(1)"), Some(SourcePosition { line: 3, column: 17 }));
+    }
+}
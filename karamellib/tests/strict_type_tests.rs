@@ -0,0 +1,82 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::vm::executer::ExecutionParameters;
+    use crate::common::{execute, execute_with, stdout, stderr};
+
+    macro_rules! strict_error_test {
+        ($name:ident, $code:expr, $message:expr) => {
+            #[test]
+            fn $name () {
+                let status = execute_with($code, ExecutionParameters { strict_types: true, ..ExecutionParameters::default() });
+                assert!(!status.executed);
+                assert!(stderr(&status).contains($message), "{}", stderr(&status));
+
+                /* Lenient mode keeps producing 'boş' */
+                let status = execute($code);
+                assert!(status.executed);
+            }
+        };
+    }
+
+    strict_error_test!(subraction, "değer = 'a' - 1", "(#195) '-' işlemi 'yazı' ve 'sayı' türleri ile kullanılamaz");
+    strict_error_test!(addition, "değer = 'a' + 1", "(#195) '+' işlemi 'yazı' ve 'sayı' türleri ile kullanılamaz");
+    strict_error_test!(multiply, "değer = 2 * 'a'", "(#195) '*' işlemi 'sayı' ve 'yazı' türleri ile kullanılamaz");
+    strict_error_test!(division, "değer = [1] / 2", "(#195) '/' işlemi 'liste' ve 'sayı' türleri ile kullanılamaz");
+    strict_error_test!(module, "değer = doğru mod 2", "(#195) 'mod' işlemi 'bool' ve 'sayı' türleri ile kullanılamaz");
+    strict_error_test!(greater_than, "değer = [1] > 2", "(#195) 'karşılaştırma' işlemi 'liste' ve 'sayı' türleri ile kullanılamaz");
+    strict_error_test!(greater_equal_than, "değer = 'a' >= 'b'", "(#195) 'karşılaştırma' işlemi 'yazı' ve 'yazı' türleri ile kullanılamaz");
+    strict_error_test!(less_than, "değer = boş < 2", "(#195) 'karşılaştırma' işlemi 'sayı' ve 'boş' türleri ile kullanılamaz");
+    strict_error_test!(increment, "değer = 'a'\ndeğer++", "(#196) '++' işlemi 'yazı' türü ile kullanılamaz");
+    strict_error_test!(decrement, "değer = [1]\ndeğer--", "(#196) '--' işlemi 'liste' türü ile kullanılamaz");
+
+    #[test]
+    fn lenient_mode_result() {
+        let status = execute("gç::satıryaz('a' - 1)");
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "boş");
+    }
+
+    #[test]
+    fn explicit_conversions() {
+        let status = execute_with(r#"gç::satıryaz('5'.sayı() + 1)
+gç::satıryaz((5).yazı() + '1')
+gç::satıryaz('ab' * 2)"#, ExecutionParameters { strict_types: true, ..ExecutionParameters::default() });
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "6\r\n\"51\"\r\n\"abab\"");
+    }
+
+    #[test]
+    fn matching_types() {
+        let status = execute_with(r#"gç::satıryaz(1 + 2, 'a' + 'b', 4 - 1, 6 / 2, 7 mod 2, 2 > 1, 2 >= 2, 1 < 2, [1] == [1], 'a' != 1)"#, ExecutionParameters { strict_types: true, ..ExecutionParameters::default() });
+        assert!(status.executed);
+    }
+
+    #[test]
+    fn type_error_is_catched() {
+        let status = execute_with(r#"dene:
+    değer = 'a' - 1
+yakala hata:
+    gç::satıryaz(hata.kod)"#, ExecutionParameters { strict_types: true, ..ExecutionParameters::default() });
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "195");
+    }
+
+    #[test]
+    fn error_location() {
+        let status = execute_with(r#"fonk çıkar(a, b):
+    döndür a - b
+
+çıkar(1, 'a')"#, ExecutionParameters { strict_types: true, ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        let stderr = stderr(&status);
+        assert!(stderr.contains("çıkar [<kod>:1:13]"));
+        assert!(stderr.contains("ana [<kod>:3:5]"));
+    }
+}
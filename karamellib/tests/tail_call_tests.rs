@@ -0,0 +1,72 @@
+extern crate karamellib;
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::karamellib::vm::executer::ExecutionParameters;
+    use crate::karamellib::vm::limits::ExecutionLimits;
+    use crate::common::{execute, execute_with, stdout, stderr};
+
+    #[test]
+    fn million_recursion() {
+        let status = execute(r#"fonk say(n, toplam):
+    n == 0 ise:
+        döndür toplam
+    döndür say(n - 1, toplam + 1)
+
+gç::satıryaz(say(1000000, 0))"#);
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "1000000");
+    }
+
+    #[test]
+    fn million_recursion_with_default_argument() {
+        let status = execute(r#"fonk topla(n, toplam=0):
+    n == 0 ise: döndür toplam
+    döndür topla(n - 1, toplam=toplam + 2)
+
+gç::satıryaz(topla(1000000))"#);
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "2000000");
+    }
+
+    #[test]
+    fn non_tail_recursion() {
+        let status = execute(r#"fonk say(n):
+    n == 0 ise: döndür 0
+    döndür 1 + say(n - 1)
+
+gç::satıryaz(say(1000000))"#);
+
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#186)"));
+    }
+
+    #[test]
+    fn recursion_inside_try() {
+        /* Error of the inner call is catched by the caller */
+        let status = execute(r#"fonk say(n):
+    n == 0 ise: hata_fırlat 'bitti'
+    dene:
+        döndür say(n - 1)
+    yakala:
+        döndür n
+
+gç::satıryaz(say(5))"#);
+
+        assert!(status.executed);
+        assert_eq!(stdout(&status), "1");
+    }
+
+    #[test]
+    fn endless_recursion_is_limited() {
+        let status = execute_with(r#"fonk say(n): döndür say(n + 1)
+say(0)"#, ExecutionParameters { limits: ExecutionLimits { max_opcodes: Some(1_000_000), ..ExecutionLimits::default() }, ..ExecutionParameters::default() });
+
+        assert!(!status.executed);
+        assert!(stderr(&status).contains("(#187)"));
+    }
+}
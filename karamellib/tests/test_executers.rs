@@ -1,11 +1,14 @@
 extern crate karamellib;
 
+mod common;
+
 #[cfg(test)]
 mod tests {
     use std::fs::Metadata;
 
     use crate::karamellib::vm::*;
-    use crate::karamellib::{vm::executer::{ExecutionParameters, ExecutionSource}};
+    use crate::karamellib::vm::executer::{ExecutionParameters, ExecutionSource};
+    use crate::common::{execute_source};
 
     enum ExecuterType {
         File,
@@ -28,7 +31,7 @@ mod tests {
         }
     }
 
-    fn executer(executer_type: ExecuterType) -> Result<(), String> {
+    fn executer(executer_type: ExecuterType, bytecode: bool, optimize: bool) -> Result<(), String> {
         use std::env;
         use std::fs;
         use std::path::Path;
@@ -51,15 +54,22 @@ mod tests {
 
                     match path.path().to_str() {
                         Some(path_str) => {
-                            let parameters = ExecutionParameters {
-                                source: ExecutionSource::File(path_str.to_string()),
-                                return_opcode: false,
-                                return_output: false,
-                                dump_opcode: false,
-                                dump_memory: false
+                            /* Compiled program is saved and loaded again */
+                            let source = ExecutionSource::File(path_str.to_string());
+                            let source = match bytecode {
+                                true => match executer::compile_to_bytecode(source) {
+                                    Ok(data) => ExecutionSource::Bytecode(data),
+                                    Err(_) => {
+                                        if is_pass {
+                                            failed_cases.push(format!("# {} failed ({})", path_str, "Not compiled".red()));
+                                        }
+                                        continue;
+                                    }
+                                },
+                                false => source
                             };
 
-                            let result = executer::code_executer(parameters);
+                            let result = execute_source(source, ExecutionParameters { optimize, ..ExecutionParameters::default() });
                             match result.compiled && result.executed {
                                 true => {
                                     if !is_pass {
@@ -90,12 +100,27 @@ mod tests {
 
     #[test]
     fn test_file_executer() -> Result<(), String> {
-        executer(ExecuterType::File)
+        executer(ExecuterType::File, false, true)
     }
 
 
     #[test]
     fn test_module_executer() -> Result<(), String> {
-        executer(ExecuterType::Module)
+        executer(ExecuterType::Module, false, true)
+    }
+
+    #[test]
+    fn test_file_bytecode_executer() -> Result<(), String> {
+        executer(ExecuterType::File, true, true)
+    }
+
+    #[test]
+    fn test_module_bytecode_executer() -> Result<(), String> {
+        executer(ExecuterType::Module, true, true)
+    }
+
+    #[test]
+    fn test_file_unoptimized_executer() -> Result<(), String> {
+        executer(ExecuterType::File, false, false)
     }
 }
\ No newline at end of file
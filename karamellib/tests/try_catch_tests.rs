@@ -0,0 +1,69 @@
+extern crate karamellib;
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use karamellib::error::{KaramelError, KaramelErrorType};
+    use karamellib::compiler::ast::KaramelAstType;
+    use karamellib::compiler::value::KaramelPrimative;
+
+    use crate::karamellib::parser::*;
+    use crate::karamellib::syntax::*;
+    #[warn(unused_macros)]
+    macro_rules! test_compare {
+        ($name:ident, $text:expr, $result:expr) => {
+            #[test]
+            fn $name () {
+                let mut parser = Parser::new($text);
+                match parser.parse() {
+                    Err(_) => assert_eq!(true, false),
+                    _ => ()
+                };
+
+                let syntax = SyntaxParser::new(parser.tokens().to_vec());
+                assert_eq!(syntax.parse(), $result);
+            }
+        };
+    }
+
+    test_compare!(try_1, r#"dene:
+    1
+yakala hata:
+    2"#, Ok(Rc::new(KaramelAstType::TryCatch {
+        body: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0)))),
+        variable: Some("hata".to_string()),
+        catch_body: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(2.0))))
+    })));
+
+    test_compare!(try_2, r#"dene: 1
+yakala: 2"#, Ok(Rc::new(KaramelAstType::TryCatch {
+        body: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0)))),
+        variable: None,
+        catch_body: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(2.0))))
+    })));
+
+    test_compare!(try_3, r#"dene:
+    1"#, Err(KaramelError {
+        error_type: KaramelErrorType::CatchBlockNotFound,
+        column: 5,
+        line: 1
+    }));
+
+    test_compare!(try_4, r#"dene:
+    1
+yakala 'hata':
+    2"#, Err(KaramelError {
+        error_type: KaramelErrorType::CatchVariableNotValid,
+        column: 13,
+        line: 2
+    }));
+
+    test_compare!(throw_1, r#"hata_fırlat 'hata'"#, Ok(Rc::new(KaramelAstType::Throw(Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Text(Rc::new("hata".to_string())))))))));
+
+    test_compare!(throw_2, r#"hata_firlat"#, Err(KaramelError {
+        error_type: KaramelErrorType::ThrowValueNotFound,
+        column: 11,
+        line: 0
+    }));
+}
@@ -105,5 +105,21 @@ mod tests {
         operator: KaramelOperatorType::Subtraction,
         right: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0))))
     })));
+    test_compare!(slice_1, "liste_[1:-1]", Ok(Rc::new(KaramelAstType::Slice {
+        body: Rc::new(KaramelAstType::Symbol("liste_".to_string())),
+        start: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(1.0)))),
+        end: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(-1.0)))),
+        step: Rc::new(KaramelAstType::None)
+    })));
+    test_compare!(slice_2, "liste_[::2]", Ok(Rc::new(KaramelAstType::Slice {
+        body: Rc::new(KaramelAstType::Symbol("liste_".to_string())),
+        start: Rc::new(KaramelAstType::None),
+        end: Rc::new(KaramelAstType::None),
+        step: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(2.0))))
+    })));
+    test_compare!(slice_3, "liste_[-1]", Ok(Rc::new(KaramelAstType::Indexer {
+        body: Rc::new(KaramelAstType::Symbol("liste_".to_string())),
+        indexer: Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Number(-1.0))))
+    })));
     //test_compare!(unary_19, "doğru değil", Ok(Rc::new(KaramelAstType::SuffixUnary(KaramelOperatorType::Not, Rc::new(KaramelAstType::Primative(Rc::new(KaramelPrimative::Bool(true)))))));
 }
\ No newline at end of file
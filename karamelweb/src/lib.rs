@@ -1,11 +1,66 @@
 extern crate karamellib;
 
-use karamellib::{compiler::KaramelPrimative, vm::executer::{ExecutionParameters, ExecutionSource}};
+use std::rc::Rc;
+use std::time::Duration;
+
+use karamellib::{compiler::KaramelPrimative, vm::executer::{ExecutionParameters, ExecutionSource}, vm::io::KaramelIo, vm::limits::ExecutionLimits};
 use wasm_bindgen::prelude::*;
 use js_sys::*;
 
+/* Browser tab should not be frozen by the user code */
+const MAX_OPCODES: u64 = 100_000_000;
+const MAX_CALL_DEPTH: usize = 1024;
+const MAX_HEAP_OBJECTS: usize = 1_000_000;
+const TIMEOUT_IN_MS: u64 = 5_000;
+
+/* 'Instant' is not available at the browser */
+fn browser_clock() -> Duration {
+    Duration::from_secs_f64(Date::now() / 1000.0)
+}
+
+/* Program's input and output are redirected to the 'write', 'write_err' and 'read_line' functions of the object */
+struct JsIo {
+    object: Object
+}
+
+impl JsIo {
+    fn function(&self, name: &str) -> Option<Function> {
+        Reflect::get(self.object.as_ref(), &JsValue::from(name)).ok()?.dyn_into::<Function>().ok()
+    }
+
+    fn call(&self, name: &str, data: &str) {
+        if let Some(function) = self.function(name) {
+            let _ = function.call1(self.object.as_ref(), &JsValue::from_str(data));
+        }
+    }
+}
+
+impl KaramelIo for JsIo {
+    fn read_line(&self) -> Option<String> {
+        self.function("read_line")?.call0(self.object.as_ref()).ok()?.as_string()
+    }
+
+    fn write(&self, data: &str) {
+        self.call("write", data);
+    }
+
+    fn write_err(&self, data: &str) {
+        self.call("write_err", data);
+    }
+}
+
 #[wasm_bindgen]
 pub fn execute_code(name: &str) -> Object {
+    execute(name, None)
+}
+
+/// Output is written to the object while the program is running, instead of being returned with the response
+#[wasm_bindgen]
+pub fn execute_code_with_io(name: &str, io: Object) -> Object {
+    execute(name, Some(Rc::new(JsIo { object: io })))
+}
+
+fn execute(name: &str, io: Option<Rc<dyn KaramelIo>>) -> Object {
     let response = js_sys::Object::new();
 
     /* JS referance object */
@@ -21,7 +76,17 @@ pub fn execute_code(name: &str) -> Object {
         return_opcode: true,
         return_output: true,
         dump_opcode: true,
-        dump_memory: true
+        dump_memory: true,
+        optimize: true,
+        limits: ExecutionLimits {
+            max_opcodes: Some(MAX_OPCODES),
+            max_call_depth: Some(MAX_CALL_DEPTH),
+            max_heap_objects: Some(MAX_HEAP_OBJECTS),
+            timeout: Some(Duration::from_millis(TIMEOUT_IN_MS)),
+            clock: browser_clock
+        },
+        io,
+        ..ExecutionParameters::default()
     };
 
     let result = karamellib::vm::executer::code_executer(parameters);